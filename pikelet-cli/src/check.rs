@@ -1,15 +1,16 @@
 use codespan_reporting::diagnostic::Severity;
 use codespan_reporting::files::SimpleFiles;
 use codespan_reporting::term::termcolor::{BufferedStandardStream, ColorChoice};
-use pikelet::lang::{core, surface};
-use pikelet::pass::surface_to_core;
+use pikelet::lang::core;
+use pikelet::pass::{staging, surface_to_core};
+use std::cell::RefCell;
 use std::io::Write;
 use std::path::PathBuf;
 
 /// Check some Pikelet source files.
 #[derive(structopt::StructOpt)]
 pub struct Options {
-    /// Validate the elaborated core language.
+    /// Validate the elaborated core language, before and after staging.
     #[structopt(long = "validate-core")]
     validate_core: bool,
     /// The Pikelet source files to be checked.
@@ -24,8 +25,8 @@ pub fn run(options: Options) -> anyhow::Result<()> {
 
     let globals = core::Globals::default();
     let (messages_tx, messages_rx) = crossbeam_channel::unbounded();
-    let mut files = SimpleFiles::new();
-    let mut surface_to_core = surface_to_core::State::new(&globals, messages_tx.clone());
+    let files = RefCell::new(SimpleFiles::new());
+    let mut surface_to_core = surface_to_core::State::new(&globals, &files, messages_tx.clone());
     let mut core_typing = match options.validate_core {
         true => Some(core::typing::State::new(&globals, messages_tx.clone())),
         false => None,
@@ -34,22 +35,24 @@ pub fn run(options: Options) -> anyhow::Result<()> {
     let mut is_ok = true;
 
     for file_name in &options.file_names {
-        let source = std::fs::read_to_string(file_name)?;
-        let file_id = files.add(file_name.display().to_string(), source);
-        let file = files.get(file_id).unwrap();
-
-        let surface_term = surface::Term::from_str(file_id, file.source(), &messages_tx);
-
-        let (core_term, _) = surface_to_core.synth_type(&surface_term);
+        // Files are elaborated in the same way as imports, so that files that
+        // are imported by other files are only elaborated once.
+        let (core_term, _) = surface_to_core.synth_file_type(file_name);
         if let Some(core_typing) = &mut core_typing {
             let _ = core_typing.synth_type(&core_term);
+            let _ = core_typing.synth_type(&staging::stage_term(&globals, &core_term));
         }
 
         for message in messages_rx.try_iter() {
             let diagnostic = message.to_diagnostic(&pretty_alloc);
             is_ok &= diagnostic.severity < Severity::Error;
 
-            codespan_reporting::term::emit(&mut writer, &reporting_config, &files, &diagnostic)?;
+            codespan_reporting::term::emit(
+                &mut writer,
+                &reporting_config,
+                &*files.borrow(),
+                &diagnostic,
+            )?;
             writer.flush()?;
         }
     }
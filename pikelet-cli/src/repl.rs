@@ -4,6 +4,7 @@ use codespan_reporting::term::termcolor::{BufferedStandardStream, ColorChoice};
 use pikelet::lang::{core, surface};
 use pikelet::pass::{surface_to_core, surface_to_pretty};
 use rustyline::error::ReadlineError;
+use std::cell::RefCell;
 use std::io::Write;
 use std::sync::Arc;
 
@@ -73,14 +74,14 @@ pub fn run(options: Options) -> anyhow::Result<()> {
 
     let globals = core::Globals::default();
     let (messages_tx, messages_rx) = crossbeam_channel::unbounded();
-    let mut files = SimpleFiles::new();
-    let mut state = surface_to_core::State::new(&globals, messages_tx.clone());
+    let files = RefCell::new(SimpleFiles::new());
+    let mut state = surface_to_core::State::new(&globals, &files, messages_tx.clone());
 
     'repl: loop {
-        let (file_id, file) = match editor.readline(&options.prompt) {
+        let (file_id, line) = match editor.readline(&options.prompt) {
             Ok(line) => {
-                let file_id = files.add("<input>", line);
-                (file_id, files.get(file_id).unwrap())
+                let file_id = files.borrow_mut().add("<input>".to_owned(), line.clone());
+                (file_id, line)
             }
             Err(ReadlineError::Interrupted) => {
                 println!("Interrupted!");
@@ -91,7 +92,7 @@ pub fn run(options: Options) -> anyhow::Result<()> {
         };
 
         if !options.no_history {
-            editor.add_history_entry(file.source());
+            editor.add_history_entry(&line);
         }
 
         // TODO: Parse REPL commands
@@ -105,15 +106,20 @@ pub fn run(options: Options) -> anyhow::Result<()> {
         // :local        <name> = <term>  define a local definition in the REPL context
         // :q :quit                       quit the repl
         // :t :type      <term>           infer the type of a term
-        let surface_term = surface::Term::from_str(file_id, file.source(), &messages_tx);
-        let (core_term, r#type) = state.synth_type(&surface_term);
+        let surface_term = surface::Term::from_str(file_id, &line, &messages_tx);
+        let (core_term, r#type) = state.synth_top_level_type(&surface_term);
 
         let mut is_ok = true;
         for message in messages_rx.try_iter() {
             let diagnostic = message.to_diagnostic(&pretty_alloc);
             is_ok &= diagnostic.severity < Severity::Error;
 
-            codespan_reporting::term::emit(&mut writer, &reporting_config, &files, &diagnostic)?;
+            codespan_reporting::term::emit(
+                &mut writer,
+                &reporting_config,
+                &*files.borrow(),
+                &diagnostic,
+            )?;
             writer.flush()?;
         }
 
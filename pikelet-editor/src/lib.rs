@@ -94,6 +94,7 @@ fn view_term<M: 'static>(term: &pikelet::lang::core::Term) -> Element<M> {
     match &term.data {
         TermData::Global(name) => Text::new(name).into(),
         TermData::Local(_) => Text::new("todo").into(),
+        TermData::Meta(_) => Text::new("todo").into(),
 
         TermData::Ann(term, r#type) => Row::new()
             .push(view_term(term))
@@ -101,22 +102,49 @@ fn view_term<M: 'static>(term: &pikelet::lang::core::Term) -> Element<M> {
             .push(view_term(r#type))
             .into(),
 
-        TermData::TypeType(UniverseLevel(level)) => Row::new()
+        TermData::Let(_, _, _) => Text::new("todo").into(),
+
+        TermData::TypeType(UniverseLevel::Finite(level)) => Row::new()
             .push(Text::new(format!("Univ^{}", level))) // TODO: superscript?
             .into(),
+        TermData::TypeType(UniverseLevel::Omega(level)) => Row::new()
+            .push(Text::new(format!("Univω^{}", level))) // TODO: superscript?
+            .into(),
         TermData::Lift(term, UniverseOffset(offset)) => Row::new()
             .push(view_term(term))
             .push(Text::new(format!("^{}", offset)))
             .into(),
 
-        TermData::FunctionType(_, _, _) => Text::new("todo").into(),
+        TermData::FunctionType(_, _, _, _, _) => Text::new("todo").into(),
         TermData::FunctionTerm(_, _) => Text::new("todo").into(),
+        TermData::Fix(_, _, _) => Text::new("todo").into(),
         TermData::FunctionElim(_, _) => Text::new("todo").into(),
 
         TermData::RecordTerm(_) => Text::new("todo").into(),
         TermData::RecordType(_) => Text::new("todo").into(),
         TermData::RecordElim(_, _) => Text::new("todo").into(),
 
+        TermData::EnumType(_) => Text::new("todo").into(),
+        TermData::EnumTerm(_) => Text::new("todo").into(),
+        TermData::EnumElim(_, _) => Text::new("todo").into(),
+
+        TermData::VariantType(_) => Text::new("todo").into(),
+        TermData::VariantTerm(_, _) => Text::new("todo").into(),
+        TermData::VariantElim(_, _) => Text::new("todo").into(),
+
+        TermData::InductiveType(_, _) => Text::new("todo").into(),
+        TermData::InductiveTerm(_, _, _) => Text::new("todo").into(),
+        TermData::InductiveElim(_, _, _) => Text::new("todo").into(),
+
+        TermData::BoolElim(_, _, _) => Text::new("todo").into(),
+        TermData::EqType(_, _, _) => Text::new("todo").into(),
+        TermData::EqTerm => Text::new("todo").into(),
+        TermData::EqElim(_, _, _) => Text::new("todo").into(),
+
+        TermData::CodeType(_) => Text::new("todo").into(),
+        TermData::CodeTerm(_) => Text::new("todo").into(),
+        TermData::CodeElim(_) => Text::new("todo").into(),
+
         TermData::ArrayTerm(_) => Text::new("todo").into(),
         TermData::ListTerm(_) => Text::new("todo").into(),
 
@@ -31,17 +31,20 @@ impl Location {
         Location::FileRange(file_id, range.into())
     }
 
+    /// Merge two locations into a location that covers both of them.
+    ///
+    /// Generated locations are absorbed by file ranges. Locations in different
+    /// files (which can occur when terms are imported from other files) cannot
+    /// be merged, so in this case the first location is returned.
     pub fn merge(self, other: Location) -> Location {
         match (self, other) {
-            (Location::Generated, Location::Generated) => Location::Generated,
-            (Location::FileRange(file_id0, range0), Location::FileRange(file_id1, range1)) => {
-                assert_eq!(
-                    file_id0, file_id1,
-                    "tried to merge source locations with different file ids"
-                );
+            (Location::FileRange(file_id0, range0), Location::FileRange(file_id1, range1))
+                if file_id0 == file_id1 =>
+            {
                 Location::FileRange(file_id0, Range::merge(range0, range1))
             }
-            (_, _) => panic!("incompatible source ranges"),
+            (Location::Generated, location) => location,
+            (location, _) => location,
         }
     }
 }
@@ -93,3 +96,85 @@ impl<Data> Located<Data> {
         Located::new(Location::generated(), data)
     }
 }
+
+/// Whether a function input is supplied explicitly, or inferred by the elaborator.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Plicity {
+    /// Explicit inputs, eg. `Fun (A : Type) -> A`.
+    Explicit,
+    /// Implicit inputs, eg. `Fun {A : Type} -> A`.
+    Implicit,
+}
+
+/// The number of times that a variable may be used at runtime.
+///
+/// Also known as: multiplicities, quantities.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Usage {
+    /// Erased variables, eg. `Fun (0 A : Type) -> A -> A`.
+    ///
+    /// These can only be used in types, and are removed at runtime.
+    Zero,
+    /// Linear variables, eg. `Fun (1 file : File) -> File`.
+    ///
+    /// These must be used exactly once at runtime.
+    One,
+    /// Unrestricted variables, eg. `Fun (x : S32) -> S32`.
+    ///
+    /// These can be used any number of times, and are the default.
+    Many,
+}
+
+impl Usage {
+    /// Join the usages of two alternatives, where only one of them will be
+    /// used at runtime.
+    pub fn join(self, other: Usage) -> Usage {
+        if self == other {
+            self
+        } else {
+            Usage::Many
+        }
+    }
+
+    /// Returns `true` if a variable that is declared with this usage can be
+    /// used the given number of times.
+    pub fn allows(self, uses: Usage) -> bool {
+        match self {
+            Usage::Zero | Usage::One => self == uses,
+            Usage::Many => true,
+        }
+    }
+}
+
+impl std::ops::Add<Usage> for Usage {
+    type Output = Usage;
+
+    fn add(self, other: Usage) -> Usage {
+        match (self, other) {
+            (Usage::Zero, usage) | (usage, Usage::Zero) => usage,
+            (_, _) => Usage::Many,
+        }
+    }
+}
+
+impl std::ops::Mul<Usage> for Usage {
+    type Output = Usage;
+
+    fn mul(self, other: Usage) -> Usage {
+        match (self, other) {
+            (Usage::Zero, _) | (_, Usage::Zero) => Usage::Zero,
+            (Usage::One, usage) | (usage, Usage::One) => usage,
+            (Usage::Many, Usage::Many) => Usage::Many,
+        }
+    }
+}
+
+impl std::fmt::Display for Usage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Usage::Zero => write!(f, "0"),
+            Usage::One => write!(f, "1"),
+            Usage::Many => write!(f, "ω"),
+        }
+    }
+}
@@ -7,12 +7,29 @@ use std::collections::BTreeMap;
 use std::fmt;
 use std::sync::Arc;
 
-use crate::lang::Located;
+use crate::lang::{Located, Plicity, Usage};
+
+use self::primitives::Primitive;
 
 pub mod marshall;
+pub mod primitives;
 pub mod semantics;
 pub mod typing;
 
+/// Doc comments, with the text following the `|||` on each line.
+pub type Docs = Arc<[String]>;
+/// Entry in a [record type](TermData::RecordType), consisting of a label, the
+/// documentation of the entry, its usage at runtime, its type, and an
+/// optional default term.
+pub type TypeEntry = (String, Docs, Usage, Arc<Term>, Option<Arc<Term>>);
+/// Branch in a [variant elimination](TermData::VariantElim), consisting of a
+/// label, a name hint for the payload, and the branch term.
+pub type VariantBranch = (String, Option<String>, Arc<Term>);
+/// Branch in an [inductive elimination](TermData::InductiveElim), consisting
+/// of a label, name hints for each of the locals bound in the branch, and the
+/// branch term.
+pub type InductiveBranch = (String, Arc<[Option<String>]>, Arc<Term>);
+
 /// Constants used in the core language.
 // FIXME: Partial eq for floating point numbers
 #[derive(Clone, Debug, PartialEq)]
@@ -58,20 +75,35 @@ pub enum Constant {
 }
 
 /// Universe levels.
+///
+/// Finite levels are ordered before the levels above `Typeω`, which are all
+/// larger than any finite level.
 #[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Eq, Ord)]
-pub struct UniverseLevel(pub u32);
+pub enum UniverseLevel {
+    /// Finite universe levels, eg. `Type^2`.
+    Finite(u32),
+    /// Universe levels above all finite universe levels, eg. `Typeω^2`.
+    Omega(u32),
+}
 
 impl std::ops::Add<UniverseOffset> for UniverseLevel {
     type Output = Option<UniverseLevel>;
 
     fn add(self, other: UniverseOffset) -> Option<UniverseLevel> {
-        u32::checked_add(self.0, other.0).map(UniverseLevel)
+        match self {
+            UniverseLevel::Finite(level) => {
+                u32::checked_add(level, other.0).map(UniverseLevel::Finite)
+            }
+            UniverseLevel::Omega(level) => {
+                u32::checked_add(level, other.0).map(UniverseLevel::Omega)
+            }
+        }
     }
 }
 
 impl From<u32> for UniverseLevel {
     fn from(level: u32) -> UniverseLevel {
-        UniverseLevel(level)
+        UniverseLevel::Finite(level)
     }
 }
 
@@ -102,19 +134,27 @@ pub enum TermData {
     Global(String),
     /// Local variables.
     Local(LocalIndex),
+    /// Metavariables, to be solved during elaboration. Holes that were not
+    /// filled during elaboration are left as unsolved metavariables.
+    Meta(MetaIndex),
 
     /// Annotated terms
     Ann(Arc<Term>, Arc<Term>),
 
+    /// Let expressions.
+    ///
+    /// The definition is bound as a local variable in the output term.
+    Let(String, Arc<Term>, Arc<Term>),
+
     /// The type of types.
     TypeType(UniverseLevel),
     /// Lift a term by the given number of universe levels.
     Lift(Arc<Term>, UniverseOffset),
 
-    /// Function types.
+    /// Function types, with the usage of the input at runtime.
     ///
     /// Also known as: pi type, dependent product type.
-    FunctionType(Option<String>, Arc<Term>, Arc<Term>),
+    FunctionType(Plicity, Usage, Option<String>, Arc<Term>, Arc<Term>),
     /// Function terms.
     ///
     /// Also known as: lambda abstraction, anonymous function.
@@ -124,8 +164,24 @@ pub enum TermData {
     /// Also known as: function application.
     FunctionElim(Arc<Term>, Arc<Term>),
 
+    /// Recursive terms, along with their type.
+    ///
+    /// The term is bound as a local variable in its own body. The type is kept
+    /// with the term so that its type can be synthesized after it has been
+    /// read back from a value.
+    ///
+    /// Also known as: fixed-point terms.
+    Fix(String, Arc<Term>, Arc<Term>),
+
     /// Record types.
-    RecordType(Arc<[(String, Arc<Term>)]>),
+    ///
+    /// The documentation for each entry is kept with the entry, so that it can
+    /// be shown by tools, but it has no effect on type checking.
+    ///
+    /// Entries may also have a default term, which is used in place of the
+    /// entry when it is missing from a record term. Default terms are in the
+    /// scope of the entries that precede them.
+    RecordType(Arc<[TypeEntry]>),
     /// Record terms.
     RecordTerm(Arc<[(String, Arc<Term>)]>),
     /// Record eliminations.
@@ -133,6 +189,88 @@ pub enum TermData {
     /// Also known as: record projection, field lookup.
     RecordElim(Arc<Term>, String),
 
+    /// Enumeration types.
+    ///
+    /// Also known as: enumeration sets, finite sets.
+    EnumType(Arc<[String]>),
+    /// Enumeration terms.
+    EnumTerm(String),
+    /// Enumeration eliminations.
+    ///
+    /// Also known as: case analysis, match expressions.
+    EnumElim(Arc<Term>, Arc<[(String, Arc<Term>)]>),
+
+    /// Variant types.
+    ///
+    /// Also known as: sum types, tagged unions.
+    VariantType(Arc<[(String, Arc<Term>)]>),
+    /// Variant terms, consisting of a label and a payload.
+    ///
+    /// Also known as: injections.
+    VariantTerm(String, Arc<Term>),
+    /// Variant eliminations.
+    ///
+    /// The payload is bound as a local variable in each branch, with an
+    /// optional name hint.
+    ///
+    /// Also known as: case analysis, match expressions.
+    VariantElim(Arc<Term>, Arc<[VariantBranch]>),
+
+    /// Inductive types, consisting of a name hint and the types of their
+    /// constructors.
+    ///
+    /// The inductive type is bound as a local variable in the types of its
+    /// constructors. Each constructor type is a telescope of inputs that
+    /// returns the inductive type, where the inductive type may only be used
+    /// directly as the type of an input.
+    ///
+    /// Also known as: algebraic data types.
+    InductiveType(String, Arc<[(String, Arc<Term>)]>),
+    /// Inductive terms, consisting of an inductive type, the label of one of
+    /// its constructors, and the inputs that the constructor was applied to.
+    InductiveTerm(Arc<Term>, String, Arc<[Arc<Term>]>),
+    /// Inductive eliminations, consisting of the head, a motive, and a branch
+    /// for each constructor.
+    ///
+    /// The inputs of the constructor are bound as local variables in each
+    /// branch, followed by the results of eliminating each of its recursive
+    /// inputs.
+    ///
+    /// Also known as: recursors, induction principles.
+    InductiveElim(Arc<Term>, Arc<Term>, Arc<[InductiveBranch]>),
+
+    /// Boolean eliminations.
+    ///
+    /// Also known as: if-then-else expressions.
+    BoolElim(Arc<Term>, Arc<Term>, Arc<Term>),
+
+    /// Equality types.
+    ///
+    /// Also known as: identity types, propositional equality.
+    EqType(Arc<Term>, Arc<Term>, Arc<Term>),
+    /// Equality terms.
+    ///
+    /// Also known as: reflexivity proofs.
+    EqTerm,
+    /// Equality eliminations, consisting of an equality proof, a motive, and
+    /// the term to be transported.
+    ///
+    /// Also known as: transport, substitution.
+    EqElim(Arc<Term>, Arc<Term>, Arc<Term>),
+
+    /// Code types, describing the code of runtime terms at compile time.
+    ///
+    /// Also known as: lifted types.
+    CodeType(Arc<Term>),
+    /// Code terms, quoting a term from the next stage.
+    ///
+    /// Also known as: quotations.
+    CodeTerm(Arc<Term>),
+    /// Code eliminations, splicing the code produced at the previous stage.
+    ///
+    /// Also known as: splices, antiquotations.
+    CodeElim(Arc<Term>),
+
     /// Array terms.
     ArrayTerm(Vec<Arc<Term>>),
     /// List terms.
@@ -145,6 +283,264 @@ pub enum TermData {
     Error,
 }
 
+impl Term {
+    /// Apply a function to each of the immediate subterms of a term, along with
+    /// the number of local variables bound in the term at that point.
+    pub fn try_map_subterms(
+        &self,
+        depth: u32,
+        f: &mut impl FnMut(&Term, u32) -> Option<Term>,
+    ) -> Option<Term> {
+        let mut map = |term: &Arc<Term>, depth| f(term, depth).map(Arc::new);
+
+        let term_data = match &self.data {
+            TermData::Global(_)
+            | TermData::Local(_)
+            | TermData::Meta(_)
+            | TermData::TypeType(_)
+            | TermData::EnumType(_)
+            | TermData::EnumTerm(_)
+            | TermData::EqTerm
+            | TermData::Constant(_)
+            | TermData::Error => self.data.clone(),
+
+            TermData::Ann(term, r#type) => TermData::Ann(map(term, depth)?, map(r#type, depth)?),
+            TermData::Let(name, def_term, output_term) => TermData::Let(
+                name.clone(),
+                map(def_term, depth)?,
+                map(output_term, depth + 1)?,
+            ),
+            TermData::Lift(term, offset) => TermData::Lift(map(term, depth)?, *offset),
+
+            TermData::FunctionType(plicity, usage, input_name_hint, input_type, output_type) => {
+                TermData::FunctionType(
+                    *plicity,
+                    *usage,
+                    input_name_hint.clone(),
+                    map(input_type, depth)?,
+                    map(output_type, depth + 1)?,
+                )
+            }
+            TermData::FunctionTerm(input_name, output_term) => {
+                TermData::FunctionTerm(input_name.clone(), map(output_term, depth + 1)?)
+            }
+            TermData::FunctionElim(head_term, input_term) => {
+                TermData::FunctionElim(map(head_term, depth)?, map(input_term, depth)?)
+            }
+            TermData::Fix(name, r#type, term) => {
+                TermData::Fix(name.clone(), map(r#type, depth)?, map(term, depth + 1)?)
+            }
+
+            TermData::RecordType(entries) => {
+                let entries = (entries.iter().zip(depth..))
+                    .map(|((label, docs, usage, r#type, default), depth)| {
+                        let default = match default {
+                            Some(default) => Some(map(default, depth)?),
+                            None => None,
+                        };
+                        let r#type = map(r#type, depth)?;
+                        Some((label.clone(), docs.clone(), *usage, r#type, default))
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                TermData::RecordType(entries.into())
+            }
+            TermData::RecordTerm(entries) => {
+                let entries = (entries.iter().zip(depth..))
+                    .map(|((label, entry), depth)| Some((label.clone(), map(entry, depth)?)))
+                    .collect::<Option<Vec<_>>>()?;
+                TermData::RecordTerm(entries.into())
+            }
+            TermData::RecordElim(head_term, label) => {
+                TermData::RecordElim(map(head_term, depth)?, label.clone())
+            }
+
+            TermData::VariantType(entries) => TermData::VariantType(
+                (entries.iter())
+                    .map(|(label, entry)| Some((label.clone(), map(entry, depth)?)))
+                    .collect::<Option<Vec<_>>>()?
+                    .into(),
+            ),
+            TermData::VariantTerm(label, term) => {
+                TermData::VariantTerm(label.clone(), map(term, depth)?)
+            }
+            TermData::VariantElim(head_term, branches) => TermData::VariantElim(
+                map(head_term, depth)?,
+                (branches.iter())
+                    .map(|(label, name_hint, branch)| {
+                        Some((label.clone(), name_hint.clone(), map(branch, depth + 1)?))
+                    })
+                    .collect::<Option<Vec<_>>>()?
+                    .into(),
+            ),
+
+            TermData::InductiveType(name_hint, constructors) => TermData::InductiveType(
+                name_hint.clone(),
+                (constructors.iter())
+                    .map(|(label, r#type)| Some((label.clone(), map(r#type, depth + 1)?)))
+                    .collect::<Option<Vec<_>>>()?
+                    .into(),
+            ),
+            TermData::InductiveTerm(r#type, label, inputs) => TermData::InductiveTerm(
+                map(r#type, depth)?,
+                label.clone(),
+                (inputs.iter())
+                    .map(|input| map(input, depth))
+                    .collect::<Option<_>>()?,
+            ),
+            TermData::InductiveElim(head_term, motive, branches) => TermData::InductiveElim(
+                map(head_term, depth)?,
+                map(motive, depth)?,
+                (branches.iter())
+                    .map(|(label, name_hints, branch)| {
+                        let depth = depth + name_hints.len() as u32;
+                        Some((label.clone(), name_hints.clone(), map(branch, depth)?))
+                    })
+                    .collect::<Option<Vec<_>>>()?
+                    .into(),
+            ),
+
+            TermData::EnumElim(head_term, branches) => TermData::EnumElim(
+                map(head_term, depth)?,
+                (branches.iter())
+                    .map(|(label, branch)| Some((label.clone(), map(branch, depth)?)))
+                    .collect::<Option<Vec<_>>>()?
+                    .into(),
+            ),
+            TermData::BoolElim(head_term, if_true, if_false) => TermData::BoolElim(
+                map(head_term, depth)?,
+                map(if_true, depth)?,
+                map(if_false, depth)?,
+            ),
+
+            TermData::EqType(r#type, lhs, rhs) => {
+                TermData::EqType(map(r#type, depth)?, map(lhs, depth)?, map(rhs, depth)?)
+            }
+            TermData::EqElim(head_term, motive, term) => TermData::EqElim(
+                map(head_term, depth)?,
+                map(motive, depth)?,
+                map(term, depth)?,
+            ),
+
+            TermData::CodeType(r#type) => TermData::CodeType(map(r#type, depth)?),
+            TermData::CodeTerm(term) => TermData::CodeTerm(map(term, depth)?),
+            TermData::CodeElim(term) => TermData::CodeElim(map(term, depth)?),
+
+            TermData::ArrayTerm(entries) => TermData::ArrayTerm(
+                (entries.iter())
+                    .map(|entry| map(entry, depth))
+                    .collect::<Option<_>>()?,
+            ),
+            TermData::ListTerm(entries) => TermData::ListTerm(
+                (entries.iter())
+                    .map(|entry| map(entry, depth))
+                    .collect::<Option<_>>()?,
+            ),
+        };
+
+        Some(Term::new(self.location, term_data))
+    }
+
+    /// Check if the local variable with the given index is used in the term.
+    pub fn has_free_local(&self, index: LocalIndex) -> bool {
+        let shifted = |count: usize| LocalIndex(index.0 + count as u32);
+
+        match &self.data {
+            TermData::Local(local_index) => *local_index == index,
+            TermData::Global(_)
+            | TermData::Meta(_)
+            | TermData::TypeType(_)
+            | TermData::EnumType(_)
+            | TermData::EnumTerm(_)
+            | TermData::EqTerm
+            | TermData::Constant(_)
+            | TermData::Error => false,
+
+            TermData::Ann(term, r#type) => {
+                term.has_free_local(index) || r#type.has_free_local(index)
+            }
+            TermData::Let(_, def_term, output_term) => {
+                def_term.has_free_local(index) || output_term.has_free_local(shifted(1))
+            }
+            TermData::Lift(term, _) => term.has_free_local(index),
+
+            TermData::FunctionType(_, _, _, input_type, output_type) => {
+                input_type.has_free_local(index) || output_type.has_free_local(shifted(1))
+            }
+            TermData::FunctionTerm(_, output_term) => output_term.has_free_local(shifted(1)),
+            TermData::FunctionElim(head_term, input_term) => {
+                head_term.has_free_local(index) || input_term.has_free_local(index)
+            }
+            TermData::Fix(_, r#type, term) => {
+                r#type.has_free_local(index) || term.has_free_local(shifted(1))
+            }
+
+            TermData::RecordType(entries) => {
+                (entries.iter().enumerate()).any(|(i, (_, _, _, r#type, default))| {
+                    r#type.has_free_local(shifted(i))
+                        || (default.iter()).any(|default| default.has_free_local(shifted(i)))
+                })
+            }
+            TermData::RecordTerm(entries) => {
+                (entries.iter().enumerate()).any(|(i, (_, term))| term.has_free_local(shifted(i)))
+            }
+            TermData::RecordElim(head_term, _) => head_term.has_free_local(index),
+
+            TermData::EnumElim(head_term, branches) => {
+                head_term.has_free_local(index)
+                    || (branches.iter()).any(|(_, term)| term.has_free_local(index))
+            }
+
+            TermData::VariantType(entries) => {
+                (entries.iter()).any(|(_, r#type)| r#type.has_free_local(index))
+            }
+            TermData::VariantTerm(_, term) => term.has_free_local(index),
+            TermData::VariantElim(head_term, branches) => {
+                head_term.has_free_local(index)
+                    || (branches.iter()).any(|(_, _, term)| term.has_free_local(shifted(1)))
+            }
+
+            TermData::InductiveType(_, constructors) => {
+                (constructors.iter()).any(|(_, r#type)| r#type.has_free_local(shifted(1)))
+            }
+            TermData::InductiveTerm(r#type, _, inputs) => {
+                r#type.has_free_local(index)
+                    || inputs.iter().any(|input| input.has_free_local(index))
+            }
+            TermData::InductiveElim(head_term, motive, branches) => {
+                head_term.has_free_local(index)
+                    || motive.has_free_local(index)
+                    || (branches.iter())
+                        .any(|(_, names, term)| term.has_free_local(shifted(names.len())))
+            }
+
+            TermData::BoolElim(head_term, if_true, if_false) => {
+                head_term.has_free_local(index)
+                    || if_true.has_free_local(index)
+                    || if_false.has_free_local(index)
+            }
+
+            TermData::EqType(r#type, lhs, rhs) => {
+                r#type.has_free_local(index)
+                    || lhs.has_free_local(index)
+                    || rhs.has_free_local(index)
+            }
+            TermData::EqElim(head_term, motive, term) => {
+                head_term.has_free_local(index)
+                    || motive.has_free_local(index)
+                    || term.has_free_local(index)
+            }
+
+            TermData::CodeType(term) | TermData::CodeTerm(term) | TermData::CodeElim(term) => {
+                term.has_free_local(index)
+            }
+
+            TermData::ArrayTerm(entries) | TermData::ListTerm(entries) => {
+                entries.iter().any(|entry| entry.has_free_local(index))
+            }
+        }
+    }
+}
+
 impl From<Constant> for TermData {
     fn from(constant: Constant) -> TermData {
         TermData::Constant(constant)
@@ -154,17 +550,29 @@ impl From<Constant> for TermData {
 /// An environment of global definitions.
 pub struct Globals {
     entries: BTreeMap<String, (Arc<Term>, Option<Arc<Term>>)>,
+    primitives: BTreeMap<String, Primitive>,
 }
 
 impl Globals {
-    pub fn new(entries: BTreeMap<String, (Arc<Term>, Option<Arc<Term>>)>) -> Globals {
-        Globals { entries }
+    pub fn new(
+        entries: BTreeMap<String, (Arc<Term>, Option<Arc<Term>>)>,
+        primitives: BTreeMap<String, Primitive>,
+    ) -> Globals {
+        Globals {
+            entries,
+            primitives,
+        }
     }
 
     pub fn get(&self, name: &str) -> Option<&(Arc<Term>, Option<Arc<Term>>)> {
         self.entries.get(name)
     }
 
+    /// Get the native implementation of a global, if it is a primitive operation.
+    pub fn get_primitive(&self, name: &str) -> Option<&Primitive> {
+        self.primitives.get(name)
+    }
+
     pub fn entries(&self) -> impl Iterator<Item = (&String, &(Arc<Term>, Option<Arc<Term>>))> {
         self.entries.iter()
     }
@@ -173,11 +581,18 @@ impl Globals {
 impl Default for Globals {
     fn default() -> Globals {
         let mut entries = BTreeMap::new();
+        let mut primitives = BTreeMap::new();
 
         let global = |name: &str| Arc::new(Term::generated(TermData::Global(name.to_owned())));
-        let type_type = |level| Arc::new(Term::generated(TermData::TypeType(UniverseLevel(level))));
+        let type_type = |level| {
+            Arc::new(Term::generated(TermData::TypeType(UniverseLevel::Finite(
+                level,
+            ))))
+        };
         let function_type = |input_type, output_type| {
             Arc::new(Term::generated(TermData::FunctionType(
+                Plicity::Explicit,
+                Usage::Many,
                 None,
                 input_type,
                 output_type,
@@ -212,7 +627,85 @@ impl Default for Globals {
             (function_type(type_type(0), type_type(0)), None),
         );
 
-        Globals::new(entries)
+        // Options are defined in terms of variants, allowing them to be
+        // eliminated using match expressions.
+        let local = |index| Arc::new(Term::generated(TermData::Local(LocalIndex(index))));
+        let option_type = |input_type| {
+            Arc::new(Term::generated(TermData::FunctionElim(
+                global("Option"),
+                input_type,
+            )))
+        };
+        let forall_type = |output_type| {
+            Arc::new(Term::generated(TermData::FunctionType(
+                Plicity::Implicit,
+                Usage::Zero,
+                Some("A".to_owned()),
+                type_type(0),
+                output_type,
+            )))
+        };
+        let function_term = |input_name: &str, output_term| {
+            Arc::new(Term::generated(TermData::FunctionTerm(
+                input_name.to_owned(),
+                output_term,
+            )))
+        };
+        let unit_type = Arc::new(Term::generated(TermData::RecordType(Arc::new([]))));
+        let unit_term = Arc::new(Term::generated(TermData::RecordTerm(Arc::new([]))));
+
+        entries.insert(
+            "Option".to_owned(),
+            (
+                function_type(type_type(0), type_type(0)),
+                Some(function_term(
+                    "A",
+                    Arc::new(Term::generated(TermData::VariantType(Arc::new([
+                        ("none".to_owned(), unit_type),
+                        ("some".to_owned(), local(0)),
+                    ])))),
+                )),
+            ),
+        );
+        entries.insert(
+            "none".to_owned(),
+            (
+                forall_type(option_type(local(0))),
+                Some(function_term(
+                    "A",
+                    Arc::new(Term::generated(TermData::VariantTerm(
+                        "none".to_owned(),
+                        unit_term,
+                    ))),
+                )),
+            ),
+        );
+        entries.insert(
+            "some".to_owned(),
+            (
+                forall_type(function_type(local(0), option_type(local(1)))),
+                Some(function_term(
+                    "A",
+                    function_term(
+                        "value",
+                        Arc::new(Term::generated(TermData::VariantTerm(
+                            "some".to_owned(),
+                            local(0),
+                        ))),
+                    ),
+                )),
+            ),
+        );
+
+        let primitive_entries = primitives::numeric()
+            .into_iter()
+            .chain(primitives::textual());
+        for (name, r#type, primitive) in primitive_entries {
+            entries.insert(name.clone(), (r#type, None));
+            primitives.insert(name, primitive);
+        }
+
+        Globals::new(entries, primitives)
     }
 }
 
@@ -259,6 +752,10 @@ impl LocalSize {
     }
 }
 
+/// An index into the table of metavariables in the elaborator.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MetaIndex(pub u32);
+
 /// A local environment.
 #[derive(Clone)]
 pub struct Locals<Entry> {
@@ -45,6 +45,15 @@ impl<T: HasType> HasType for Vec<T> {
     }
 }
 
+impl<T: HasType> HasType for Option<T> {
+    fn r#type() -> Arc<Term> {
+        Arc::new(Term::generated(TermData::FunctionElim(
+            Arc::new(Term::generated(TermData::Global("Option".to_owned()))),
+            T::r#type(),
+        )))
+    }
+}
+
 macro_rules! impl_has_type_array {
     ($($len:expr),*) => {
         $(impl<T: HasType> HasType for [T; $len] {
@@ -128,6 +137,32 @@ impl<T: TryFromTerm> TryFromTerm for Vec<T> {
     }
 }
 
+impl<T: TryFromTerm> TryFromTerm for Option<T> {
+    type Error = ();
+
+    fn try_from_term(term: &Term) -> Result<Option<T>, ()> {
+        match &term.data {
+            // Normalized options
+            TermData::VariantTerm(label, _) if label == "none" => Ok(None),
+            TermData::VariantTerm(label, payload_term) if label == "some" => {
+                T::try_from_term(payload_term).map(Some).map_err(|_| ())
+            }
+            // Applications of the `none` and `some` globals
+            TermData::FunctionElim(head_term, input_term) => match &head_term.data {
+                TermData::Global(name) if name == "none" => Ok(None),
+                TermData::FunctionElim(head_term, _) => match &head_term.data {
+                    TermData::Global(name) if name == "some" => {
+                        T::try_from_term(input_term).map(Some).map_err(|_| ())
+                    }
+                    _ => Err(()),
+                },
+                _ => Err(()),
+            },
+            _ => Err(()),
+        }
+    }
+}
+
 macro_rules! impl_try_from_term_array {
     ($($len:expr),*) => {
         $(impl<T: TryFromTerm + Sized> TryFromTerm for [T; $len] {
@@ -226,6 +261,23 @@ impl<T: ToTerm> ToTerm for Vec<T> {
     }
 }
 
+impl<T: ToTerm> ToTerm for Option<T> {
+    fn to_term(&self) -> Term {
+        let global = |name: &str| Arc::new(Term::generated(TermData::Global(name.to_owned())));
+
+        match self {
+            None => Term::generated(TermData::FunctionElim(global("none"), T::r#type())),
+            Some(value) => Term::generated(TermData::FunctionElim(
+                Arc::new(Term::generated(TermData::FunctionElim(
+                    global("some"),
+                    T::r#type(),
+                ))),
+                Arc::new(T::to_term(value)),
+            )),
+        }
+    }
+}
+
 macro_rules! impl_to_term_array {
     ($($len:expr),*) => {
         $(impl<T: ToTerm> ToTerm for [T; $len] {
@@ -244,3 +296,32 @@ impl_to_term_array!(
     0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25,
     26, 27, 28, 29, 30, 31, 32
 );
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lang::core::semantics::{self, Unfold, Value};
+    use crate::lang::core::{Globals, Locals, UniverseOffset};
+
+    /// Serialize a value to a term, normalize it, and then deserialize it.
+    fn round_trip<T: ToTerm + TryFromTerm>(value: &T, unfold: Unfold) -> Result<T, T::Error> {
+        let globals = Globals::default();
+        let mut locals = Locals::<Arc<Value>>::new();
+        let term = value.to_term();
+        let value = semantics::eval_term(&globals, UniverseOffset(0), &mut locals, &term);
+        let term = semantics::read_back_value(&globals, locals.size(), unfold, &value);
+        T::try_from_term(&term)
+    }
+
+    #[test]
+    fn option_some() {
+        assert_eq!(round_trip(&Some(3u32), Unfold::Never), Ok(Some(3)));
+        assert_eq!(round_trip(&Some(3u32), Unfold::Always), Ok(Some(3)));
+    }
+
+    #[test]
+    fn option_none() {
+        assert_eq!(round_trip(&None::<u32>, Unfold::Never), Ok(None));
+        assert_eq!(round_trip(&None::<u32>, Unfold::Always), Ok(None));
+    }
+}
@@ -0,0 +1,517 @@
+//! Primitive operations that are implemented natively by the evaluator.
+//!
+//! Primitive operations are registered as [global variables][crate::lang::core::Globals]
+//! without definitions. When a primitive is applied to enough constant inputs,
+//! it is reduced by calling its native implementation. If any of the inputs
+//! are not constants, or the operation is undefined for the given inputs (for
+//! example on integer overflow, or division by zero), the application remains
+//! stuck.
+
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+use crate::lang::core::semantics::Value;
+use crate::lang::core::{Constant, Term, TermData};
+use crate::lang::{Plicity, Usage};
+
+/// A primitive operation.
+#[derive(Copy, Clone, Debug)]
+pub struct Primitive {
+    /// The number of inputs that the primitive operation expects.
+    arity: usize,
+    /// The native implementation of the primitive operation.
+    apply: fn(&[&Constant]) -> Option<Value>,
+}
+
+impl Primitive {
+    /// Construct a new primitive operation.
+    pub fn new(arity: usize, apply: fn(&[&Constant]) -> Option<Value>) -> Primitive {
+        Primitive { arity, apply }
+    }
+
+    /// The number of inputs that the primitive operation expects.
+    pub fn arity(&self) -> usize {
+        self.arity
+    }
+
+    /// Apply the primitive operation to some constant inputs.
+    ///
+    /// Returns `None` if the operation is not defined for the given inputs.
+    pub fn apply(&self, inputs: &[&Constant]) -> Option<Value> {
+        match inputs.len() {
+            len if len == self.arity => (self.apply)(inputs),
+            _ => None,
+        }
+    }
+}
+
+/// Convert a Rust boolean into a boolean value.
+fn bool_value(value: bool) -> Value {
+    Value::global(if value { "true" } else { "false" }, 0, [])
+}
+
+/// Integer arithmetic, returning `None` on overflow and division by zero.
+macro_rules! checked_arithmetic {
+    ($Constant:ident, $op:ident) => {
+        |inputs: &[&Constant]| match inputs {
+            [Constant::$Constant(x), Constant::$Constant(y)] => {
+                Some(Value::from(Constant::$Constant(x.$op(*y)?)))
+            }
+            _ => None,
+        }
+    };
+}
+
+/// Floating point arithmetic, following the semantics of [IEEE-754].
+///
+/// [IEEE-754]: https://en.wikipedia.org/wiki/IEEE_754
+macro_rules! float_arithmetic {
+    ($Constant:ident, $op:path) => {
+        |inputs: &[&Constant]| match inputs {
+            [Constant::$Constant(x), Constant::$Constant(y)] => {
+                Some(Value::from(Constant::$Constant($op(*x, *y))))
+            }
+            _ => None,
+        }
+    };
+}
+
+/// Comparisons, returning a boolean.
+macro_rules! comparison {
+    ($Constant:ident, $op:path) => {
+        |inputs: &[&Constant]| match inputs {
+            [Constant::$Constant(x), Constant::$Constant(y)] => Some(bool_value($op(x, y))),
+            _ => None,
+        }
+    };
+}
+
+/// Format a constant as a string, using Rust's `Display` implementation.
+macro_rules! to_string {
+    ($Constant:ident) => {
+        |inputs: &[&Constant]| match inputs {
+            [Constant::$Constant(x)] => Some(Value::from(Constant::String(x.to_string()))),
+            _ => None,
+        }
+    };
+}
+
+/// Comparison operations that are shared between all numeric types.
+macro_rules! comparisons {
+    ($primitives:ident, $prefix:literal, $Type:literal, $Constant:ident) => {
+        $primitives.push(comparison_entry(
+            $prefix,
+            "-eq",
+            $Type,
+            comparison!($Constant, PartialEq::eq),
+        ));
+        $primitives.push(comparison_entry(
+            $prefix,
+            "-neq",
+            $Type,
+            comparison!($Constant, PartialEq::ne),
+        ));
+        $primitives.push(comparison_entry(
+            $prefix,
+            "-lt",
+            $Type,
+            comparison!($Constant, PartialOrd::lt),
+        ));
+        $primitives.push(comparison_entry(
+            $prefix,
+            "-lte",
+            $Type,
+            comparison!($Constant, PartialOrd::le),
+        ));
+        $primitives.push(comparison_entry(
+            $prefix,
+            "-gt",
+            $Type,
+            comparison!($Constant, PartialOrd::gt),
+        ));
+        $primitives.push(comparison_entry(
+            $prefix,
+            "-gte",
+            $Type,
+            comparison!($Constant, PartialOrd::ge),
+        ));
+    };
+}
+
+/// Integer arithmetic, comparison, and formatting operations.
+macro_rules! integer_primitives {
+    ($primitives:ident, $prefix:literal, $Type:literal, $Constant:ident) => {
+        $primitives.push(arithmetic_entry(
+            $prefix,
+            "-add",
+            $Type,
+            checked_arithmetic!($Constant, checked_add),
+        ));
+        $primitives.push(arithmetic_entry(
+            $prefix,
+            "-sub",
+            $Type,
+            checked_arithmetic!($Constant, checked_sub),
+        ));
+        $primitives.push(arithmetic_entry(
+            $prefix,
+            "-mul",
+            $Type,
+            checked_arithmetic!($Constant, checked_mul),
+        ));
+        $primitives.push(arithmetic_entry(
+            $prefix,
+            "-div",
+            $Type,
+            checked_arithmetic!($Constant, checked_div),
+        ));
+        $primitives.push(arithmetic_entry(
+            $prefix,
+            "-rem",
+            $Type,
+            checked_arithmetic!($Constant, checked_rem),
+        ));
+        comparisons!($primitives, $prefix, $Type, $Constant);
+        $primitives.push(entry(
+            format!("{}-to-string", $prefix),
+            &[$Type],
+            "String",
+            to_string!($Constant),
+        ));
+    };
+}
+
+/// Floating point arithmetic, comparison, and formatting operations.
+macro_rules! float_primitives {
+    ($primitives:ident, $prefix:literal, $Type:literal, $Constant:ident) => {
+        $primitives.push(arithmetic_entry(
+            $prefix,
+            "-add",
+            $Type,
+            float_arithmetic!($Constant, std::ops::Add::add),
+        ));
+        $primitives.push(arithmetic_entry(
+            $prefix,
+            "-sub",
+            $Type,
+            float_arithmetic!($Constant, std::ops::Sub::sub),
+        ));
+        $primitives.push(arithmetic_entry(
+            $prefix,
+            "-mul",
+            $Type,
+            float_arithmetic!($Constant, std::ops::Mul::mul),
+        ));
+        $primitives.push(arithmetic_entry(
+            $prefix,
+            "-div",
+            $Type,
+            float_arithmetic!($Constant, std::ops::Div::div),
+        ));
+        $primitives.push(arithmetic_entry(
+            $prefix,
+            "-rem",
+            $Type,
+            float_arithmetic!($Constant, std::ops::Rem::rem),
+        ));
+        comparisons!($primitives, $prefix, $Type, $Constant);
+        $primitives.push(entry(
+            format!("{}-to-string", $prefix),
+            &[$Type],
+            "String",
+            to_string!($Constant),
+        ));
+    };
+}
+
+/// A global entry for a primitive operation, consisting of the name of the
+/// primitive, its type, and its implementation.
+pub type Entry = (String, Arc<Term>, Primitive);
+
+fn global(name: &str) -> Arc<Term> {
+    Arc::new(Term::generated(TermData::Global(name.to_owned())))
+}
+
+fn function_type(input_type: Arc<Term>, output_type: Arc<Term>) -> Arc<Term> {
+    Arc::new(Term::generated(TermData::FunctionType(
+        Plicity::Explicit,
+        Usage::Many,
+        None,
+        input_type,
+        output_type,
+    )))
+}
+
+/// An entry for a primitive operation with the given input and output types.
+fn entry(
+    name: String,
+    input_types: &[&str],
+    output_type: &str,
+    apply: fn(&[&Constant]) -> Option<Value>,
+) -> Entry {
+    let r#type = input_types
+        .iter()
+        .rev()
+        .fold(global(output_type), |r#type, input_type| {
+            function_type(global(input_type), r#type)
+        });
+
+    (name, r#type, Primitive::new(input_types.len(), apply))
+}
+
+/// An entry for a binary arithmetic operation on the given type.
+fn arithmetic_entry(
+    prefix: &str,
+    suffix: &str,
+    r#type: &str,
+    apply: fn(&[&Constant]) -> Option<Value>,
+) -> Entry {
+    entry(
+        format!("{}{}", prefix, suffix),
+        &[r#type, r#type],
+        r#type,
+        apply,
+    )
+}
+
+/// An entry for a binary comparison operation on the given type.
+fn comparison_entry(
+    prefix: &str,
+    suffix: &str,
+    r#type: &str,
+    apply: fn(&[&Constant]) -> Option<Value>,
+) -> Entry {
+    entry(
+        format!("{}{}", prefix, suffix),
+        &[r#type, r#type],
+        "Bool",
+        apply,
+    )
+}
+
+/// Primitive arithmetic, comparison, and formatting operations on numeric types.
+#[rustfmt::skip]
+pub fn numeric() -> Vec<Entry> {
+    let mut primitives = Vec::new();
+
+    integer_primitives!(primitives, "u8", "U8", U8);
+    integer_primitives!(primitives, "u16", "U16", U16);
+    integer_primitives!(primitives, "u32", "U32", U32);
+    integer_primitives!(primitives, "u64", "U64", U64);
+    integer_primitives!(primitives, "s8", "S8", S8);
+    integer_primitives!(primitives, "s16", "S16", S16);
+    integer_primitives!(primitives, "s32", "S32", S32);
+    integer_primitives!(primitives, "s64", "S64", S64);
+    float_primitives!(primitives, "f32", "F32", F32);
+    float_primitives!(primitives, "f64", "F64", F64);
+
+    primitives
+}
+
+/// Concatenate two strings.
+fn string_append(inputs: &[&Constant]) -> Option<Value> {
+    match inputs {
+        [Constant::String(x), Constant::String(y)] => {
+            Some(Value::from(Constant::String(format!("{}{}", x, y))))
+        }
+        _ => None,
+    }
+}
+
+/// The number of Unicode scalar values in a string, returning `None` if the
+/// length does not fit in a `U32`.
+fn string_length(inputs: &[&Constant]) -> Option<Value> {
+    match inputs {
+        [Constant::String(x)] => {
+            let length = u32::try_from(x.chars().count()).ok()?;
+            Some(Value::from(Constant::U32(length)))
+        }
+        _ => None,
+    }
+}
+
+/// The substring between a start and end index, counted in Unicode scalar
+/// values. Returns `None` if the range is out of bounds.
+fn string_slice(inputs: &[&Constant]) -> Option<Value> {
+    match inputs {
+        [Constant::String(x), Constant::U32(start), Constant::U32(end)] => {
+            let (start, end) = (*start as usize, *end as usize);
+            if start > end || end > x.chars().count() {
+                return None;
+            }
+            let slice = x.chars().skip(start).take(end - start).collect();
+            Some(Value::from(Constant::String(slice)))
+        }
+        _ => None,
+    }
+}
+
+/// Convert a character to a string containing only that character.
+fn char_to_string(inputs: &[&Constant]) -> Option<Value> {
+    match inputs {
+        [Constant::Char(x)] => Some(Value::from(Constant::String(x.to_string()))),
+        _ => None,
+    }
+}
+
+/// Primitive operations on strings and characters.
+#[rustfmt::skip]
+pub fn textual() -> Vec<Entry> {
+    vec![
+        entry("string-append".to_owned(), &["String", "String"], "String", string_append),
+        entry("string-length".to_owned(), &["String"], "U32", string_length),
+        entry("string-slice".to_owned(), &["String", "U32", "U32"], "String", string_slice),
+        entry("string-eq".to_owned(), &["String", "String"], "Bool", comparison!(String, PartialEq::eq)),
+        entry("string-neq".to_owned(), &["String", "String"], "Bool", comparison!(String, PartialEq::ne)),
+        entry("char-eq".to_owned(), &["Char", "Char"], "Bool", comparison!(Char, PartialEq::eq)),
+        entry("char-neq".to_owned(), &["Char", "Char"], "Bool", comparison!(Char, PartialEq::ne)),
+        entry("char-to-string".to_owned(), &["Char"], "String", char_to_string),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lang::core::semantics::{self, Unfold};
+    use crate::lang::core::{Globals, Locals, UniverseOffset};
+
+    fn apply(name: &str, inputs: Vec<Constant>) -> Term {
+        let globals = Globals::default();
+        let term = inputs.into_iter().fold(
+            Term::generated(TermData::Global(name.to_owned())),
+            |head, input| {
+                Term::generated(TermData::FunctionElim(
+                    Arc::new(head),
+                    Arc::new(Term::generated(TermData::from(input))),
+                ))
+            },
+        );
+        let mut locals = Locals::new();
+        let value = semantics::eval_term(&globals, UniverseOffset(0), &mut locals, &term);
+        semantics::read_back_value(&globals, locals.size(), Unfold::Always, &value)
+    }
+
+    fn assert_constant(term: Term, expected: Constant) {
+        match term.data {
+            TermData::Constant(constant) => assert_eq!(constant, expected),
+            data => panic!("expected a constant, found {:?}", data),
+        }
+    }
+
+    fn assert_global(term: Term, expected: &str) {
+        match term.data {
+            TermData::Global(name) => assert_eq!(name, expected),
+            data => panic!("expected a global, found {:?}", data),
+        }
+    }
+
+    fn assert_stuck(term: Term) {
+        match term.data {
+            TermData::FunctionElim(_, _) => {}
+            data => panic!("expected a stuck application, found {:?}", data),
+        }
+    }
+
+    #[test]
+    fn integer_arithmetic() {
+        use Constant::{S8, U32, U8};
+
+        assert_constant(apply("u8-add", vec![U8(1), U8(2)]), U8(3));
+        assert_constant(apply("u32-mul", vec![U32(6), U32(7)]), U32(42));
+        assert_constant(apply("s8-sub", vec![S8(-1), S8(2)]), S8(-3));
+        assert_constant(apply("s8-div", vec![S8(-7), S8(2)]), S8(-3));
+        assert_constant(apply("s8-rem", vec![S8(-7), S8(2)]), S8(-1));
+    }
+
+    #[test]
+    fn integer_overflow_is_stuck() {
+        use Constant::{S8, U8};
+
+        assert_stuck(apply("u8-add", vec![U8(255), U8(1)]));
+        assert_stuck(apply("u8-sub", vec![U8(0), U8(1)]));
+        assert_stuck(apply("s8-mul", vec![S8(-128), S8(-1)]));
+        assert_stuck(apply("s8-div", vec![S8(-128), S8(-1)]));
+    }
+
+    #[test]
+    fn integer_division_by_zero_is_stuck() {
+        use Constant::{S64, U16};
+
+        assert_stuck(apply("u16-div", vec![U16(1), U16(0)]));
+        assert_stuck(apply("s64-rem", vec![S64(1), S64(0)]));
+    }
+
+    #[test]
+    fn float_arithmetic() {
+        use Constant::{F32, F64};
+
+        assert_constant(apply("f32-add", vec![F32(0.5), F32(0.25)]), F32(0.75));
+        assert_constant(
+            apply("f64-div", vec![F64(1.0), F64(0.0)]),
+            F64(f64::INFINITY),
+        );
+    }
+
+    #[test]
+    fn comparisons() {
+        use Constant::{F64, S32, U64};
+
+        assert_global(apply("u64-eq", vec![U64(1), U64(1)]), "true");
+        assert_global(apply("s32-lt", vec![S32(2), S32(-1)]), "false");
+        assert_global(apply("f64-gte", vec![F64(1.0), F64(0.5)]), "true");
+        assert_global(apply("f64-eq", vec![F64(f64::NAN), F64(f64::NAN)]), "false");
+    }
+
+    #[test]
+    fn number_to_string() {
+        use Constant::{String, F64, S16};
+
+        assert_constant(
+            apply("s16-to-string", vec![S16(-42)]),
+            String("-42".to_owned()),
+        );
+        assert_constant(
+            apply("f64-to-string", vec![F64(1.5)]),
+            String("1.5".to_owned()),
+        );
+    }
+
+    #[test]
+    fn strings() {
+        use Constant::{Char, String, U32};
+
+        let string = |s: &str| String(s.to_owned());
+
+        assert_constant(
+            apply("string-append", vec![string("foo"), string("bar")]),
+            string("foobar"),
+        );
+        assert_constant(apply("string-length", vec![string("가나다")]), U32(3));
+        assert_constant(
+            apply("string-slice", vec![string("가나다"), U32(1), U32(3)]),
+            string("나다"),
+        );
+        assert_constant(apply("char-to-string", vec![Char('🥞')]), string("🥞"));
+        assert_global(apply("string-eq", vec![string("a"), string("a")]), "true");
+        assert_global(apply("char-neq", vec![Char('a'), Char('a')]), "false");
+    }
+
+    #[test]
+    fn string_slice_out_of_bounds_is_stuck() {
+        use Constant::{String, U32};
+
+        assert_stuck(apply(
+            "string-slice",
+            vec![String("abc".to_owned()), U32(2), U32(1)],
+        ));
+        assert_stuck(apply(
+            "string-slice",
+            vec![String("abc".to_owned()), U32(0), U32(4)],
+        ));
+    }
+
+    #[test]
+    fn partial_application_is_stuck() {
+        assert_stuck(apply("u8-add", vec![Constant::U8(1)]));
+    }
+}
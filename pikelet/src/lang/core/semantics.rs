@@ -8,8 +8,10 @@ use std::cell::RefCell;
 use std::sync::Arc;
 
 use crate::lang::core::{
-    Constant, Globals, LocalLevel, LocalSize, Locals, Term, TermData, UniverseLevel, UniverseOffset,
+    Constant, Globals, InductiveBranch, LocalLevel, LocalSize, Locals, MetaIndex, Term, TermData,
+    TypeEntry, UniverseLevel, UniverseOffset, VariantBranch,
 };
+use crate::lang::{Plicity, Usage};
 
 /// Values in the core language.
 #[derive(Clone, Debug)]
@@ -45,20 +47,54 @@ pub enum Value {
     /// The type of types.
     TypeType(UniverseLevel),
 
-    /// Function types.
+    /// Function types, with the usage of the input at runtime.
     ///
     /// Also known as: pi type, dependent product type.
-    FunctionType(Option<String>, Arc<Value>, FunctionClosure),
+    FunctionType(Plicity, Usage, Option<String>, Arc<Value>, FunctionClosure),
     /// Function terms.
     ///
     /// Also known as: lambda abstraction, anonymous function.
     FunctionTerm(String, FunctionClosure),
 
     /// Record types.
-    RecordType(RecordClosure),
+    RecordType(RecordClosure<TypeEntry>),
     /// Record terms.
     RecordTerm(RecordClosure),
 
+    /// Enumeration types.
+    EnumType(Arc<[String]>),
+    /// Enumeration terms.
+    EnumTerm(String),
+
+    /// Variant types.
+    VariantType(Arc<[(String, Arc<Value>)]>),
+    /// Variant terms.
+    VariantTerm(String, Arc<Value>),
+
+    /// Inductive types.
+    InductiveType(InductiveClosure),
+    /// Inductive terms, consisting of an inductive type, the label of a
+    /// constructor, and the inputs that the constructor was applied to.
+    InductiveTerm(Arc<Value>, String, Vec<Arc<Value>>),
+
+    /// Equality types.
+    ///
+    /// Also known as: identity types, propositional equality.
+    EqType(Arc<Value>, Arc<Value>, Arc<Value>),
+    /// Equality terms.
+    ///
+    /// Also known as: reflexivity proofs.
+    EqTerm,
+
+    /// Code types.
+    ///
+    /// Also known as: lifted types.
+    CodeType(Arc<Value>),
+    /// Code terms.
+    ///
+    /// Also known as: quotations.
+    CodeTerm(Arc<Value>),
+
     /// Array terms.
     ArrayTerm(Vec<Arc<Value>>),
     /// List terms.
@@ -124,12 +160,22 @@ impl From<Constant> for Value {
 /// This cannot currently be reduced in the current scope due to its definition
 /// not being known. Once it becomes known, the head may be 'remembered' in an
 /// [unstuck value][Value::Unstuck].
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug)]
 pub enum Head {
     /// Global variables.
     Global(String, UniverseOffset),
     /// Local variables.
     Local(LocalLevel),
+    /// Metavariables.
+    ///
+    /// These are only solved by the elaborator, which substitutes their
+    /// solutions when comparing values.
+    Meta(MetaIndex),
+    /// Recursive terms.
+    ///
+    /// These are only unfolded when their first input is not neutral.
+    /// See [`unfold_fix`] for more details.
+    Fix(String, FixClosure),
 }
 
 /// An eliminator that is part of the spine of a [stuck value][`Value::Stuck`].
@@ -151,6 +197,50 @@ pub enum Elim {
     ///
     /// Also known as: record projections, field lookup.
     Record(String),
+    /// Enumeration eliminators.
+    ///
+    /// This eliminator can be applied to a [`Value`] with the
+    /// [`apply_enum_elim`] function.
+    ///
+    /// Also known as: case analysis, match expressions.
+    Enum(EnumClosure),
+    /// Variant eliminators.
+    ///
+    /// This eliminator can be applied to a [`Value`] with the
+    /// [`apply_variant_elim`] function.
+    ///
+    /// Also known as: case analysis, match expressions.
+    Variant(VariantClosure),
+    /// Inductive eliminators, consisting of a motive and the branches for
+    /// each constructor.
+    ///
+    /// This eliminator can be applied to a [`Value`] with the
+    /// [`apply_inductive_elim`] function.
+    ///
+    /// Also known as: recursors, induction principles.
+    Inductive(Arc<Value>, InductiveElimClosure),
+    /// Boolean eliminators.
+    ///
+    /// This eliminator can be applied to a [`Value`] with the
+    /// [`apply_bool_elim`] function.
+    ///
+    /// Also known as: if-then-else expressions.
+    Bool(BoolClosure),
+    /// Equality eliminators, consisting of a motive and the value to be
+    /// transported.
+    ///
+    /// This eliminator can be applied to a [`Value`] with the
+    /// [`apply_eq_elim`] function.
+    ///
+    /// Also known as: transport, substitution.
+    Eq(Arc<Value>, Arc<Value>),
+    /// Code eliminators.
+    ///
+    /// This eliminator can be applied to a [`Value`] with the
+    /// [`apply_code_elim`] function.
+    ///
+    /// Also known as: splices, antiquotations.
+    Code,
 }
 
 /// Function closure, capturing the current universe offset and the current locals in scope.
@@ -182,20 +272,93 @@ impl FunctionClosure {
     }
 }
 
+/// Recursive closure, capturing the current universe offset and the current locals in scope.
+#[derive(Clone, Debug)]
+pub struct FixClosure {
+    universe_offset: UniverseOffset,
+    locals: Locals<Arc<Value>>,
+    r#type: Arc<Term>,
+    term: Arc<Term>,
+}
+
+impl FixClosure {
+    pub fn new(
+        universe_offset: UniverseOffset,
+        locals: Locals<Arc<Value>>,
+        r#type: Arc<Term>,
+        term: Arc<Term>,
+    ) -> FixClosure {
+        FixClosure {
+            universe_offset,
+            locals,
+            r#type,
+            term,
+        }
+    }
+
+    /// Evaluate the type of the recursive term.
+    pub fn r#type(&self, globals: &Globals) -> Arc<Value> {
+        let mut locals = self.locals.clone();
+        eval_term(globals, self.universe_offset, &mut locals, &self.r#type)
+    }
+
+    /// Apply a value for the recursive variable to the body of the closure.
+    pub fn apply(&self, globals: &Globals, value: Arc<Value>) -> Arc<Value> {
+        let mut locals = self.locals.clone();
+        locals.push(value);
+        eval_term(globals, self.universe_offset, &mut locals, &self.term)
+    }
+
+    /// Unfold the body of the closure once, binding the recursive variable to
+    /// the recursive term itself.
+    pub fn unfold(&self, globals: &Globals, name: &str) -> Arc<Value> {
+        let head = Head::Fix(name.to_owned(), self.clone());
+        self.apply(globals, Arc::new(Value::Stuck(head, Vec::new())))
+    }
+}
+
+/// The entries of record types and record terms.
+pub trait RecordEntry {
+    /// The label of the entry.
+    fn label(&self) -> &str;
+    /// The term to be evaluated for the entry.
+    fn term(&self) -> &Arc<Term>;
+}
+
+impl RecordEntry for (String, Arc<Term>) {
+    fn label(&self) -> &str {
+        &self.0
+    }
+
+    fn term(&self) -> &Arc<Term> {
+        &self.1
+    }
+}
+
+impl RecordEntry for TypeEntry {
+    fn label(&self) -> &str {
+        &self.0
+    }
+
+    fn term(&self) -> &Arc<Term> {
+        &self.3
+    }
+}
+
 /// Record closure, capturing the current universe offset and the current locals in scope.
 #[derive(Clone, Debug)]
-pub struct RecordClosure {
+pub struct RecordClosure<Entry = (String, Arc<Term>)> {
     universe_offset: UniverseOffset,
     locals: Locals<Arc<Value>>,
-    entries: Arc<[(String, Arc<Term>)]>,
+    entries: Arc<[Entry]>,
 }
 
-impl RecordClosure {
+impl<Entry: RecordEntry> RecordClosure<Entry> {
     pub fn new(
         universe_offset: UniverseOffset,
         locals: Locals<Arc<Value>>,
-        entries: Arc<[(String, Arc<Term>)]>,
-    ) -> RecordClosure {
+        entries: Arc<[Entry]>,
+    ) -> RecordClosure<Entry> {
         RecordClosure {
             universe_offset,
             locals,
@@ -203,6 +366,16 @@ impl RecordClosure {
         }
     }
 
+    /// The entries in the record closure.
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// The labels of the entries in the record closure.
+    pub fn labels(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(RecordEntry::label)
+    }
+
     /// Apply a callback to each of the entries in the record closure.
     pub fn for_each_entry<'closure>(
         &'closure self,
@@ -212,12 +385,27 @@ impl RecordClosure {
         let universe_offset = self.universe_offset;
         let mut locals = self.locals.clone();
 
-        for (label, entry_value) in self.entries.iter() {
-            let entry_value = eval_term(globals, universe_offset, &mut locals, entry_value);
-            locals.push(on_entry(label, entry_value));
+        for entry in self.entries.iter() {
+            let entry_value = eval_term(globals, universe_offset, &mut locals, entry.term());
+            locals.push(on_entry(entry.label(), entry_value));
         }
     }
 
+    /// Evaluate a term in the scope of the closure, with the given values
+    /// bound to its first entries.
+    pub fn eval_with_entries(
+        &self,
+        globals: &Globals,
+        entry_values: impl IntoIterator<Item = Arc<Value>>,
+        term: &Term,
+    ) -> Arc<Value> {
+        let mut locals = self.locals.clone();
+        for entry_value in entry_values {
+            locals.push(entry_value);
+        }
+        eval_term(globals, self.universe_offset, &mut locals, term)
+    }
+
     /// Find an entry in the record closure.
     pub fn find_entry<'closure, T>(
         &'closure self,
@@ -227,9 +415,9 @@ impl RecordClosure {
         let universe_offset = self.universe_offset;
         let mut locals = self.locals.clone();
 
-        for (label, entry_value) in self.entries.iter() {
-            let entry_value = eval_term(globals, universe_offset, &mut locals, entry_value);
-            match on_entry(label, entry_value) {
+        for entry in self.entries.iter() {
+            let entry_value = eval_term(globals, universe_offset, &mut locals, entry.term());
+            match on_entry(entry.label(), entry_value) {
                 Ok(t) => return Some(t),
                 Err(entry_value) => locals.push(entry_value),
             }
@@ -239,6 +427,285 @@ impl RecordClosure {
     }
 }
 
+/// Enumeration closure, capturing the current universe offset and the current locals in scope.
+#[derive(Clone, Debug)]
+pub struct EnumClosure {
+    universe_offset: UniverseOffset,
+    locals: Locals<Arc<Value>>,
+    branches: Arc<[(String, Arc<Term>)]>,
+}
+
+impl EnumClosure {
+    pub fn new(
+        universe_offset: UniverseOffset,
+        locals: Locals<Arc<Value>>,
+        branches: Arc<[(String, Arc<Term>)]>,
+    ) -> EnumClosure {
+        EnumClosure {
+            universe_offset,
+            locals,
+            branches,
+        }
+    }
+
+    /// Apply a label to the enumeration closure, evaluating the matching branch.
+    pub fn apply(&self, globals: &Globals, label: &str) -> Option<Arc<Value>> {
+        let (_, branch_term) = self.branches.iter().find(|(l, _)| l == label)?;
+        let mut locals = self.locals.clone();
+        Some(eval_term(
+            globals,
+            self.universe_offset,
+            &mut locals,
+            branch_term,
+        ))
+    }
+
+    /// Apply a callback to each of the branches in the enumeration closure.
+    pub fn for_each_branch<'closure>(
+        &'closure self,
+        globals: &Globals,
+        mut on_branch: impl FnMut(&'closure str, Arc<Value>),
+    ) {
+        let mut locals = self.locals.clone();
+
+        for (label, branch_term) in self.branches.iter() {
+            on_branch(
+                label,
+                eval_term(globals, self.universe_offset, &mut locals, branch_term),
+            );
+        }
+    }
+}
+
+/// Variant closure, capturing the current universe offset and the current locals in scope.
+#[derive(Clone, Debug)]
+pub struct VariantClosure {
+    universe_offset: UniverseOffset,
+    locals: Locals<Arc<Value>>,
+    branches: Arc<[VariantBranch]>,
+}
+
+impl VariantClosure {
+    pub fn new(
+        universe_offset: UniverseOffset,
+        locals: Locals<Arc<Value>>,
+        branches: Arc<[VariantBranch]>,
+    ) -> VariantClosure {
+        VariantClosure {
+            universe_offset,
+            locals,
+            branches,
+        }
+    }
+
+    /// Apply a label and a payload to the variant closure, evaluating the
+    /// matching branch.
+    pub fn apply(&self, globals: &Globals, label: &str, payload: Arc<Value>) -> Option<Arc<Value>> {
+        let (_, _, branch_term) = self.branches.iter().find(|(l, _, _)| l == label)?;
+        let mut locals = self.locals.clone();
+        locals.push(payload);
+        Some(eval_term(
+            globals,
+            self.universe_offset,
+            &mut locals,
+            branch_term,
+        ))
+    }
+
+    /// Apply a callback to each of the branches in the variant closure, with
+    /// the given payload bound in each branch.
+    pub fn for_each_branch<'closure>(
+        &'closure self,
+        globals: &Globals,
+        payload: Arc<Value>,
+        mut on_branch: impl FnMut(&'closure str, Option<&'closure str>, Arc<Value>),
+    ) {
+        for (label, name_hint, branch_term) in self.branches.iter() {
+            let mut locals = self.locals.clone();
+            locals.push(payload.clone());
+            on_branch(
+                label,
+                name_hint.as_deref(),
+                eval_term(globals, self.universe_offset, &mut locals, branch_term),
+            );
+        }
+    }
+}
+
+/// Inductive closure, capturing the current universe offset and the current locals in scope.
+#[derive(Clone, Debug)]
+pub struct InductiveClosure {
+    universe_offset: UniverseOffset,
+    locals: Locals<Arc<Value>>,
+    name_hint: String,
+    constructors: Arc<[(String, Arc<Term>)]>,
+}
+
+impl InductiveClosure {
+    pub fn new(
+        universe_offset: UniverseOffset,
+        locals: Locals<Arc<Value>>,
+        name_hint: String,
+        constructors: Arc<[(String, Arc<Term>)]>,
+    ) -> InductiveClosure {
+        InductiveClosure {
+            universe_offset,
+            locals,
+            name_hint,
+            constructors,
+        }
+    }
+
+    /// The name hint of the inductive type.
+    pub fn name_hint(&self) -> &str {
+        &self.name_hint
+    }
+
+    /// The labels of the constructors in the inductive closure.
+    pub fn labels(&self) -> impl Iterator<Item = &str> + Clone {
+        self.constructors.iter().map(|(label, _)| label.as_str())
+    }
+
+    /// Return the type of the constructor with the given label, with the
+    /// inductive type bound to the given value.
+    pub fn constructor_type(
+        &self,
+        globals: &Globals,
+        label: &str,
+        inductive_type: Arc<Value>,
+    ) -> Option<Arc<Value>> {
+        let (_, constructor_type) = self.constructors.iter().find(|(l, _)| l == label)?;
+        let mut locals = self.locals.clone();
+        locals.push(inductive_type);
+        Some(eval_term(
+            globals,
+            self.universe_offset,
+            &mut locals,
+            constructor_type,
+        ))
+    }
+
+    /// Return whether each of the inputs of the constructor with the given
+    /// label are recursive occurrences of the inductive type.
+    pub fn recursive_inputs(&self, label: &str) -> Option<Vec<bool>> {
+        let (_, constructor_type) = self.constructors.iter().find(|(l, _)| l == label)?;
+        let mut recursive_inputs = Vec::new();
+        let mut current_type = constructor_type;
+
+        while let TermData::FunctionType(_, _, _, input_type, output_type) = &current_type.data {
+            let depth = recursive_inputs.len() as u32;
+            recursive_inputs.push(match input_type.data {
+                TermData::Local(index) => index.0 == depth,
+                _ => false,
+            });
+            current_type = output_type;
+        }
+
+        Some(recursive_inputs)
+    }
+}
+
+/// Inductive elimination closure, capturing the current universe offset and
+/// the current locals in scope.
+#[derive(Clone, Debug)]
+pub struct InductiveElimClosure {
+    universe_offset: UniverseOffset,
+    locals: Locals<Arc<Value>>,
+    branches: Arc<[InductiveBranch]>,
+}
+
+impl InductiveElimClosure {
+    pub fn new(
+        universe_offset: UniverseOffset,
+        locals: Locals<Arc<Value>>,
+        branches: Arc<[InductiveBranch]>,
+    ) -> InductiveElimClosure {
+        InductiveElimClosure {
+            universe_offset,
+            locals,
+            branches,
+        }
+    }
+
+    /// Apply a label to the closure, evaluating the matching branch with the
+    /// given values bound to its locals.
+    pub fn apply(
+        &self,
+        globals: &Globals,
+        label: &str,
+        values: impl IntoIterator<Item = Arc<Value>>,
+    ) -> Option<Arc<Value>> {
+        let (_, _, branch_term) = self.branches.iter().find(|(l, _, _)| l == label)?;
+        let mut locals = self.locals.clone();
+        for value in values {
+            locals.push(value);
+        }
+        Some(eval_term(
+            globals,
+            self.universe_offset,
+            &mut locals,
+            branch_term,
+        ))
+    }
+
+    /// Apply a callback to each of the branches in the closure, with fresh
+    /// local variables bound to the locals of each branch. The callback is
+    /// also supplied the size of the environment that the branch is in.
+    fn for_each_branch<'closure>(
+        &'closure self,
+        globals: &Globals,
+        local_size: LocalSize,
+        mut on_branch: impl FnMut(&'closure str, &'closure [Option<String>], LocalSize, Arc<Value>),
+    ) {
+        for (label, name_hints, branch_term) in self.branches.iter() {
+            let mut locals = self.locals.clone();
+            let mut local_size = local_size;
+            for _ in name_hints.iter() {
+                locals.push(Arc::new(Value::local(local_size.next_level(), [])));
+                local_size = local_size.increment();
+            }
+            let branch_value = eval_term(globals, self.universe_offset, &mut locals, branch_term);
+            on_branch(label, name_hints, local_size, branch_value);
+        }
+    }
+}
+
+/// Boolean closure, capturing the current universe offset and the current locals in scope.
+#[derive(Clone, Debug)]
+pub struct BoolClosure {
+    universe_offset: UniverseOffset,
+    locals: Locals<Arc<Value>>,
+    if_true: Arc<Term>,
+    if_false: Arc<Term>,
+}
+
+impl BoolClosure {
+    pub fn new(
+        universe_offset: UniverseOffset,
+        locals: Locals<Arc<Value>>,
+        if_true: Arc<Term>,
+        if_false: Arc<Term>,
+    ) -> BoolClosure {
+        BoolClosure {
+            universe_offset,
+            locals,
+            if_true,
+            if_false,
+        }
+    }
+
+    /// Apply a boolean to the boolean closure, evaluating the selected branch.
+    pub fn apply(&self, globals: &Globals, condition: bool) -> Arc<Value> {
+        let mut locals = self.locals.clone();
+        let branch_term = if condition {
+            &self.if_true
+        } else {
+            &self.if_false
+        };
+        eval_term(globals, self.universe_offset, &mut locals, branch_term)
+    }
+}
+
 /// Initialization operation for lazy values.
 ///
 /// We need to use a [defunctionalized] representation because Rust does not allow
@@ -249,6 +716,7 @@ impl RecordClosure {
 enum LazyInit {
     EvalTerm(UniverseOffset, Locals<Arc<Value>>, Arc<Term>),
     ApplyElim(Arc<LazyValue>, Elim),
+    UnfoldFix(String, FixClosure),
 }
 
 /// A lazily initialized value.
@@ -281,6 +749,14 @@ impl LazyValue {
         }
     }
 
+    /// Lazily unfold a recursive term.
+    pub fn unfold_fix(name: String, closure: FixClosure) -> LazyValue {
+        LazyValue {
+            init: RefCell::new(Some(LazyInit::UnfoldFix(name, closure))),
+            cell: OnceCell::new(),
+        }
+    }
+
     /// Lazily apply an elimination.
     pub fn apply_elim(head: Arc<LazyValue>, elim: Elim) -> LazyValue {
         LazyValue {
@@ -295,12 +771,31 @@ impl LazyValue {
             Some(LazyInit::EvalTerm(universe_offset, mut locals, term)) => {
                 eval_term(globals, universe_offset, &mut locals, &term)
             }
+            Some(LazyInit::UnfoldFix(name, closure)) => closure.unfold(globals, &name),
             Some(LazyInit::ApplyElim(head, Elim::Record(label))) => {
                 apply_record_elim(globals, head.force(globals).clone(), &label)
             }
             Some(LazyInit::ApplyElim(head, Elim::Function(input))) => {
                 apply_function_elim(globals, head.force(globals).clone(), input)
             }
+            Some(LazyInit::ApplyElim(head, Elim::Enum(closure))) => {
+                apply_enum_elim(globals, head.force(globals).clone(), closure)
+            }
+            Some(LazyInit::ApplyElim(head, Elim::Variant(closure))) => {
+                apply_variant_elim(globals, head.force(globals).clone(), closure)
+            }
+            Some(LazyInit::ApplyElim(head, Elim::Inductive(motive, closure))) => {
+                apply_inductive_elim(globals, head.force(globals).clone(), motive, closure)
+            }
+            Some(LazyInit::ApplyElim(head, Elim::Bool(closure))) => {
+                apply_bool_elim(globals, head.force(globals).clone(), closure)
+            }
+            Some(LazyInit::ApplyElim(head, Elim::Eq(motive, value))) => {
+                apply_eq_elim(head.force(globals).clone(), motive, value)
+            }
+            Some(LazyInit::ApplyElim(head, Elim::Code)) => {
+                apply_code_elim(globals, head.force(globals).clone())
+            }
             None => panic!("Lazy instance has previously been poisoned"),
         })
     }
@@ -344,6 +839,7 @@ pub fn eval_term(
                 Arc::new(Value::Stuck(head, Vec::new()))
             }
         },
+        TermData::Meta(index) => Arc::new(Value::Stuck(Head::Meta(*index), Vec::new())),
         TermData::Local(index) => match locals.get(*index) {
             Some(value) => value.clone(),
             // FIXME: Local gluing is kind of broken right now :(
@@ -360,6 +856,14 @@ pub fn eval_term(
 
         TermData::Ann(term, _) => eval_term(globals, universe_offset, locals, term),
 
+        TermData::Let(_, def_term, output_term) => {
+            let def_value = eval_term(globals, universe_offset, locals, def_term);
+            locals.push(def_value);
+            let output_value = eval_term(globals, universe_offset, locals, output_term);
+            locals.pop();
+            output_value
+        }
+
         TermData::TypeType(level) => {
             let universe_level = (*level + universe_offset).unwrap(); // FIXME: Handle overflow
             Arc::new(Value::type_type(universe_level))
@@ -384,8 +888,10 @@ pub fn eval_term(
             apply_record_elim(globals, head, label)
         }
 
-        TermData::FunctionType(input_name_hint, input_type, output_type) => {
+        TermData::FunctionType(plicity, usage, input_name_hint, input_type, output_type) => {
             Arc::new(Value::FunctionType(
+                *plicity,
+                *usage,
                 input_name_hint.clone(),
                 eval_term(globals, universe_offset, locals, input_type),
                 FunctionClosure::new(universe_offset, locals.clone(), output_type.clone()),
@@ -401,6 +907,111 @@ pub fn eval_term(
             apply_function_elim(globals, head, Arc::new(input))
         }
 
+        TermData::Fix(name, r#type, term) => {
+            let closure = FixClosure::new(
+                universe_offset,
+                locals.clone(),
+                r#type.clone(),
+                term.clone(),
+            );
+            Arc::new(Value::Stuck(Head::Fix(name.clone(), closure), Vec::new()))
+        }
+
+        TermData::EnumType(labels) => Arc::new(Value::EnumType(labels.clone())),
+        TermData::EnumTerm(label) => Arc::new(Value::EnumTerm(label.clone())),
+        TermData::EnumElim(head, branches) => {
+            let head = eval_term(globals, universe_offset, locals, head);
+            let closure = EnumClosure::new(universe_offset, locals.clone(), branches.clone());
+            apply_enum_elim(globals, head, closure)
+        }
+
+        TermData::VariantType(type_entries) => Arc::new(Value::VariantType(
+            (type_entries.iter())
+                .map(|(label, r#type)| {
+                    let r#type = eval_term(globals, universe_offset, locals, r#type);
+                    (label.clone(), r#type)
+                })
+                .collect(),
+        )),
+        TermData::VariantTerm(label, term) => Arc::new(Value::VariantTerm(
+            label.clone(),
+            eval_term(globals, universe_offset, locals, term),
+        )),
+        TermData::VariantElim(head, branches) => {
+            let head = eval_term(globals, universe_offset, locals, head);
+            let closure = VariantClosure::new(universe_offset, locals.clone(), branches.clone());
+            apply_variant_elim(globals, head, closure)
+        }
+
+        TermData::InductiveType(name_hint, constructors) => {
+            Arc::new(Value::InductiveType(InductiveClosure::new(
+                universe_offset,
+                locals.clone(),
+                name_hint.clone(),
+                constructors.clone(),
+            )))
+        }
+        TermData::InductiveTerm(r#type, label, inputs) => Arc::new(Value::InductiveTerm(
+            eval_term(globals, universe_offset, locals, r#type),
+            label.clone(),
+            (inputs.iter())
+                .map(|input| eval_term(globals, universe_offset, locals, input))
+                .collect(),
+        )),
+        TermData::InductiveElim(head, motive, branches) => {
+            let head = eval_term(globals, universe_offset, locals, head);
+            let motive = eval_term(globals, universe_offset, locals, motive);
+            let closure =
+                InductiveElimClosure::new(universe_offset, locals.clone(), branches.clone());
+            apply_inductive_elim(globals, head, motive, closure)
+        }
+
+        TermData::BoolElim(head, if_true, if_false) => {
+            let head = eval_term(globals, universe_offset, locals, head);
+            let closure = BoolClosure::new(
+                universe_offset,
+                locals.clone(),
+                if_true.clone(),
+                if_false.clone(),
+            );
+            apply_bool_elim(globals, head, closure)
+        }
+
+        TermData::EqType(r#type, lhs, rhs) => Arc::new(Value::EqType(
+            eval_term(globals, universe_offset, locals, r#type),
+            eval_term(globals, universe_offset, locals, lhs),
+            eval_term(globals, universe_offset, locals, rhs),
+        )),
+        TermData::EqTerm => Arc::new(Value::EqTerm),
+        TermData::EqElim(head, motive, term) => {
+            let head = eval_term(globals, universe_offset, locals, head);
+            let motive = eval_term(globals, universe_offset, locals, motive);
+            let value = eval_term(globals, universe_offset, locals, term);
+            apply_eq_elim(head, motive, value)
+        }
+
+        TermData::CodeType(r#type) => Arc::new(Value::CodeType(eval_term(
+            globals,
+            universe_offset,
+            locals,
+            r#type,
+        ))),
+        TermData::CodeTerm(term) => {
+            let value = eval_term(globals, universe_offset, locals, term);
+            match value.as_ref() {
+                // Quoting a splice gives back the code that was spliced.
+                Value::Stuck(head, spine) if matches!(spine.last(), Some(Elim::Code)) => {
+                    let spine = spine[..spine.len() - 1].to_vec();
+                    Arc::new(Value::Stuck(head.clone(), spine))
+                }
+                _ => Arc::new(Value::CodeTerm(value)),
+            }
+        }
+        TermData::CodeElim(head) => {
+            let head = eval_term(globals, universe_offset, locals, head);
+            apply_code_elim(globals, head)
+        }
+
         TermData::ArrayTerm(term_entries) => {
             let value_entries = term_entries
                 .iter()
@@ -418,79 +1029,419 @@ pub fn eval_term(
             Arc::new(Value::ListTerm(value_entries))
         }
 
-        TermData::Constant(constant) => Arc::new(Value::from(constant.clone())),
+        TermData::Constant(constant) => Arc::new(Value::from(constant.clone())),
+
+        TermData::Error => Arc::new(Value::Error),
+    }
+}
+
+/// Return the type of the record elimination.
+pub fn record_elim_type(
+    globals: &Globals,
+    head_value: Arc<Value>,
+    label: &str,
+    closure: &RecordClosure<TypeEntry>,
+) -> Option<Arc<Value>> {
+    closure.find_entry(globals, |entry_label, entry_type| {
+        if entry_label == label {
+            Ok(entry_type)
+        } else {
+            Err(apply_record_elim(globals, head_value.clone(), entry_label))
+        }
+    })
+}
+
+/// Check that the first record type contains the entries of the second record
+/// type, in the same order and with the same usages, comparing the types of
+/// the entries that share a label with the given callback. The callback is
+/// also supplied the size of the environment that the entry types are in.
+/// Entries that share a label are bound to the same local variable.
+pub fn compare_record_subtype_entries(
+    globals: &Globals,
+    local_size: LocalSize,
+    closure0: &RecordClosure<TypeEntry>,
+    closure1: &RecordClosure<TypeEntry>,
+    mut compare_entry_types: impl FnMut(LocalSize, &Arc<Value>, &Arc<Value>) -> bool,
+) -> bool {
+    let mut local_size = local_size;
+    let mut entries0 = Vec::with_capacity(closure0.entries.len());
+    let mut usages0 = closure0.entries.iter().map(|(_, _, usage, _, _)| *usage);
+
+    closure0.for_each_entry(globals, |label, entry_type| {
+        let local = Arc::new(Value::local(local_size.next_level(), []));
+        let usage = usages0.next();
+        entries0.push((label, usage, local_size, local.clone(), entry_type));
+        local_size = local_size.increment();
+        local
+    });
+
+    let mut entries = Vec::with_capacity(closure1.entries.len());
+    let mut pending_entries0 = entries0.iter();
+    let mut is_subset = true;
+    let mut usages1 = closure1.entries.iter().map(|(_, _, usage, _, _)| *usage);
+
+    closure1.for_each_entry(globals, |label, entry_type1| {
+        let usage1 = usages1.next();
+        match pending_entries0.find(|(label0, _, _, _, _)| *label0 == label) {
+            Some((_, usage0, local_size, local, entry_type0)) => {
+                is_subset &= *usage0 == usage1;
+                entries.push((*local_size, entry_type0.clone(), entry_type1));
+                local.clone()
+            }
+            None => {
+                is_subset = false;
+                Arc::new(Value::Error)
+            }
+        }
+    });
+
+    is_subset
+        && (entries.iter()).all(|(local_size, entry_type0, entry_type1)| {
+            compare_entry_types(*local_size, entry_type0, entry_type1)
+        })
+}
+
+/// Apply a record term elimination.
+fn apply_record_elim(globals: &Globals, mut head_value: Arc<Value>, label: &str) -> Arc<Value> {
+    match Arc::make_mut(&mut head_value) {
+        Value::Stuck(_, spine) => {
+            spine.push(Elim::Record(label.to_owned()));
+            head_value
+        }
+        Value::Unstuck(_, spine, value) => {
+            spine.push(Elim::Record(label.to_owned()));
+            *value = Arc::new(LazyValue::apply_elim(
+                value.clone(),
+                Elim::Record(label.to_owned()),
+            ));
+            head_value
+        }
+
+        Value::RecordTerm(closure) => closure
+            .find_entry(globals, |entry_label, entry_value| {
+                if entry_label == label {
+                    Ok(entry_value)
+                } else {
+                    Err(entry_value)
+                }
+            })
+            .unwrap_or_else(|| Arc::new(Value::Error)),
+
+        _ => Arc::new(Value::Error),
+    }
+}
+
+/// Return the type of an equality elimination, given its motive, and the side
+/// of the equality that the motive should be applied to.
+pub fn eq_elim_type(globals: &Globals, motive: Arc<Value>, side: Arc<Value>) -> Arc<Value> {
+    apply_function_elim(globals, motive, Arc::new(LazyValue::new(side)))
+}
+
+/// Return the type of the motive of an equality or inductive elimination,
+/// which is a function from the eliminated type to `Type^0`.
+///
+/// Motives that return types in higher universes are not yet supported. This
+/// would require the universe level to be taken from the motive itself.
+pub fn motive_type(
+    universe_offset: UniverseOffset,
+    locals: Locals<Arc<Value>>,
+    r#type: Arc<Value>,
+) -> Arc<Value> {
+    let output_type = Term::generated(TermData::TypeType(UniverseLevel::Finite(0)));
+    let output_closure = FunctionClosure::new(universe_offset, locals, Arc::new(output_type));
+    Arc::new(Value::FunctionType(
+        Plicity::Explicit,
+        Usage::Many,
+        None,
+        r#type,
+        output_closure,
+    ))
+}
+
+/// Apply a function term elimination.
+fn apply_function_elim(
+    globals: &Globals,
+    mut head_value: Arc<Value>,
+    input: Arc<LazyValue>,
+) -> Arc<Value> {
+    match Arc::make_mut(&mut head_value) {
+        Value::Stuck(head, spine) => {
+            spine.push(Elim::Function(input));
+            match unfold_fix(globals, head, spine).or_else(|| apply_primitive(globals, head, spine))
+            {
+                Some(value) => Arc::new(value),
+                None => head_value,
+            }
+        }
+        Value::Unstuck(_, spine, value) => {
+            spine.push(Elim::Function(input.clone()));
+            *value = Arc::new(LazyValue::apply_elim(value.clone(), Elim::Function(input)));
+            head_value
+        }
+
+        Value::FunctionTerm(_, output_closure) => {
+            output_closure.apply(globals, input.force(globals).clone())
+        }
+
+        _ => Arc::new(Value::Error),
+    }
+}
+
+/// Attempt to unfold a stuck recursive term, returning `None` if the head is
+/// not a recursive term, or if its first input is [neutral][is_neutral].
+///
+/// Guarding the unfolding in this way ensures that reading back and comparing
+/// recursive functions that are applied to local variables will terminate.
+/// The unfolding itself is deferred until the [unstuck value][Value::Unstuck]
+/// is forced, so recursive terms with equal inputs can be compared without
+/// being computed.
+fn unfold_fix(globals: &Globals, head: &Head, spine: &[Elim]) -> Option<Value> {
+    let (name, closure) = match head {
+        Head::Fix(name, closure) => (name, closure),
+        Head::Global(_, _) | Head::Local(_) | Head::Meta(_) => return None,
+    };
+
+    match spine.first() {
+        Some(Elim::Function(input)) if !is_neutral(globals, input.force(globals)) => {
+            let fix_value = LazyValue::unfold_fix(name.clone(), closure.clone());
+            let value = (spine.iter()).fold(fix_value, |value, elim| {
+                LazyValue::apply_elim(Arc::new(value), elim.clone())
+            });
+            Some(Value::Unstuck(
+                head.clone(),
+                spine.to_vec(),
+                Arc::new(value),
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Returns `true` if the value is blocked on a local variable, a metavariable,
+/// or a recursive term that could not be unfolded.
+///
+/// Stuck globals, like `true` or `none`, are only neutral if they are applied
+/// to neutral inputs.
+fn is_neutral(globals: &Globals, value: &Value) -> bool {
+    match value.force(globals) {
+        Value::Stuck(Head::Local(_), _)
+        | Value::Stuck(Head::Meta(_), _)
+        | Value::Stuck(Head::Fix(_, _), _) => true,
+        Value::Stuck(Head::Global(_, _), spine) => spine.iter().any(|elim| match elim {
+            Elim::Function(input) => is_neutral(globals, input.force(globals)),
+            _ => true,
+        }),
+        _ => false,
+    }
+}
+
+/// Attempt to reduce a stuck primitive operation, returning `None` if the
+/// head is not a primitive operation, or if the primitive could not be applied
+/// to the inputs in the spine.
+fn apply_primitive(globals: &Globals, head: &Head, spine: &[Elim]) -> Option<Value> {
+    let primitive = match head {
+        Head::Global(name, _) => globals.get_primitive(name)?,
+        Head::Local(_) | Head::Meta(_) | Head::Fix(_, _) => return None,
+    };
+
+    if spine.len() != primitive.arity() {
+        return None;
+    }
+
+    let inputs = spine
+        .iter()
+        .map(|elim| match elim {
+            Elim::Function(input) => match input.force(globals).force(globals) {
+                Value::Constant(constant) => Some(constant),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    primitive.apply(&inputs)
+}
+
+/// Apply an enumeration term elimination.
+fn apply_enum_elim(
+    globals: &Globals,
+    mut head_value: Arc<Value>,
+    closure: EnumClosure,
+) -> Arc<Value> {
+    match Arc::make_mut(&mut head_value) {
+        Value::Stuck(_, spine) => {
+            spine.push(Elim::Enum(closure));
+            head_value
+        }
+        Value::Unstuck(_, spine, value) => {
+            spine.push(Elim::Enum(closure.clone()));
+            *value = Arc::new(LazyValue::apply_elim(value.clone(), Elim::Enum(closure)));
+            head_value
+        }
+
+        Value::EnumTerm(label) => closure
+            .apply(globals, label)
+            .unwrap_or_else(|| Arc::new(Value::Error)),
+
+        _ => Arc::new(Value::Error),
+    }
+}
+
+/// Apply a variant term elimination.
+fn apply_variant_elim(
+    globals: &Globals,
+    mut head_value: Arc<Value>,
+    closure: VariantClosure,
+) -> Arc<Value> {
+    match Arc::make_mut(&mut head_value) {
+        Value::Stuck(_, spine) => {
+            spine.push(Elim::Variant(closure));
+            head_value
+        }
+        Value::Unstuck(_, spine, value) => {
+            spine.push(Elim::Variant(closure.clone()));
+            *value = Arc::new(LazyValue::apply_elim(value.clone(), Elim::Variant(closure)));
+            head_value
+        }
+
+        Value::VariantTerm(label, payload) => closure
+            .apply(globals, label, payload.clone())
+            .unwrap_or_else(|| Arc::new(Value::Error)),
 
-        TermData::Error => Arc::new(Value::Error),
+        _ => Arc::new(Value::Error),
     }
 }
 
-/// Return the type of the record elimination.
-pub fn record_elim_type(
-    globals: &Globals,
-    head_value: Arc<Value>,
-    label: &str,
-    closure: &RecordClosure,
-) -> Option<Arc<Value>> {
-    closure.find_entry(globals, |entry_label, entry_type| {
-        if entry_label == label {
-            Ok(entry_type)
-        } else {
-            Err(apply_record_elim(globals, head_value.clone(), label))
-        }
-    })
+/// Return the type of an inductive elimination, or of the branch for an
+/// inductive term.
+pub fn inductive_elim_type(globals: &Globals, motive: Arc<Value>, head: Arc<Value>) -> Arc<Value> {
+    apply_function_elim(globals, motive, Arc::new(LazyValue::new(head)))
 }
 
-/// Apply a record term elimination.
-fn apply_record_elim(globals: &Globals, mut head_value: Arc<Value>, label: &str) -> Arc<Value> {
+/// Apply an inductive term elimination.
+///
+/// The recursive inputs of inductive terms are eliminated before being bound
+/// in the matching branch, along with the inputs themselves.
+fn apply_inductive_elim(
+    globals: &Globals,
+    mut head_value: Arc<Value>,
+    motive: Arc<Value>,
+    closure: InductiveElimClosure,
+) -> Arc<Value> {
     match Arc::make_mut(&mut head_value) {
         Value::Stuck(_, spine) => {
-            spine.push(Elim::Record(label.to_owned()));
+            spine.push(Elim::Inductive(motive, closure));
             head_value
         }
         Value::Unstuck(_, spine, value) => {
-            spine.push(Elim::Record(label.to_owned()));
+            spine.push(Elim::Inductive(motive.clone(), closure.clone()));
             *value = Arc::new(LazyValue::apply_elim(
                 value.clone(),
-                Elim::Record(label.to_owned()),
+                Elim::Inductive(motive, closure),
             ));
             head_value
         }
 
-        Value::RecordTerm(closure) => closure
-            .find_entry(globals, |entry_label, entry_value| {
-                if entry_label == label {
-                    Ok(entry_value)
-                } else {
-                    Err(entry_value)
-                }
-            })
-            .unwrap_or_else(|| Arc::new(Value::Error)),
+        Value::InductiveTerm(r#type, label, inputs) => {
+            let recursive_inputs = match r#type.force(globals) {
+                Value::InductiveType(type_closure) => type_closure.recursive_inputs(label),
+                _ => None,
+            };
+            let recursive_inputs = match recursive_inputs {
+                Some(recursive_inputs) => recursive_inputs,
+                None => return Arc::new(Value::Error),
+            };
+
+            let recursive_values = Iterator::zip(inputs.iter(), recursive_inputs)
+                .filter(|(_, is_recursive)| *is_recursive)
+                .map(|(input, _)| {
+                    apply_inductive_elim(globals, input.clone(), motive.clone(), closure.clone())
+                })
+                .collect::<Vec<_>>();
+
+            closure
+                .apply(
+                    globals,
+                    label,
+                    inputs.iter().cloned().chain(recursive_values),
+                )
+                .unwrap_or_else(|| Arc::new(Value::Error))
+        }
 
         _ => Arc::new(Value::Error),
     }
 }
 
-/// Apply a function term elimination.
-fn apply_function_elim(
+/// Apply a boolean term elimination.
+fn apply_bool_elim(
     globals: &Globals,
     mut head_value: Arc<Value>,
-    input: Arc<LazyValue>,
+    closure: BoolClosure,
 ) -> Arc<Value> {
+    match head_value.try_global() {
+        Some(("true", _, [])) => return closure.apply(globals, true),
+        Some(("false", _, [])) => return closure.apply(globals, false),
+        Some(_) | None => {}
+    }
+
     match Arc::make_mut(&mut head_value) {
         Value::Stuck(_, spine) => {
-            spine.push(Elim::Function(input));
+            spine.push(Elim::Bool(closure));
             head_value
         }
         Value::Unstuck(_, spine, value) => {
-            spine.push(Elim::Function(input.clone()));
-            *value = Arc::new(LazyValue::apply_elim(value.clone(), Elim::Function(input)));
+            spine.push(Elim::Bool(closure.clone()));
+            *value = Arc::new(LazyValue::apply_elim(value.clone(), Elim::Bool(closure)));
             head_value
         }
 
-        Value::FunctionTerm(_, output_closure) => {
-            output_closure.apply(globals, input.force(globals).clone())
+        _ => Arc::new(Value::Error),
+    }
+}
+
+/// Apply an equality term elimination.
+fn apply_eq_elim(mut head_value: Arc<Value>, motive: Arc<Value>, value: Arc<Value>) -> Arc<Value> {
+    match Arc::make_mut(&mut head_value) {
+        Value::Stuck(_, spine) => {
+            spine.push(Elim::Eq(motive, value));
+            head_value
+        }
+        Value::Unstuck(_, spine, lazy_value) => {
+            spine.push(Elim::Eq(motive.clone(), value.clone()));
+            *lazy_value = Arc::new(LazyValue::apply_elim(
+                lazy_value.clone(),
+                Elim::Eq(motive, value),
+            ));
+            head_value
         }
 
+        // Transporting along a reflexivity proof leaves the value unchanged.
+        Value::EqTerm => value,
+
+        _ => Arc::new(Value::Error),
+    }
+}
+
+/// Apply a code term elimination.
+///
+/// Splices are run as soon as their head computes to a quoted term, unfolding
+/// definitions if necessary, which ensures that the compile-time stages of a
+/// program are evaluated before it is read back.
+fn apply_code_elim(globals: &Globals, mut head_value: Arc<Value>) -> Arc<Value> {
+    if let Value::CodeTerm(value) = head_value.force(globals) {
+        return value.clone();
+    }
+
+    match Arc::make_mut(&mut head_value) {
+        Value::Stuck(_, spine) => {
+            spine.push(Elim::Code);
+            head_value
+        }
+        Value::Unstuck(_, spine, lazy_value) => {
+            spine.push(Elim::Code);
+            *lazy_value = Arc::new(LazyValue::apply_elim(lazy_value.clone(), Elim::Code));
+            head_value
+        }
         _ => Arc::new(Value::Error),
     }
 }
@@ -534,6 +1485,18 @@ fn read_back_stuck_value(
             let index = level.to_index(local_size).unwrap();
             Term::generated(TermData::Local(index)) // TODO: Handle overflow
         }
+        Head::Meta(index) => Term::generated(TermData::Meta(*index)),
+        Head::Fix(name, closure) => {
+            let r#type = read_back_value(globals, local_size, unfold, &closure.r#type(globals));
+            let local = Arc::new(Value::local(local_size.next_level(), []));
+            let term = closure.apply(globals, local);
+            let term = read_back_value(globals, local_size.increment(), unfold, &term);
+            Term::generated(TermData::Fix(
+                name.clone(),
+                Arc::new(r#type),
+                Arc::new(term),
+            ))
+        }
     };
 
     spine.iter().fold(head, |head, elim| match elim {
@@ -542,6 +1505,68 @@ fn read_back_stuck_value(
             Term::generated(TermData::FunctionElim(Arc::new(head), Arc::new(input)))
         }
         Elim::Record(label) => Term::generated(TermData::RecordElim(Arc::new(head), label.clone())),
+        Elim::Enum(closure) => {
+            let mut branches = Vec::with_capacity(closure.branches.len());
+            closure.for_each_branch(globals, |label, branch_value| {
+                let branch_term = read_back_value(globals, local_size, unfold, &branch_value);
+                branches.push((label.to_owned(), Arc::new(branch_term)));
+            });
+
+            Term::generated(TermData::EnumElim(Arc::new(head), branches.into()))
+        }
+        Elim::Variant(closure) => {
+            let mut branches = Vec::with_capacity(closure.branches.len());
+            let payload = Arc::new(Value::local(local_size.next_level(), []));
+            closure.for_each_branch(globals, payload, |label, name_hint, branch_value| {
+                let local_size = local_size.increment();
+                let branch_term = read_back_value(globals, local_size, unfold, &branch_value);
+                let name_hint = name_hint.map(str::to_owned);
+                branches.push((label.to_owned(), name_hint, Arc::new(branch_term)));
+            });
+
+            Term::generated(TermData::VariantElim(Arc::new(head), branches.into()))
+        }
+        Elim::Inductive(motive, closure) => {
+            let motive = read_back_value(globals, local_size, unfold, motive);
+            let mut branches = Vec::with_capacity(closure.branches.len());
+            closure.for_each_branch(
+                globals,
+                local_size,
+                |label, name_hints, local_size, branch_value| {
+                    let branch_term = read_back_value(globals, local_size, unfold, &branch_value);
+                    branches.push((label.to_owned(), name_hints.into(), Arc::new(branch_term)));
+                },
+            );
+
+            Term::generated(TermData::InductiveElim(
+                Arc::new(head),
+                Arc::new(motive),
+                branches.into(),
+            ))
+        }
+        Elim::Bool(closure) => {
+            let if_true =
+                read_back_value(globals, local_size, unfold, &closure.apply(globals, true));
+            let if_false =
+                read_back_value(globals, local_size, unfold, &closure.apply(globals, false));
+
+            Term::generated(TermData::BoolElim(
+                Arc::new(head),
+                Arc::new(if_true),
+                Arc::new(if_false),
+            ))
+        }
+        Elim::Eq(motive, value) => {
+            let motive = read_back_value(globals, local_size, unfold, motive);
+            let term = read_back_value(globals, local_size, unfold, value);
+
+            Term::generated(TermData::EqElim(
+                Arc::new(head),
+                Arc::new(motive),
+                Arc::new(term),
+            ))
+        }
+        Elim::Code => Term::generated(TermData::CodeElim(Arc::new(head))),
     })
 }
 
@@ -563,7 +1588,7 @@ pub fn read_back_value(
 
         Value::TypeType(level) => Term::generated(TermData::TypeType(*level)),
 
-        Value::FunctionType(input_name_hint, input_type, output_closure) => {
+        Value::FunctionType(plicity, usage, input_name_hint, input_type, output_closure) => {
             let local = Arc::new(Value::local(local_size.next_level(), []));
             let input_type = Arc::new(read_back_value(globals, local_size, unfold, input_type));
             let output_type = output_closure.apply(globals, local);
@@ -571,6 +1596,8 @@ pub fn read_back_value(
                 read_back_value(globals, local_size.increment(), unfold, &output_type);
 
             Term::generated(TermData::FunctionType(
+                *plicity,
+                *usage,
                 input_name_hint.clone(),
                 input_type,
                 Arc::new(output_type),
@@ -590,18 +1617,43 @@ pub fn read_back_value(
 
         Value::RecordType(closure) => {
             let mut local_size = local_size;
-            let mut type_entries = Vec::with_capacity(closure.entries.len());
+            let mut entry_types = Vec::with_capacity(closure.entries.len());
+            let mut entry_locals = Vec::with_capacity(closure.entries.len());
 
-            closure.for_each_entry(globals, |label, entry_type| {
-                let entry_type = read_back_value(globals, local_size, unfold, &entry_type);
-                type_entries.push((label.to_owned(), Arc::new(entry_type)));
+            closure.for_each_entry(globals, |_, entry_type| {
+                entry_types.push(read_back_value(globals, local_size, unfold, &entry_type));
 
                 let local_level = local_size.next_level();
+                let local = Arc::new(Value::local(local_level, []));
+                entry_locals.push((local_size, local.clone()));
                 local_size = local_size.increment();
 
-                Arc::new(Value::local(local_level, []))
+                local
             });
 
+            let type_entries = (closure.entries.iter().zip(entry_types).enumerate())
+                .map(
+                    |(index, ((label, docs, usage, _, default_term), entry_type))| {
+                        let default_term = default_term.as_ref().map(|default_term| {
+                            let (local_size, _) = entry_locals[index];
+                            let entry_locals =
+                                entry_locals[..index].iter().map(|(_, local)| local.clone());
+                            let default_value =
+                                closure.eval_with_entries(globals, entry_locals, default_term);
+                            Arc::new(read_back_value(globals, local_size, unfold, &default_value))
+                        });
+                        let entry_type = Arc::new(entry_type);
+                        (
+                            label.clone(),
+                            docs.clone(),
+                            *usage,
+                            entry_type,
+                            default_term,
+                        )
+                    },
+                )
+                .collect::<Vec<_>>();
+
             Term::generated(TermData::RecordType(type_entries.into()))
         }
         Value::RecordTerm(closure) => {
@@ -621,6 +1673,62 @@ pub fn read_back_value(
             Term::generated(TermData::RecordTerm(term_entries.into()))
         }
 
+        Value::EnumType(labels) => Term::generated(TermData::EnumType(labels.clone())),
+        Value::EnumTerm(label) => Term::generated(TermData::EnumTerm(label.clone())),
+
+        Value::VariantType(type_entries) => Term::generated(TermData::VariantType(
+            (type_entries.iter())
+                .map(|(label, r#type)| {
+                    let r#type = read_back_value(globals, local_size, unfold, r#type);
+                    (label.clone(), Arc::new(r#type))
+                })
+                .collect(),
+        )),
+        Value::VariantTerm(label, value) => Term::generated(TermData::VariantTerm(
+            label.clone(),
+            Arc::new(read_back_value(globals, local_size, unfold, value)),
+        )),
+
+        Value::InductiveType(closure) => {
+            let inductive_type = Arc::new(Value::local(local_size.next_level(), []));
+            let constructors = (closure.labels())
+                .map(|label| {
+                    let constructor_type = closure
+                        .constructor_type(globals, label, inductive_type.clone())
+                        .unwrap_or_else(|| Arc::new(Value::Error));
+                    let constructor_type =
+                        read_back_value(globals, local_size.increment(), unfold, &constructor_type);
+                    (label.to_owned(), Arc::new(constructor_type))
+                })
+                .collect();
+
+            Term::generated(TermData::InductiveType(
+                closure.name_hint.clone(),
+                constructors,
+            ))
+        }
+        Value::InductiveTerm(r#type, label, inputs) => Term::generated(TermData::InductiveTerm(
+            Arc::new(read_back_value(globals, local_size, unfold, r#type)),
+            label.clone(),
+            (inputs.iter())
+                .map(|input| Arc::new(read_back_value(globals, local_size, unfold, input)))
+                .collect(),
+        )),
+
+        Value::EqType(r#type, lhs, rhs) => Term::generated(TermData::EqType(
+            Arc::new(read_back_value(globals, local_size, unfold, r#type)),
+            Arc::new(read_back_value(globals, local_size, unfold, lhs)),
+            Arc::new(read_back_value(globals, local_size, unfold, rhs)),
+        )),
+        Value::EqTerm => Term::generated(TermData::EqTerm),
+
+        Value::CodeType(r#type) => Term::generated(TermData::CodeType(Arc::new(read_back_value(
+            globals, local_size, unfold, r#type,
+        )))),
+        Value::CodeTerm(value) => Term::generated(TermData::CodeTerm(Arc::new(read_back_value(
+            globals, local_size, unfold, value,
+        )))),
+
         Value::ArrayTerm(value_entries) => {
             let term_entries = value_entries
                 .iter()
@@ -648,6 +1756,51 @@ pub fn read_back_value(
     }
 }
 
+/// Check that the head of one stuck value is equal to the head of another
+/// stuck value.
+fn is_equal_head(globals: &Globals, local_size: LocalSize, head0: &Head, head1: &Head) -> bool {
+    match (head0, head1) {
+        (Head::Global(name0, offset0), Head::Global(name1, offset1)) => {
+            name0 == name1 && offset0 == offset1
+        }
+        (Head::Local(level0), Head::Local(level1)) => level0 == level1,
+        (Head::Meta(index0), Head::Meta(index1)) => index0 == index1,
+        (Head::Fix(_, closure0), Head::Fix(_, closure1)) => {
+            // Recursive variables are compared as if they were fresh local
+            // variables, to avoid unfolding the recursive terms forever.
+            let local = Arc::new(Value::local(local_size.next_level(), []));
+            is_equal(
+                globals,
+                local_size.increment(),
+                &closure0.apply(globals, local.clone()),
+                &closure1.apply(globals, local),
+            )
+        }
+        (_, _) => false,
+    }
+}
+
+/// Check that a function term is equal to a stuck or unstuck value, by
+/// applying both of them to a fresh local.
+///
+/// Unstuck values are applied without being forced, so that they are only
+/// unfolded if the applied values are not already equal.
+fn is_equal_function_eta(
+    globals: &Globals,
+    local_size: LocalSize,
+    output_closure: &FunctionClosure,
+    value: &Value,
+) -> bool {
+    let local = Arc::new(Value::local(local_size.next_level(), []));
+    let input = Arc::new(LazyValue::new(local.clone()));
+    is_equal(
+        globals,
+        local_size.increment(),
+        &output_closure.apply(globals, local),
+        &apply_function_elim(globals, Arc::new(value.clone()), input),
+    )
+}
+
 /// Check that one stuck value is equal to another stuck value.
 fn is_equal_stuck_value(
     globals: &Globals,
@@ -655,7 +1808,7 @@ fn is_equal_stuck_value(
     (head0, spine0): (&Head, &[Elim]),
     (head1, spine1): (&Head, &[Elim]),
 ) -> bool {
-    if head0 != head1 || spine0.len() != spine1.len() {
+    if spine0.len() != spine1.len() || !is_equal_head(globals, local_size, head0, head1) {
         return false;
     }
 
@@ -670,6 +1823,82 @@ fn is_equal_stuck_value(
                 }
             }
             (Elim::Record(label0), Elim::Record(label1)) if label0 == label1 => {}
+            (Elim::Enum(closure0), Elim::Enum(closure1)) => {
+                if !is_equal_enum_closure(globals, local_size, closure0, closure1) {
+                    return false;
+                }
+            }
+            (Elim::Variant(closure0), Elim::Variant(closure1)) => {
+                if !is_equal_variant_closure(globals, local_size, closure0, closure1) {
+                    return false;
+                }
+            }
+            (Elim::Inductive(motive0, closure0), Elim::Inductive(motive1, closure1)) => {
+                if !is_equal(globals, local_size, motive0, motive1)
+                    || !is_equal_inductive_elim_closure(globals, local_size, closure0, closure1)
+                {
+                    return false;
+                }
+            }
+            (Elim::Bool(closure0), Elim::Bool(closure1)) => {
+                let if_true0 = closure0.apply(globals, true);
+                let if_true1 = closure1.apply(globals, true);
+                let if_false0 = closure0.apply(globals, false);
+                let if_false1 = closure1.apply(globals, false);
+
+                if !is_equal(globals, local_size, &if_true0, &if_true1)
+                    || !is_equal(globals, local_size, &if_false0, &if_false1)
+                {
+                    return false;
+                }
+            }
+            (Elim::Eq(motive0, value0), Elim::Eq(motive1, value1)) => {
+                if !is_equal(globals, local_size, motive0, motive1)
+                    || !is_equal(globals, local_size, value0, value1)
+                {
+                    return false;
+                }
+            }
+            (Elim::Code, Elim::Code) => {}
+            (_, _) => return false,
+        }
+    }
+
+    true
+}
+
+/// Check that the branches of one variant closure are equal to the branches
+/// of another variant closure.
+fn is_equal_variant_closure(
+    globals: &Globals,
+    local_size: LocalSize,
+    closure0: &VariantClosure,
+    closure1: &VariantClosure,
+) -> bool {
+    if closure0.branches.len() != closure1.branches.len() {
+        return false;
+    }
+
+    let payload = Arc::new(Value::local(local_size.next_level(), []));
+
+    for ((label0, _, _), (label1, _, _)) in
+        Iterator::zip(closure0.branches.iter(), closure1.branches.iter())
+    {
+        if label0 != label1 {
+            return false;
+        }
+
+        let branch_value0 = closure0.apply(globals, label0, payload.clone());
+        let branch_value1 = closure1.apply(globals, label1, payload.clone());
+
+        match (branch_value0, branch_value1) {
+            (Some(branch_value0), Some(branch_value1))
+                if is_equal(
+                    globals,
+                    local_size.increment(),
+                    &branch_value0,
+                    &branch_value1,
+                ) => {}
             (_, _) => return false,
         }
     }
@@ -677,10 +1906,126 @@ fn is_equal_stuck_value(
     true
 }
 
+/// Check that the branches of one inductive elimination closure are equal to
+/// the branches of another inductive elimination closure.
+fn is_equal_inductive_elim_closure(
+    globals: &Globals,
+    local_size: LocalSize,
+    closure0: &InductiveElimClosure,
+    closure1: &InductiveElimClosure,
+) -> bool {
+    if closure0.branches.len() != closure1.branches.len() {
+        return false;
+    }
+
+    let mut branches0 = Vec::with_capacity(closure0.branches.len());
+    closure0.for_each_branch(
+        globals,
+        local_size,
+        |label, name_hints, local_size, value| {
+            branches0.push((label, name_hints.len(), local_size, value));
+        },
+    );
+
+    let mut is_equal_branches = true;
+    let mut branches0 = branches0.into_iter();
+    closure1.for_each_branch(globals, local_size, |label1, name_hints1, _, value1| {
+        is_equal_branches = is_equal_branches
+            && match branches0.next() {
+                Some((label0, local_count0, local_size, value0)) => {
+                    label0 == label1
+                        && local_count0 == name_hints1.len()
+                        && is_equal(globals, local_size, &value0, &value1)
+                }
+                None => false,
+            };
+    });
+
+    is_equal_branches
+}
+
+/// Check that the constructors of one inductive closure are equal to the
+/// constructors of another inductive closure.
+fn is_equal_inductive_closure(
+    globals: &Globals,
+    local_size: LocalSize,
+    closure0: &InductiveClosure,
+    closure1: &InductiveClosure,
+) -> bool {
+    if closure0.constructors.len() != closure1.constructors.len() {
+        return false;
+    }
+
+    // The inductive types are compared as if they were fresh local variables,
+    // to avoid unfolding their recursive occurrences forever.
+    let inductive_type = Arc::new(Value::local(local_size.next_level(), []));
+
+    Iterator::zip(closure0.labels(), closure1.labels()).all(|(label0, label1)| {
+        let constructor_type0 = closure0.constructor_type(globals, label0, inductive_type.clone());
+        let constructor_type1 = closure1.constructor_type(globals, label1, inductive_type.clone());
+
+        match (constructor_type0, constructor_type1) {
+            (Some(constructor_type0), Some(constructor_type1)) => {
+                label0 == label1
+                    && is_equal(
+                        globals,
+                        local_size.increment(),
+                        &constructor_type0,
+                        &constructor_type1,
+                    )
+            }
+            (_, _) => false,
+        }
+    })
+}
+
+/// Check that the branches of one enumeration closure are equal to the
+/// branches of another enumeration closure.
+fn is_equal_enum_closure(
+    globals: &Globals,
+    local_size: LocalSize,
+    closure0: &EnumClosure,
+    closure1: &EnumClosure,
+) -> bool {
+    if closure0.branches.len() != closure1.branches.len() {
+        return false;
+    }
+
+    let mut locals0 = closure0.locals.clone();
+    let mut locals1 = closure1.locals.clone();
+
+    for ((label0, branch_term0), (label1, branch_term1)) in
+        Iterator::zip(closure0.branches.iter(), closure1.branches.iter())
+    {
+        if label0 != label1 {
+            return false;
+        }
+
+        let branch_value0 = eval_term(
+            globals,
+            closure0.universe_offset,
+            &mut locals0,
+            branch_term0,
+        );
+        let branch_value1 = eval_term(
+            globals,
+            closure1.universe_offset,
+            &mut locals1,
+            branch_term1,
+        );
+
+        if !is_equal(globals, local_size, &branch_value0, &branch_value1) {
+            return false;
+        }
+    }
+
+    true
+}
+
 /// Check that one value is [computationally equal] to another value.
 ///
 /// [computationally equal]: https://ncatlab.org/nlab/show/equality#computational_equality
-fn is_equal(globals: &Globals, local_size: LocalSize, value0: &Value, value1: &Value) -> bool {
+pub fn is_equal(globals: &Globals, local_size: LocalSize, value0: &Value, value1: &Value) -> bool {
     match (value0, value1) {
         (Value::Stuck(head0, spine0), Value::Stuck(head1, spine1)) => {
             is_equal_stuck_value(globals, local_size, (head0, spine0), (head1, spine1))
@@ -695,6 +2040,15 @@ fn is_equal(globals: &Globals, local_size: LocalSize, value0: &Value, value1: &V
             let value1 = value1.force(globals);
             is_equal(globals, local_size, value0, value1)
         }
+        // Eta-conversion: a function term is equal to a stuck or unstuck value
+        // if applying both to a fresh local results in equal values.
+        (Value::FunctionTerm(_, output_closure0), Value::Stuck(_, _) | Value::Unstuck(_, _, _)) => {
+            is_equal_function_eta(globals, local_size, output_closure0, value1)
+        }
+        (Value::Stuck(_, _) | Value::Unstuck(_, _, _), Value::FunctionTerm(_, output_closure1)) => {
+            is_equal_function_eta(globals, local_size, output_closure1, value0)
+        }
+
         (Value::Unstuck(_, _, value0), value1) => {
             is_equal(globals, local_size, value0.force(globals), value1)
         }
@@ -705,10 +2059,13 @@ fn is_equal(globals: &Globals, local_size: LocalSize, value0: &Value, value1: &V
         (Value::TypeType(level0), Value::TypeType(level1)) => level0 == level1,
 
         (
-            Value::FunctionType(_, input_type0, output_closure0),
-            Value::FunctionType(_, input_type1, output_closure1),
+            Value::FunctionType(plicity0, usage0, _, input_type0, output_closure0),
+            Value::FunctionType(plicity1, usage1, _, input_type1, output_closure1),
         ) => {
-            if !is_equal(globals, local_size, input_type1, input_type0) {
+            if plicity0 != plicity1
+                || usage0 != usage1
+                || !is_equal(globals, local_size, input_type1, input_type0)
+            {
                 return false;
             }
 
@@ -741,10 +2098,10 @@ fn is_equal(globals: &Globals, local_size: LocalSize, value0: &Value, value1: &V
             let mut locals0 = closure0.locals.clone();
             let mut locals1 = closure1.locals.clone();
 
-            for ((label0, entry_type0), (label1, entry_type1)) in
+            for ((label0, _, usage0, entry_type0, _), (label1, _, usage1, entry_type1, _)) in
                 Iterator::zip(closure0.entries.iter(), closure1.entries.iter())
             {
-                if label0 != label1 {
+                if label0 != label1 || usage0 != usage1 {
                     return false;
                 }
 
@@ -797,6 +2154,51 @@ fn is_equal(globals: &Globals, local_size: LocalSize, value0: &Value, value1: &V
             true
         }
 
+        (Value::EnumType(labels0), Value::EnumType(labels1)) => {
+            labels0.len() == labels1.len() && labels0.iter().all(|label| labels1.contains(label))
+        }
+        (Value::EnumTerm(label0), Value::EnumTerm(label1)) => label0 == label1,
+
+        (Value::VariantType(type_entries0), Value::VariantType(type_entries1)) => {
+            type_entries0.len() == type_entries1.len()
+                && type_entries0.iter().all(|(label0, type0)| {
+                    (type_entries1.iter()).any(|(label1, type1)| {
+                        label0 == label1 && is_equal(globals, local_size, type0, type1)
+                    })
+                })
+        }
+        (Value::VariantTerm(label0, value0), Value::VariantTerm(label1, value1)) => {
+            label0 == label1 && is_equal(globals, local_size, value0, value1)
+        }
+
+        (Value::InductiveType(closure0), Value::InductiveType(closure1)) => {
+            is_equal_inductive_closure(globals, local_size, closure0, closure1)
+        }
+        (
+            Value::InductiveTerm(type0, label0, inputs0),
+            Value::InductiveTerm(type1, label1, inputs1),
+        ) => {
+            label0 == label1
+                && inputs0.len() == inputs1.len()
+                && is_equal(globals, local_size, type0, type1)
+                && Iterator::zip(inputs0.iter(), inputs1.iter())
+                    .all(|(input0, input1)| is_equal(globals, local_size, input0, input1))
+        }
+
+        (Value::EqType(type0, lhs0, rhs0), Value::EqType(type1, lhs1, rhs1)) => {
+            is_equal(globals, local_size, type0, type1)
+                && is_equal(globals, local_size, lhs0, lhs1)
+                && is_equal(globals, local_size, rhs0, rhs1)
+        }
+        (Value::EqTerm, Value::EqTerm) => true,
+
+        (Value::CodeType(type0), Value::CodeType(type1)) => {
+            is_equal(globals, local_size, type0, type1)
+        }
+        (Value::CodeTerm(value0), Value::CodeTerm(value1)) => {
+            is_equal(globals, local_size, value0, value1)
+        }
+
         (Value::ArrayTerm(value_entries0), Value::ArrayTerm(value_entries1))
         | (Value::ListTerm(value_entries0), Value::ListTerm(value_entries1)) => {
             if value_entries0.len() != value_entries1.len() {
@@ -865,10 +2267,13 @@ pub fn is_subtype(
         (Value::TypeType(level0), Value::TypeType(level1)) => level0 <= level1,
 
         (
-            Value::FunctionType(_, input_type0, output_closure0),
-            Value::FunctionType(_, input_type1, output_closure1),
+            Value::FunctionType(plicity0, usage0, _, input_type0, output_closure0),
+            Value::FunctionType(plicity1, usage1, _, input_type1, output_closure1),
         ) => {
-            if !is_subtype(globals, local_size, input_type1, input_type0) {
+            if plicity0 != plicity1
+                || usage0 != usage1
+                || !is_subtype(globals, local_size, input_type1, input_type0)
+            {
                 return false;
             }
 
@@ -884,38 +2289,38 @@ pub fn is_subtype(
             )
         }
 
+        // Record types are subtypes of the record types that contain a subset
+        // of their entries, with the same usages.
         (Value::RecordType(closure0), Value::RecordType(closure1)) => {
-            if closure0.entries.len() != closure1.entries.len() {
-                return false;
-            }
-
-            let mut local_size = local_size;
-            let universe_offset0 = closure0.universe_offset;
-            let universe_offset1 = closure1.universe_offset;
-            let mut locals0 = closure0.locals.clone();
-            let mut locals1 = closure1.locals.clone();
-
-            for ((label0, entry_type0), (label1, entry_type1)) in
-                Iterator::zip(closure0.entries.iter(), closure1.entries.iter())
-            {
-                if label0 != label1 {
-                    return false;
-                }
-
-                let entry_type0 = eval_term(globals, universe_offset0, &mut locals0, entry_type0);
-                let entry_type1 = eval_term(globals, universe_offset1, &mut locals1, entry_type1);
+            compare_record_subtype_entries(
+                globals,
+                local_size,
+                closure0,
+                closure1,
+                |local_size, entry_type0, entry_type1| {
+                    is_subtype(globals, local_size, entry_type0, entry_type1)
+                },
+            )
+        }
 
-                if !is_subtype(globals, local_size, &entry_type0, &entry_type1) {
-                    return false;
-                }
+        // Variant types are subtypes of the variant types that contain all of
+        // their labels, with payload types that are supertypes of their own.
+        (Value::VariantType(type_entries0), Value::VariantType(type_entries1)) => {
+            type_entries0.iter().all(|(label0, type0)| {
+                (type_entries1.iter()).any(|(label1, type1)| {
+                    label0 == label1 && is_subtype(globals, local_size, type0, type1)
+                })
+            })
+        }
 
-                let local_level = local_size.next_level();
-                locals0.push(Arc::new(Value::local(local_level, [])));
-                locals1.push(Arc::new(Value::local(local_level, [])));
-                local_size = local_size.increment();
-            }
+        (Value::CodeType(type0), Value::CodeType(type1)) => {
+            is_subtype(globals, local_size, type0, type1)
+        }
 
-            true
+        (Value::EnumType(_), Value::EnumType(_))
+        | (Value::InductiveType(_), Value::InductiveType(_))
+        | (Value::EqType(_, _, _), Value::EqType(_, _, _)) => {
+            is_equal(globals, local_size, value0, value1)
         }
 
         // Errors are always treated as subtypes, regardless of what they are compared with.
@@ -15,16 +15,73 @@ use std::sync::Arc;
 
 use crate::lang::core::semantics::{self, Elim, RecordClosure, Unfold, Value};
 use crate::lang::core::{
-    Constant, Globals, LocalLevel, Locals, Term, TermData, UniverseLevel, UniverseOffset,
+    Constant, Globals, InductiveBranch, LocalIndex, LocalLevel, Locals, Term, TermData, TypeEntry,
+    UniverseLevel, UniverseOffset, VariantBranch,
 };
+use crate::lang::Usage;
 use crate::reporting::{AmbiguousTerm, CoreTypingMessage, ExpectedType, Message};
 
+/// An invalid occurrence of an inductive type in the type of one of its
+/// constructors.
+pub enum ConstructorTypeError<'term> {
+    /// The inductive type was used in the given term, in a position other
+    /// than directly as the type of an input.
+    UnsupportedOccurrence(&'term Term),
+    /// The constructor type returned the given term, instead of the inductive
+    /// type.
+    UnexpectedOutputType(&'term Term),
+}
+
+/// Check the type of a constructor, where the inductive type is bound as the
+/// most recent local variable.
+///
+/// Constructor types must be telescopes of inputs that return the inductive
+/// type, where the inductive type is only used directly as the type of an
+/// input. This ensures that the inductive type only occurs strictly
+/// positively, which is needed for the language to remain consistent.
+///
+/// Strictly positive occurrences that are nested inside the input types, for
+/// example `(S32 -> Tree) -> Tree`, are not yet supported, because their
+/// induction hypotheses would need to be functions.
+pub fn check_constructor_type(constructor_type: &Term) -> Result<(), ConstructorTypeError<'_>> {
+    let mut depth = 0;
+    let mut current_type = constructor_type;
+
+    loop {
+        match &current_type.data {
+            TermData::FunctionType(_, _, _, input_type, output_type) => {
+                match &input_type.data {
+                    TermData::Local(index) if index.0 == depth => {}
+                    _ if input_type.has_free_local(LocalIndex(depth)) => {
+                        return Err(ConstructorTypeError::UnsupportedOccurrence(input_type));
+                    }
+                    _ => {}
+                }
+                depth += 1;
+                current_type = output_type;
+            }
+            TermData::Local(index) if index.0 == depth => return Ok(()),
+            _ => return Err(ConstructorTypeError::UnexpectedOutputType(current_type)),
+        }
+    }
+}
+
 /// The state of the type checker.
 pub struct State<'me> {
     /// Global definition environment.
     globals: &'me Globals,
     /// The current universe offset.
     universe_offset: UniverseOffset,
+    /// The current stage, where `0` is runtime, and later stages are
+    /// evaluated at compile time.
+    stage: u32,
+    /// Local stage environment (used for checking the stages of local variables).
+    local_stages: Locals<u32>,
+    /// The number of times each local variable has been used at runtime,
+    /// indexed by level.
+    local_uses: Vec<Usage>,
+    /// Whether the current term is erased at runtime.
+    erased: bool,
     /// Local type environment (used for getting the types of local variables).
     local_declarations: Locals<Arc<Value>>,
     /// Local value environment (used for evaluation).
@@ -39,6 +96,10 @@ impl<'me> State<'me> {
         State {
             globals,
             universe_offset: UniverseOffset(0),
+            stage: 0,
+            local_stages: Locals::new(),
+            local_uses: Vec::new(),
+            erased: false,
             local_declarations: Locals::new(),
             local_definitions: Locals::new(),
             message_tx,
@@ -52,6 +113,8 @@ impl<'me> State<'me> {
 
     /// Push a local entry.
     fn push_local(&mut self, value: Arc<Value>, r#type: Arc<Value>) {
+        self.local_stages.push(self.stage);
+        self.local_uses.push(Usage::Zero);
         self.local_declarations.push(r#type);
         self.local_definitions.push(value);
     }
@@ -65,12 +128,17 @@ impl<'me> State<'me> {
 
     /// Pop a local entry.
     fn pop_local(&mut self) {
+        self.local_stages.pop();
+        self.local_uses.pop();
         self.local_declarations.pop();
         self.local_definitions.pop();
     }
 
     /// Pop the given number of local entries.
     fn pop_many_locals(&mut self, count: usize) {
+        self.local_stages.pop_many(count);
+        self.local_uses
+            .truncate(self.local_uses.len().saturating_sub(count));
         self.local_declarations.pop_many(count);
         self.local_definitions.pop_many(count);
     }
@@ -80,6 +148,54 @@ impl<'me> State<'me> {
         self.message_tx.send(message.into()).unwrap();
     }
 
+    /// Run a callback in a context that is used the given number of times,
+    /// scaling the uses of the local variables that it contains.
+    fn with_usage<T>(&mut self, usage: Usage, f: impl FnOnce(&mut Self) -> T) -> T {
+        let initial_uses = vec![Usage::Zero; self.local_uses.len()];
+        let outer_uses = std::mem::replace(&mut self.local_uses, initial_uses);
+        let previous_erased = self.erased;
+        self.erased |= usage == Usage::Zero;
+
+        let output = f(self);
+
+        self.erased = previous_erased;
+        let inner_uses = std::mem::replace(&mut self.local_uses, outer_uses);
+        for (outer_use, inner_use) in Iterator::zip(self.local_uses.iter_mut(), inner_uses) {
+            *outer_use = *outer_use + usage * inner_use;
+        }
+        output
+    }
+
+    /// Run a callback for each of the alternatives of an elimination, where
+    /// only one alternative will be used at runtime, joining the uses of the
+    /// local variables from each alternative.
+    fn with_alternatives(&mut self, count: usize, mut f: impl FnMut(&mut Self, usize)) {
+        let initial_uses = self.local_uses.clone();
+        let mut joined_uses = None::<Vec<Usage>>;
+
+        for index in 0..count {
+            self.local_uses.clone_from(&initial_uses);
+            f(self, index);
+            joined_uses = Some(match joined_uses {
+                None => self.local_uses.clone(),
+                Some(joined_uses) => Iterator::zip(joined_uses.iter(), self.local_uses.iter())
+                    .map(|(use0, use1)| use0.join(*use1))
+                    .collect(),
+            });
+        }
+
+        self.local_uses = joined_uses.unwrap_or(initial_uses);
+    }
+
+    /// Check that the most recently bound local variable was used the number
+    /// of times allowed by its usage.
+    fn check_last_local_usage(&mut self, usage: Usage) {
+        let found_usage = *self.local_uses.last().unwrap();
+        if !usage.allows(found_usage) {
+            self.report(CoreTypingMessage::UnexpectedLocalUsage { usage, found_usage });
+        }
+    }
+
     /// Evaluate a [`Term`] into a [`Value`].
     ///
     /// [`Value`]: crate::lang::core::semantics::Value
@@ -98,11 +214,22 @@ impl<'me> State<'me> {
         &self,
         head_value: Arc<Value>,
         name: &str,
-        closure: &RecordClosure,
+        closure: &RecordClosure<TypeEntry>,
     ) -> Option<Arc<Value>> {
         semantics::record_elim_type(self.globals, head_value, name, closure)
     }
 
+    /// Return the type of an equality elimination.
+    pub fn eq_elim_type(&self, motive: Arc<Value>, side: Arc<Value>) -> Arc<Value> {
+        semantics::eq_elim_type(self.globals, motive, side)
+    }
+
+    /// Return the type of the motive of an equality or inductive elimination.
+    fn motive_type(&self, r#type: Arc<Value>) -> Arc<Value> {
+        let locals = self.local_definitions.clone();
+        semantics::motive_type(self.universe_offset, locals, r#type)
+    }
+
     /// Read back a value into a normal form using the current state of the elaborator.
     pub fn read_back_value(&self, value: &Value) -> Term {
         semantics::read_back_value(
@@ -122,8 +249,16 @@ impl<'me> State<'me> {
         semantics::is_subtype(self.globals, self.local_definitions.size(), value0, value1)
     }
 
+    /// Check that one [`Value`] is computationally equal to another [`Value`].
+    ///
+    /// [`Value`]: crate::lang::core::semantics::Value
+    pub fn is_equal(&self, value0: &Value, value1: &Value) -> bool {
+        semantics::is_equal(self.globals, self.local_definitions.size(), value0, value1)
+    }
+
     /// Check that a term is a type and return the universe level it inhabits.
     #[debug_ensures(self.universe_offset == old(self.universe_offset))]
+    #[debug_ensures(self.stage == old(self.stage))]
     #[debug_ensures(self.local_declarations.size() == old(self.local_declarations.size()))]
     #[debug_ensures(self.local_definitions.size() == old(self.local_definitions.size()))]
     pub fn is_type(&mut self, term: &Term) -> Option<UniverseLevel> {
@@ -143,6 +278,7 @@ impl<'me> State<'me> {
 
     /// Check that a term is an element of a type.
     #[debug_ensures(self.universe_offset == old(self.universe_offset))]
+    #[debug_ensures(self.stage == old(self.stage))]
     #[debug_ensures(self.local_declarations.size() == old(self.local_declarations.size()))]
     #[debug_ensures(self.local_definitions.size() == old(self.local_definitions.size()))]
     pub fn check_type(&mut self, term: &Term, expected_type: &Arc<Value>) {
@@ -151,27 +287,40 @@ impl<'me> State<'me> {
 
             (
                 TermData::FunctionTerm(_, output_term),
-                Value::FunctionType(_, input_type, output_closure),
+                Value::FunctionType(_, usage, _, input_type, output_closure),
             ) => {
                 let input_term = self.push_local_param(input_type.clone());
                 let output_type = output_closure.apply(self.globals, input_term);
                 self.check_type(output_term, &output_type);
+                self.check_last_local_usage(*usage);
                 self.pop_local();
             }
             (TermData::FunctionTerm(_, _), _) => {
                 self.report(CoreTypingMessage::TooManyInputsInFunctionTerm);
             }
 
+            (TermData::Let(_, def_term, output_term), _) => {
+                let def_type = self.with_usage(Usage::Many, |this| this.synth_type(def_term));
+                let def_value = self.eval_term(def_term);
+                self.push_local(def_value, def_type);
+                self.check_type(output_term, expected_type);
+                self.pop_local();
+            }
+
             (TermData::RecordTerm(term_entries), Value::RecordType(closure)) => {
                 let mut pending_term_entries = term_entries.iter();
                 let mut missing_labels = Vec::new();
                 let mut unexpected_labels = Vec::new();
                 let mut term_entry_count = 0;
+                let mut usages = closure.entries().iter().map(|(_, _, usage, _, _)| *usage);
 
                 closure.for_each_entry(self.globals, |label, entry_type| loop {
+                    let usage = usages.next().unwrap_or(Usage::Many);
                     match pending_term_entries.next() {
                         Some((next_label, entry_term)) if next_label == label => {
-                            self.check_type(&entry_term, &entry_type);
+                            self.with_usage(usage, |this| {
+                                this.check_type(&entry_term, &entry_type)
+                            });
                             let entry_value = self.eval_term(&entry_term);
 
                             self.push_local(entry_value.clone(), entry_type);
@@ -198,6 +347,72 @@ impl<'me> State<'me> {
                 }
             }
 
+            (TermData::EnumElim(head_term, branches), _) => {
+                self.check_enum_elim_head(head_term, branches);
+                self.with_alternatives(branches.len(), |this, index| {
+                    this.check_type(&branches[index].1, expected_type);
+                });
+            }
+
+            (TermData::EnumTerm(label), Value::EnumType(labels)) => {
+                if !labels.contains(label) {
+                    let expected_type = self.read_back_value(expected_type);
+                    self.report(CoreTypingMessage::InvalidEnumTerm {
+                        label: label.clone(),
+                        expected_type,
+                    });
+                }
+            }
+            (TermData::VariantTerm(label, term), Value::VariantType(type_entries)) => {
+                match type_entries.iter().find(|(l, _)| l == label) {
+                    Some((_, payload_type)) => self.check_type(term, payload_type),
+                    None => {
+                        let expected_type = self.read_back_value(expected_type);
+                        self.report(CoreTypingMessage::InvalidVariantTerm {
+                            label: label.clone(),
+                            expected_type,
+                        });
+                    }
+                }
+            }
+            (TermData::VariantElim(head_term, branches), _) => {
+                let payload_types = self.synth_variant_elim_head(head_term, branches);
+                self.with_alternatives(branches.len(), |this, index| {
+                    this.push_local_param(payload_types[index].clone());
+                    this.check_type(&branches[index].2, expected_type);
+                    this.pop_local();
+                });
+            }
+
+            (TermData::BoolElim(head_term, if_true, if_false), _) => {
+                self.check_type(head_term, &Arc::new(Value::global("Bool", 0, [])));
+                self.with_alternatives(2, |this, index| {
+                    let branch_term = if index == 0 { if_true } else { if_false };
+                    this.check_type(branch_term, expected_type);
+                });
+            }
+
+            (TermData::EqTerm, Value::EqType(_, lhs, rhs)) if self.is_equal(lhs, rhs) => {}
+            (TermData::EqTerm, _) => {
+                let expected_type = self.read_back_value(expected_type);
+                self.report(CoreTypingMessage::InvalidEqTerm { expected_type });
+            }
+
+            (TermData::CodeTerm(term), Value::CodeType(r#type)) => match self.stage {
+                0 => self.report(CoreTypingMessage::UnexpectedCodeStage),
+                stage => {
+                    let previous_stage = std::mem::replace(&mut self.stage, stage - 1);
+                    self.check_type(term, r#type);
+                    self.stage = previous_stage;
+                }
+            },
+            (TermData::CodeElim(term), _) => {
+                let code_type = Arc::new(Value::CodeType(expected_type.clone()));
+                self.stage += 1;
+                self.check_type(term, &code_type);
+                self.stage -= 1;
+            }
+
             (TermData::ArrayTerm(entry_terms), forced_type) => match forced_type.try_global() {
                 Some(("Array", _, [Elim::Function(len), Elim::Function(entry_type)])) => {
                     let forced_entry_type = entry_type.force(self.globals);
@@ -205,7 +420,7 @@ impl<'me> State<'me> {
                         self.check_type(entry_term, forced_entry_type);
                     }
 
-                    match len.force(self.globals).as_ref() {
+                    match len.force(self.globals).force(self.globals) {
                         Value::Constant(Constant::U32(len))
                             if *len as usize == entry_terms.len() => {}
                         _ => {
@@ -255,6 +470,7 @@ impl<'me> State<'me> {
 
     /// Synthesize the type of a term.
     #[debug_ensures(self.universe_offset == old(self.universe_offset))]
+    #[debug_ensures(self.stage == old(self.stage))]
     #[debug_ensures(self.local_declarations.size() == old(self.local_declarations.size()))]
     #[debug_ensures(self.local_definitions.size() == old(self.local_definitions.size()))]
     pub fn synth_type(&mut self, term: &Term) -> Arc<Value> {
@@ -269,20 +485,43 @@ impl<'me> State<'me> {
                 }
             },
             TermData::Local(index) => match self.local_declarations.get(*index) {
-                Some(r#type) => r#type.clone(),
+                Some(r#type) => {
+                    let position = self.local_uses.len() - 1 - index.0 as usize;
+                    self.local_uses[position] = self.local_uses[position] + Usage::One;
+                    let local_stage = *self.local_stages.get(*index).unwrap();
+                    if local_stage < self.stage {
+                        self.report(CoreTypingMessage::UnexpectedLocalStage {
+                            local_stage,
+                            current_stage: self.stage,
+                        });
+                    }
+                    r#type.clone()
+                }
                 None => {
                     self.report(CoreTypingMessage::UnboundLocal);
                     Arc::new(Value::Error)
                 }
             },
+            // Unsolved metavariables are only left behind by holes, which
+            // could be filled with a term of any type.
+            TermData::Meta(_) => Arc::new(Value::Error),
 
             TermData::Ann(term, r#type) => {
-                self.is_type(r#type);
+                self.with_usage(Usage::Zero, |this| this.is_type(r#type));
                 let r#type = self.eval_term(r#type);
                 self.check_type(term, &r#type);
                 r#type
             }
 
+            TermData::Let(_, def_term, output_term) => {
+                let def_type = self.with_usage(Usage::Many, |this| this.synth_type(def_term));
+                let def_value = self.eval_term(def_term);
+                self.push_local(def_value, def_type);
+                let output_type = self.synth_type(output_term);
+                self.pop_local();
+                output_type
+            }
+
             TermData::TypeType(level) => match *level + UniverseOffset(1) {
                 Some(level) => Arc::new(Value::type_type(level)),
                 None => {
@@ -303,7 +542,7 @@ impl<'me> State<'me> {
                 }
             },
 
-            TermData::FunctionType(_, input_type, output_type) => {
+            TermData::FunctionType(_, _, _, input_type, output_type) => {
                 let input_level = self.is_type(input_type);
                 let input_type = match input_level {
                     None => Arc::new(Value::Error),
@@ -330,8 +569,8 @@ impl<'me> State<'me> {
             TermData::FunctionElim(head_term, input_term) => {
                 let head_type = self.synth_type(head_term);
                 match head_type.force(self.globals) {
-                    Value::FunctionType(_, input_type, output_closure) => {
-                        self.check_type(input_term, &input_type);
+                    Value::FunctionType(_, usage, _, input_type, output_closure) => {
+                        self.with_usage(*usage, |this| this.check_type(input_term, &input_type));
                         let input_value = self.eval_term(input_term);
                         output_closure.apply(self.globals, input_value)
                     }
@@ -344,6 +583,15 @@ impl<'me> State<'me> {
                 }
             }
 
+            TermData::Fix(_, r#type, term) => {
+                self.with_usage(Usage::Zero, |this| this.is_type(r#type));
+                let r#type = self.eval_term(r#type);
+                self.push_local_param(r#type.clone());
+                self.with_usage(Usage::Many, |this| this.check_type(term, &r#type));
+                self.pop_local();
+                r#type
+            }
+
             TermData::RecordTerm(term_entries) => {
                 if term_entries.is_empty() {
                     Arc::from(Value::RecordType(RecordClosure::new(
@@ -361,11 +609,11 @@ impl<'me> State<'me> {
             TermData::RecordType(type_entries) => {
                 use std::collections::BTreeSet;
 
-                let mut max_level = UniverseLevel(0);
+                let mut max_level = UniverseLevel::Finite(0);
                 let mut duplicate_labels = Vec::new();
                 let mut seen_labels = BTreeSet::new();
 
-                for (name, r#type) in type_entries.iter() {
+                for (name, _, _, r#type, default_term) in type_entries.iter() {
                     if !seen_labels.insert(name) {
                         duplicate_labels.push(name.clone());
                     }
@@ -377,6 +625,9 @@ impl<'me> State<'me> {
                         }
                     };
                     let r#type = self.eval_term(r#type);
+                    if let Some(default_term) = default_term {
+                        self.check_type(default_term, &r#type);
+                    }
                     self.push_local_param(r#type);
                 }
 
@@ -395,6 +646,15 @@ impl<'me> State<'me> {
                     Value::RecordType(closure) => {
                         let head_value = self.eval_term(head_term);
 
+                        let usage = (closure.entries().iter())
+                            .find(|(entry_label, _, _, _, _)| entry_label == label)
+                            .map(|(_, _, usage, _, _)| *usage);
+                        if usage == Some(Usage::Zero) && !self.erased {
+                            self.report(CoreTypingMessage::UnexpectedErasedLabel {
+                                label: label.clone(),
+                            });
+                        }
+
                         if let Some(entry_type) = self.record_elim_type(head_value, label, closure)
                         {
                             return entry_type;
@@ -412,6 +672,320 @@ impl<'me> State<'me> {
                 Arc::new(Value::Error)
             }
 
+            TermData::EnumType(labels) => {
+                use std::collections::BTreeSet;
+
+                let mut duplicate_labels = Vec::new();
+                let mut seen_labels = BTreeSet::new();
+
+                for label in labels.iter() {
+                    if !seen_labels.insert(label) {
+                        duplicate_labels.push(label.clone());
+                    }
+                }
+
+                if !duplicate_labels.is_empty() {
+                    self.report(CoreTypingMessage::InvalidEnumType { duplicate_labels });
+                }
+
+                Arc::new(Value::TypeType(UniverseLevel::Finite(0)))
+            }
+            TermData::EnumTerm(label) => Arc::new(Value::EnumType(Arc::new([label.clone()]))),
+            TermData::EnumElim(head_term, branches) => {
+                self.check_enum_elim_head(head_term, branches);
+                match branches.split_first() {
+                    Some(((_, branch_term), _)) => {
+                        let mut r#type = Arc::new(Value::Error);
+                        self.with_alternatives(branches.len(), |this, index| match index {
+                            0 => r#type = this.synth_type(branch_term),
+                            _ => this.check_type(&branches[index].1, &r#type),
+                        });
+                        r#type
+                    }
+                    None => {
+                        self.report(CoreTypingMessage::AmbiguousTerm {
+                            term: AmbiguousTerm::EnumElim,
+                        });
+                        Arc::new(Value::Error)
+                    }
+                }
+            }
+
+            TermData::VariantType(type_entries) => {
+                use std::collections::BTreeSet;
+
+                let mut max_level = UniverseLevel::Finite(0);
+                let mut duplicate_labels = Vec::new();
+                let mut seen_labels = BTreeSet::new();
+
+                for (label, r#type) in type_entries.iter() {
+                    if !seen_labels.insert(label) {
+                        duplicate_labels.push(label.clone());
+                    }
+                    max_level = match self.is_type(r#type) {
+                        Some(level) => std::cmp::max(max_level, level),
+                        None => return Arc::new(Value::Error),
+                    };
+                }
+
+                if !duplicate_labels.is_empty() {
+                    self.report(CoreTypingMessage::InvalidVariantType { duplicate_labels });
+                }
+
+                Arc::new(Value::TypeType(max_level))
+            }
+            TermData::VariantTerm(_, _) => {
+                self.report(CoreTypingMessage::AmbiguousTerm {
+                    term: AmbiguousTerm::VariantTerm,
+                });
+                Arc::new(Value::Error)
+            }
+            TermData::VariantElim(head_term, branches) => {
+                let payload_types = self.synth_variant_elim_head(head_term, branches);
+                match branches.is_empty() {
+                    false => {
+                        let mut r#type = Arc::new(Value::Error);
+                        self.with_alternatives(branches.len(), |this, index| {
+                            this.push_local_param(payload_types[index].clone());
+                            match index {
+                                0 => r#type = this.synth_type(&branches[index].2),
+                                _ => this.check_type(&branches[index].2, &r#type),
+                            }
+                            this.pop_local();
+                        });
+                        r#type
+                    }
+                    true => {
+                        self.report(CoreTypingMessage::AmbiguousTerm {
+                            term: AmbiguousTerm::VariantElim,
+                        });
+                        Arc::new(Value::Error)
+                    }
+                }
+            }
+
+            TermData::InductiveType(_, constructors) => {
+                use std::collections::BTreeSet;
+
+                let mut max_level = UniverseLevel::Finite(0);
+                let mut duplicate_labels = Vec::new();
+                let mut seen_labels = BTreeSet::new();
+
+                // Recursive occurrences of the inductive type do not raise
+                // its universe level, so it is bound as a small type.
+                self.push_local_param(Arc::new(Value::type_type(0)));
+                for (label, r#type) in constructors.iter() {
+                    if !seen_labels.insert(label) {
+                        duplicate_labels.push(label.clone());
+                    }
+                    max_level = match self.is_type(r#type) {
+                        Some(level) => std::cmp::max(max_level, level),
+                        None => {
+                            self.pop_local();
+                            return Arc::new(Value::Error);
+                        }
+                    };
+                    if check_constructor_type(r#type).is_err() {
+                        self.report(CoreTypingMessage::InvalidConstructorType {
+                            label: label.clone(),
+                        });
+                    }
+                }
+                self.pop_local();
+
+                if !duplicate_labels.is_empty() {
+                    self.report(CoreTypingMessage::InvalidInductiveType { duplicate_labels });
+                }
+
+                Arc::new(Value::TypeType(max_level))
+            }
+            TermData::InductiveTerm(r#type, label, inputs) => {
+                let type_value = match self.with_usage(Usage::Zero, |this| this.is_type(r#type)) {
+                    Some(_) => self.eval_term(r#type),
+                    None => return Arc::new(Value::Error),
+                };
+                let constructor_type = match type_value.force(self.globals) {
+                    Value::InductiveType(closure) => {
+                        let recursive_inputs = closure.recursive_inputs(label);
+                        let constructor_type =
+                            closure.constructor_type(self.globals, label, type_value.clone());
+                        recursive_inputs.zip(constructor_type)
+                    }
+                    Value::Error => return Arc::new(Value::Error),
+                    _ => None,
+                };
+
+                let (recursive_inputs, mut constructor_type) = match constructor_type {
+                    Some(constructor_type) => constructor_type,
+                    None => {
+                        let head_type = self.read_back_value(&type_value);
+                        self.report(CoreTypingMessage::LabelNotFound {
+                            expected_label: label.clone(),
+                            head_type,
+                        });
+                        return Arc::new(Value::Error);
+                    }
+                };
+
+                if recursive_inputs.len() != inputs.len() {
+                    self.report(CoreTypingMessage::InvalidInductiveTerm {
+                        label: label.clone(),
+                        expected_count: recursive_inputs.len(),
+                        found_count: inputs.len(),
+                    });
+                    return Arc::new(Value::Error);
+                }
+
+                for input in inputs.iter() {
+                    let (usage, input_type, output_closure) =
+                        match constructor_type.force(self.globals) {
+                            Value::FunctionType(_, usage, _, input_type, output_closure) => {
+                                (*usage, input_type.clone(), output_closure.clone())
+                            }
+                            _ => break,
+                        };
+                    self.with_usage(usage, |this| this.check_type(input, &input_type));
+                    let input_value = self.eval_term(input);
+                    constructor_type = output_closure.apply(self.globals, input_value);
+                }
+
+                type_value
+            }
+            TermData::InductiveElim(head_term, motive, branches) => {
+                let head_type = self.synth_type(head_term);
+                let closure = match head_type.force(self.globals) {
+                    Value::InductiveType(closure) => closure.clone(),
+                    Value::Error => return Arc::new(Value::Error),
+                    _ => {
+                        let head_type = self.read_back_value(&head_type);
+                        self.report(CoreTypingMessage::UnexpectedInductiveElimHead { head_type });
+                        return Arc::new(Value::Error);
+                    }
+                };
+
+                let motive_type = self.motive_type(head_type.clone());
+                self.with_usage(Usage::Zero, |this| this.check_type(motive, &motive_type));
+                let motive = self.eval_term(motive);
+
+                let mut unexpected_labels = Vec::new();
+                for (index, (label, _, _)) in branches.iter().enumerate() {
+                    if !closure.labels().any(|l| l == label)
+                        || branches[..index].iter().any(|(l, _, _)| l == label)
+                    {
+                        unexpected_labels.push(label.clone());
+                    }
+                }
+                let missing_labels: Vec<_> = (closure.labels())
+                    .filter(|label| !branches.iter().any(|(l, _, _)| l == label))
+                    .map(str::to_owned)
+                    .collect();
+
+                if !missing_labels.is_empty() || !unexpected_labels.is_empty() {
+                    self.report(CoreTypingMessage::InvalidInductiveElim {
+                        missing_labels,
+                        unexpected_labels,
+                    });
+                }
+
+                self.with_alternatives(branches.len(), |this, index| {
+                    this.check_inductive_branch(&head_type, &closure, &motive, &branches[index]);
+                });
+
+                let head_value = self.eval_term(head_term);
+                semantics::inductive_elim_type(self.globals, motive, head_value)
+            }
+
+            TermData::BoolElim(head_term, if_true, if_false) => {
+                self.check_type(head_term, &Arc::new(Value::global("Bool", 0, [])));
+                let mut r#type = Arc::new(Value::Error);
+                self.with_alternatives(2, |this, index| match index {
+                    0 => r#type = this.synth_type(if_true),
+                    _ => this.check_type(if_false, &r#type),
+                });
+                r#type
+            }
+
+            TermData::EqType(r#type, lhs, rhs) => {
+                let level = self.is_type(r#type);
+                let r#type = match level {
+                    None => Arc::new(Value::Error),
+                    Some(_) => self.eval_term(r#type),
+                };
+                self.check_type(lhs, &r#type);
+                self.check_type(rhs, &r#type);
+
+                match level {
+                    Some(level) => Arc::new(Value::TypeType(level)),
+                    None => Arc::new(Value::Error),
+                }
+            }
+            TermData::EqTerm => {
+                self.report(CoreTypingMessage::AmbiguousTerm {
+                    term: AmbiguousTerm::EqTerm,
+                });
+                Arc::new(Value::Error)
+            }
+            TermData::EqElim(head_term, motive, term) => {
+                let head_type = self.synth_type(head_term);
+                match head_type.force(self.globals) {
+                    Value::EqType(r#type, lhs, rhs) => {
+                        let motive_type = self.motive_type(r#type.clone());
+                        self.with_usage(Usage::Zero, |this| this.check_type(motive, &motive_type));
+                        let motive = self.eval_term(motive);
+                        self.check_type(term, &self.eq_elim_type(motive.clone(), lhs.clone()));
+                        self.eq_elim_type(motive, rhs.clone())
+                    }
+                    Value::Error => Arc::new(Value::Error),
+                    _ => {
+                        let head_type = self.read_back_value(&head_type);
+                        self.report(CoreTypingMessage::UnexpectedEqElimHead { head_type });
+                        Arc::new(Value::Error)
+                    }
+                }
+            }
+
+            TermData::CodeType(r#type) => match self.stage {
+                0 => {
+                    self.report(CoreTypingMessage::UnexpectedCodeStage);
+                    Arc::new(Value::Error)
+                }
+                stage => {
+                    let previous_stage = std::mem::replace(&mut self.stage, stage - 1);
+                    let level = self.is_type(r#type);
+                    self.stage = previous_stage;
+                    match level {
+                        Some(level) => Arc::new(Value::TypeType(level)),
+                        None => Arc::new(Value::Error),
+                    }
+                }
+            },
+            TermData::CodeTerm(term) => match self.stage {
+                0 => {
+                    self.report(CoreTypingMessage::UnexpectedCodeStage);
+                    Arc::new(Value::Error)
+                }
+                stage => {
+                    let previous_stage = std::mem::replace(&mut self.stage, stage - 1);
+                    let r#type = self.synth_type(term);
+                    self.stage = previous_stage;
+                    Arc::new(Value::CodeType(r#type))
+                }
+            },
+            TermData::CodeElim(head_term) => {
+                self.stage += 1;
+                let head_type = self.synth_type(head_term);
+                self.stage -= 1;
+                match head_type.force(self.globals) {
+                    Value::CodeType(r#type) => r#type.clone(),
+                    Value::Error => Arc::new(Value::Error),
+                    _ => {
+                        let head_type = self.read_back_value(&head_type);
+                        self.report(CoreTypingMessage::UnexpectedCodeElimHead { head_type });
+                        Arc::new(Value::Error)
+                    }
+                }
+            }
+
             TermData::ArrayTerm(_) => {
                 self.report(CoreTypingMessage::AmbiguousTerm {
                     term: AmbiguousTerm::Sequence,
@@ -443,4 +1017,145 @@ impl<'me> State<'me> {
             TermData::Error => Arc::new(Value::Error),
         }
     }
+
+    /// Check that the head of an enumeration elimination is an enumeration,
+    /// and that there is exactly one branch for each of its labels.
+    fn check_enum_elim_head(&mut self, head_term: &Term, branches: &[(String, Arc<Term>)]) {
+        let head_type = self.synth_type(head_term);
+        match head_type.force(self.globals) {
+            Value::EnumType(labels) => {
+                let mut unexpected_labels = Vec::new();
+                for (index, (label, _)) in branches.iter().enumerate() {
+                    if !labels.contains(label) || branches[..index].iter().any(|(l, _)| l == label)
+                    {
+                        unexpected_labels.push(label.clone());
+                    }
+                }
+                let missing_labels: Vec<_> = (labels.iter())
+                    .filter(|label| !branches.iter().any(|(l, _)| l == *label))
+                    .cloned()
+                    .collect();
+
+                if !missing_labels.is_empty() || !unexpected_labels.is_empty() {
+                    self.report(CoreTypingMessage::InvalidEnumElim {
+                        missing_labels,
+                        unexpected_labels,
+                    });
+                }
+            }
+            Value::Error => {}
+            _ => {
+                let head_type = self.read_back_value(&head_type);
+                self.report(CoreTypingMessage::UnexpectedEnumElimHead { head_type });
+            }
+        }
+    }
+
+    /// Check a branch of an inductive elimination, binding the inputs of the
+    /// matching constructor, followed by the results of eliminating each of
+    /// its recursive inputs.
+    fn check_inductive_branch(
+        &mut self,
+        inductive_type: &Arc<Value>,
+        closure: &semantics::InductiveClosure,
+        motive: &Arc<Value>,
+        (label, name_hints, branch_term): &InductiveBranch,
+    ) {
+        let recursive_inputs = closure.recursive_inputs(label);
+        let constructor_type =
+            closure.constructor_type(self.globals, label, inductive_type.clone());
+        let (recursive_inputs, mut constructor_type) = match recursive_inputs.zip(constructor_type)
+        {
+            Some(constructor_type) => constructor_type,
+            // Unexpected labels have already been reported.
+            None => return,
+        };
+
+        let recursive_count = recursive_inputs.iter().filter(|r| **r).count();
+        let local_count = recursive_inputs.len() + recursive_count;
+        if name_hints.len() != local_count {
+            self.report(CoreTypingMessage::InvalidInductiveBranch {
+                label: label.clone(),
+                expected_count: local_count,
+                found_count: name_hints.len(),
+            });
+            return;
+        }
+
+        let mut inputs = Vec::with_capacity(recursive_inputs.len());
+        for _ in recursive_inputs.iter() {
+            let (input_type, output_closure) = match constructor_type.force(self.globals) {
+                Value::FunctionType(_, _, _, input_type, output_closure) => {
+                    (input_type.clone(), output_closure.clone())
+                }
+                _ => {
+                    self.pop_many_locals(inputs.len());
+                    return;
+                }
+            };
+            let input = self.push_local_param(input_type);
+            constructor_type = output_closure.apply(self.globals, input.clone());
+            inputs.push(input);
+        }
+        for (input, _) in Iterator::zip(inputs.iter(), recursive_inputs).filter(|(_, r)| *r) {
+            let output_type =
+                semantics::inductive_elim_type(self.globals, motive.clone(), input.clone());
+            self.push_local_param(output_type);
+        }
+
+        let term = Value::InductiveTerm(inductive_type.clone(), label.clone(), inputs);
+        let branch_type =
+            semantics::inductive_elim_type(self.globals, motive.clone(), Arc::new(term));
+        self.check_type(branch_term, &branch_type);
+        self.pop_many_locals(local_count);
+    }
+
+    /// Check that the head of a variant elimination is a variant, and that
+    /// there is exactly one branch for each of its labels, returning the
+    /// payload types that should be bound in each of the branches.
+    fn synth_variant_elim_head(
+        &mut self,
+        head_term: &Term,
+        branches: &[VariantBranch],
+    ) -> Vec<Arc<Value>> {
+        let head_type = self.synth_type(head_term);
+        match head_type.force(self.globals) {
+            Value::VariantType(type_entries) => {
+                let mut payload_types = Vec::with_capacity(branches.len());
+                let mut unexpected_labels = Vec::new();
+                for (index, (label, _, _)) in branches.iter().enumerate() {
+                    match type_entries.iter().find(|(l, _)| l == label) {
+                        Some((_, payload_type))
+                            if !branches[..index].iter().any(|(l, _, _)| l == label) =>
+                        {
+                            payload_types.push(payload_type.clone());
+                        }
+                        Some(_) | None => {
+                            unexpected_labels.push(label.clone());
+                            payload_types.push(Arc::new(Value::Error));
+                        }
+                    }
+                }
+                let missing_labels: Vec<_> = (type_entries.iter())
+                    .filter(|(label, _)| !branches.iter().any(|(l, _, _)| l == label))
+                    .map(|(label, _)| label.clone())
+                    .collect();
+
+                if !missing_labels.is_empty() || !unexpected_labels.is_empty() {
+                    self.report(CoreTypingMessage::InvalidVariantElim {
+                        missing_labels,
+                        unexpected_labels,
+                    });
+                }
+
+                payload_types
+            }
+            Value::Error => branches.iter().map(|_| Arc::new(Value::Error)).collect(),
+            _ => {
+                let head_type = self.read_back_value(&head_type);
+                self.report(CoreTypingMessage::UnexpectedVariantElimHead { head_type });
+                branches.iter().map(|_| Arc::new(Value::Error)).collect()
+            }
+        }
+    }
 }
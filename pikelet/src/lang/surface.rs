@@ -4,7 +4,7 @@
 
 use crossbeam_channel::Sender;
 
-use crate::lang::{FileId, Located, Location};
+use crate::lang::{FileId, Located, Location, Plicity, Usage};
 use crate::reporting::Message;
 
 mod lexer;
@@ -14,12 +14,35 @@ mod grammar {
     include!(concat!(env!("OUT_DIR"), "/lang/surface/grammar.rs"));
 }
 
-/// Entry in a [record type](Term::RecordType).
-pub type TypeEntry = (Located<String>, Option<Located<String>>, Term);
+/// Doc comments, with the text following the `|||` on each line.
+pub type Docs = Vec<String>;
+/// Entry in a [record type](Term::RecordType), with an optional default term.
+pub type TypeEntry = (
+    Docs,
+    Usage,
+    Located<String>,
+    Option<Located<String>>,
+    Term,
+    Option<Term>,
+);
 /// Entry in a [record term](Term::RecordTerm).
 pub type TermEntry = (Located<String>, Option<Located<String>>, Term);
+/// Entry in a [variant type](Term::VariantType).
+pub type VariantEntry = (Located<String>, Term);
+/// Entry in an [inductive type](Term::InductiveType).
+pub type ConstructorEntry = (Located<String>, Term);
+/// Branch in a [match expression](Term::EnumElim) or an
+/// [eliminator](Term::InductiveElim), with names for the payload of a variant,
+/// or for the inputs of a constructor.
+pub type Branch = (Located<String>, Vec<Located<String>>, Term);
 /// A group of function inputs that are elements of the same type.
-pub type InputGroup = (Vec<Located<String>>, Term);
+pub type InputGroup = (Plicity, Usage, Vec<Located<String>>, Term);
+/// A group of [function term](Term::FunctionTerm) inputs, with an optional
+/// type annotation.
+pub type InputNameGroup = (Plicity, Vec<Located<String>>, Option<Term>);
+/// An input to a [function elimination](Term::FunctionElim), along with the
+/// name of the implicit input that it is given for, if any.
+pub type InputTerm = (Option<Located<String>>, Term);
 
 pub type Term = Located<TermData>;
 
@@ -28,13 +51,33 @@ pub type Term = Located<TermData>;
 pub enum TermData {
     /// Names.
     Name(String),
+    /// Holes, to be filled in by the programmer.
+    ///
+    /// The expected type of a hole is reported during elaboration, along with
+    /// the local variables that are in scope.
+    Hole(String),
 
     /// Annotated terms.
     Ann(Box<Term>, Box<Term>),
 
+    /// The universe above all finite universe levels.
+    TypeOmega,
+
     /// Lift a term by the given number of universe levels.
     Lift(Box<Term>, u32),
 
+    /// Import expressions.
+    ///
+    /// Elaborates to the contents of the file at the quoted path, relative to
+    /// the file containing the import.
+    Import(String),
+
+    /// Let expressions.
+    ///
+    /// Binds the result of a definition, with an optional type annotation,
+    /// for use in the output term.
+    Let(Located<String>, Option<Box<Term>>, Box<Term>, Box<Term>),
+
     /// Function types.
     ///
     /// Also known as: pi type, dependent product type.
@@ -45,22 +88,100 @@ pub enum TermData {
     FunctionArrowType(Box<Term>, Box<Term>),
     /// Function terms.
     ///
+    /// The type of a function term can be synthesized if all of its inputs
+    /// are annotated with types.
+    ///
     /// Also known as: lambda abstraction, anonymous function.
-    FunctionTerm(Vec<Located<String>>, Box<Term>),
+    FunctionTerm(Vec<InputNameGroup>, Box<Term>),
     /// Function eliminations.
     ///
     /// Also known as: function application.
-    FunctionElim(Box<Term>, Vec<Term>),
+    FunctionElim(Box<Term>, Vec<InputTerm>),
+
+    /// Recursive terms.
+    ///
+    /// Binds the term itself under the given name, for use in its own body.
+    ///
+    /// Also known as: fixed-point terms.
+    Fix(Located<String>, Box<Term>),
 
     /// Record types.
     RecordType(Vec<TypeEntry>),
     /// Record terms.
     RecordTerm(Vec<TermEntry>),
+    /// Record updates.
+    ///
+    /// Replaces the entries of the head record with the given entries, adding
+    /// any entries that were not already present in the head record.
+    RecordUpdate(Box<Term>, Vec<TermEntry>),
     /// Record eliminations.
     ///
     /// Also known as: record projections, field lookup.
     RecordElim(Box<Term>, Located<String>),
 
+    /// Enumeration types.
+    ///
+    /// Also known as: enumeration sets, finite sets.
+    EnumType(Vec<Located<String>>),
+    /// Enumeration terms.
+    EnumTerm(Located<String>),
+    /// Enumeration eliminations.
+    ///
+    /// Also known as: case analysis, match expressions.
+    EnumElim(Box<Term>, Vec<Branch>),
+
+    /// Variant types.
+    ///
+    /// Also known as: sum types, tagged unions.
+    VariantType(Vec<VariantEntry>),
+    /// Variant terms.
+    ///
+    /// Also known as: injections.
+    VariantTerm(Located<String>, Box<Term>),
+
+    /// Inductive types, consisting of a name for the type being defined, and
+    /// a list of constructors.
+    ///
+    /// Also known as: algebraic data types, W-types.
+    InductiveType(Located<String>, Vec<ConstructorEntry>),
+    /// Inductive eliminations, consisting of the term being eliminated, a
+    /// motive, and a branch for each constructor.
+    ///
+    /// Also known as: induction principles, recursors, folds.
+    InductiveElim(Box<Term>, Box<Term>, Vec<Branch>),
+
+    /// Boolean eliminations.
+    ///
+    /// Also known as: if-then-else expressions.
+    BoolElim(Box<Term>, Box<Term>, Box<Term>),
+
+    /// Equality types.
+    ///
+    /// Also known as: identity types, propositional equality.
+    EqType(Box<Term>, Box<Term>, Box<Term>),
+    /// Equality terms.
+    ///
+    /// Also known as: reflexivity proofs.
+    EqTerm,
+    /// Equality eliminations, consisting of an equality proof, a motive, and
+    /// the term to be transported.
+    ///
+    /// Also known as: transport, substitution.
+    EqElim(Box<Term>, Box<Term>, Box<Term>),
+
+    /// Code types.
+    ///
+    /// Also known as: lifted types.
+    CodeType(Box<Term>),
+    /// Code terms.
+    ///
+    /// Also known as: quotations.
+    CodeTerm(Box<Term>),
+    /// Code eliminations.
+    ///
+    /// Also known as: splices, antiquotations.
+    CodeElim(Box<Term>),
+
     /// Ordered sequences.
     SequenceTerm(Vec<Term>),
     /// Character literals.
@@ -91,4 +212,142 @@ impl<'input> Term {
                 )
             })
     }
+
+    /// Returns `true` if the given name occurs free in the term.
+    pub fn has_free_name(&self, name: &str) -> bool {
+        fn type_entries_have_free_name(name: &str, entries: &[TypeEntry]) -> bool {
+            for (_, _, label, entry_name, r#type, default_term) in entries {
+                if r#type.has_free_name(name)
+                    || (default_term.iter()).any(|default_term| default_term.has_free_name(name))
+                {
+                    return true;
+                }
+                if entry_name.as_ref().unwrap_or(label).data == name {
+                    return false;
+                }
+            }
+            false
+        }
+
+        fn entries_have_free_name(name: &str, entries: &[TermEntry]) -> bool {
+            for (label, entry_name, term) in entries {
+                if term.has_free_name(name) {
+                    return true;
+                }
+                if entry_name.as_ref().unwrap_or(label).data == name {
+                    return false;
+                }
+            }
+            false
+        }
+
+        fn branches_have_free_name(name: &str, branches: &[Branch]) -> bool {
+            (branches.iter()).any(|(_, branch_names, branch_term)| {
+                branch_names.iter().all(|n| n.data != name) && branch_term.has_free_name(name)
+            })
+        }
+
+        match &self.data {
+            TermData::Name(term_name) => term_name == name,
+            TermData::Hole(_)
+            | TermData::TypeOmega
+            | TermData::Import(_)
+            | TermData::EnumType(_)
+            | TermData::EnumTerm(_)
+            | TermData::EqTerm
+            | TermData::CharTerm(_)
+            | TermData::StringTerm(_)
+            | TermData::NumberTerm(_)
+            | TermData::Error => false,
+
+            TermData::Ann(term, r#type) => term.has_free_name(name) || r#type.has_free_name(name),
+            TermData::Lift(term, _) => term.has_free_name(name),
+            TermData::Let(def_name, def_type, def_term, output_term) => {
+                def_type.iter().any(|def_type| def_type.has_free_name(name))
+                    || def_term.has_free_name(name)
+                    || (def_name.data != name && output_term.has_free_name(name))
+            }
+
+            TermData::FunctionType(input_type_groups, output_type) => {
+                for (_, _, input_names, input_type) in input_type_groups {
+                    if input_type.has_free_name(name) {
+                        return true;
+                    }
+                    if input_names.iter().any(|input_name| input_name.data == name) {
+                        return false;
+                    }
+                }
+                output_type.has_free_name(name)
+            }
+            TermData::FunctionArrowType(input_type, output_type) => {
+                input_type.has_free_name(name) || output_type.has_free_name(name)
+            }
+            TermData::FunctionTerm(input_name_groups, output_term) => {
+                for (_, input_names, input_type) in input_name_groups {
+                    for input_name in input_names {
+                        if input_type.iter().any(|t| t.has_free_name(name)) {
+                            return true;
+                        }
+                        if input_name.data == name {
+                            return false;
+                        }
+                    }
+                }
+                output_term.has_free_name(name)
+            }
+            TermData::FunctionElim(head_term, input_terms) => {
+                head_term.has_free_name(name)
+                    || input_terms
+                        .iter()
+                        .any(|(_, input_term)| input_term.has_free_name(name))
+            }
+            TermData::Fix(fix_name, term) => fix_name.data != name && term.has_free_name(name),
+
+            TermData::RecordType(type_entries) => type_entries_have_free_name(name, type_entries),
+            TermData::RecordTerm(term_entries) => entries_have_free_name(name, term_entries),
+            TermData::RecordUpdate(head_term, term_entries) => {
+                head_term.has_free_name(name) || entries_have_free_name(name, term_entries)
+            }
+            TermData::RecordElim(head_term, _) => head_term.has_free_name(name),
+
+            TermData::EnumElim(head_term, branches) => {
+                head_term.has_free_name(name) || branches_have_free_name(name, branches)
+            }
+            TermData::VariantType(type_entries) => {
+                (type_entries.iter()).any(|(_, entry_type)| entry_type.has_free_name(name))
+            }
+            TermData::VariantTerm(_, term) => term.has_free_name(name),
+            TermData::InductiveType(type_name, constructors) => {
+                type_name.data != name
+                    && (constructors.iter()).any(|(_, r#type)| r#type.has_free_name(name))
+            }
+            TermData::InductiveElim(head_term, motive, branches) => {
+                head_term.has_free_name(name)
+                    || motive.has_free_name(name)
+                    || branches_have_free_name(name, branches)
+            }
+            TermData::BoolElim(head_term, if_true, if_false) => {
+                head_term.has_free_name(name)
+                    || if_true.has_free_name(name)
+                    || if_false.has_free_name(name)
+            }
+
+            TermData::EqType(r#type, lhs, rhs) => {
+                r#type.has_free_name(name) || lhs.has_free_name(name) || rhs.has_free_name(name)
+            }
+            TermData::EqElim(head_term, motive, term) => {
+                head_term.has_free_name(name)
+                    || motive.has_free_name(name)
+                    || term.has_free_name(name)
+            }
+
+            TermData::CodeType(term) | TermData::CodeTerm(term) | TermData::CodeElim(term) => {
+                term.has_free_name(name)
+            }
+
+            TermData::SequenceTerm(entry_terms) => entry_terms
+                .iter()
+                .any(|entry_term| entry_term.has_free_name(name)),
+        }
+    }
 }
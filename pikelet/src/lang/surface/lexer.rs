@@ -17,19 +17,67 @@ pub enum Token<'a> {
     NumericLiteral(&'a str),
     #[regex(r"[a-zA-Z][a-zA-Z0-9\-]*")]
     Name(&'a str),
+    #[regex(r"\?[a-zA-Z][a-zA-Z0-9\-]*")]
+    Hole(&'a str),
     #[regex(r"\^[0-9]+(\.[0-9]+)?")]
     Shift(&'a str),
 
     #[token("as")]
     As,
+    #[token("enum")]
+    EnumTerm,
+    #[token("Enum")]
+    EnumType,
+    #[token("elim")]
+    Elim,
+    #[token("else")]
+    Else,
+    #[token("Eq")]
+    EqType,
+    #[token("fix")]
+    Fix,
     #[token("fun")]
     FunTerm,
+    #[token("if")]
+    If,
+    #[token("import")]
+    Import,
+    #[token("in")]
+    In,
+    #[token("let")]
+    Let,
+    #[token("match")]
+    Match,
+    #[token("quote")]
+    Quote,
+    #[token("refl")]
+    Refl,
+    #[token("splice")]
+    Splice,
+    #[token("then")]
+    Then,
+    #[token("transport")]
+    Transport,
+    #[token("with")]
+    With,
+    #[token("Code")]
+    CodeType,
     #[token("Fun")]
     FunType,
     #[token("record")]
     RecordTerm,
     #[token("Record")]
     RecordType,
+    #[token("Inductive")]
+    InductiveType,
+    #[token("variant")]
+    VariantTerm,
+    #[token("Variant")]
+    VariantType,
+    #[token("Typeω")]
+    TypeOmega,
+    #[token("ω")]
+    Omega,
 
     #[token(":")]
     Colon,
@@ -71,13 +119,37 @@ impl<'a> fmt::Display for Token<'a> {
             Token::StringLiteral(s) => write!(f, "{}", s),
             Token::NumericLiteral(s) => write!(f, "{}", s),
             Token::Name(s) => write!(f, "{}", s),
+            Token::Hole(s) => write!(f, "{}", s),
             Token::Shift(s) => write!(f, "{}", s),
 
             Token::As => write!(f, "as"),
+            Token::EnumTerm => write!(f, "enum"),
+            Token::EnumType => write!(f, "Enum"),
+            Token::Elim => write!(f, "elim"),
+            Token::Else => write!(f, "else"),
+            Token::EqType => write!(f, "Eq"),
+            Token::Fix => write!(f, "fix"),
             Token::FunTerm => write!(f, "fun"),
+            Token::If => write!(f, "if"),
+            Token::Import => write!(f, "import"),
+            Token::In => write!(f, "in"),
+            Token::Let => write!(f, "let"),
+            Token::Match => write!(f, "match"),
+            Token::Quote => write!(f, "quote"),
+            Token::Refl => write!(f, "refl"),
+            Token::Splice => write!(f, "splice"),
+            Token::Then => write!(f, "then"),
+            Token::Transport => write!(f, "transport"),
+            Token::With => write!(f, "with"),
+            Token::CodeType => write!(f, "Code"),
             Token::FunType => write!(f, "Fun"),
             Token::RecordTerm => write!(f, "record"),
             Token::RecordType => write!(f, "Record"),
+            Token::InductiveType => write!(f, "Inductive"),
+            Token::VariantTerm => write!(f, "variant"),
+            Token::VariantType => write!(f, "Variant"),
+            Token::TypeOmega => write!(f, "Typeω"),
+            Token::Omega => write!(f, "ω"),
 
             Token::Colon => write!(f, ":"),
             Token::Comma => write!(f, ","),
@@ -122,3 +194,16 @@ fn behavior_after_error() {
     let result: Vec<_> = from_lex.iter().map(Result::is_ok).collect();
     assert_eq!(result, vec![false, true]);
 }
+
+#[test]
+fn numeric_literal_suffixes() {
+    let source = "42u8 1.5f32 0x1f32";
+    let tokens: Vec<_> = tokens(0, source).map(Result::unwrap).collect();
+    let literals: Vec<_> = (tokens.iter())
+        .map(|(_, token, _)| match token {
+            Token::NumericLiteral(literal) => *literal,
+            token => panic!("unexpected token: {}", token),
+        })
+        .collect();
+    assert_eq!(literals, vec!["42u8", "1.5f32", "0x1f32"]);
+}
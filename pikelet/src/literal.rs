@@ -44,6 +44,80 @@ impl Base {
     }
 }
 
+/// The type suffix of a numeric literal, for example the `u8` in `42u8`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum NumberSuffix {
+    U8,
+    U16,
+    U32,
+    U64,
+    S8,
+    S16,
+    S32,
+    S64,
+    F32,
+    F64,
+}
+
+impl NumberSuffix {
+    const ALL: [(&'static str, NumberSuffix); 10] = [
+        ("u8", NumberSuffix::U8),
+        ("u16", NumberSuffix::U16),
+        ("u32", NumberSuffix::U32),
+        ("u64", NumberSuffix::U64),
+        ("s8", NumberSuffix::S8),
+        ("s16", NumberSuffix::S16),
+        ("s32", NumberSuffix::S32),
+        ("s64", NumberSuffix::S64),
+        ("f32", NumberSuffix::F32),
+        ("f64", NumberSuffix::F64),
+    ];
+
+    /// The name of the primitive type that the suffix stands for.
+    pub fn type_name(self) -> &'static str {
+        match self {
+            NumberSuffix::U8 => "U8",
+            NumberSuffix::U16 => "U16",
+            NumberSuffix::U32 => "U32",
+            NumberSuffix::U64 => "U64",
+            NumberSuffix::S8 => "S8",
+            NumberSuffix::S16 => "S16",
+            NumberSuffix::S32 => "S32",
+            NumberSuffix::S64 => "S64",
+            NumberSuffix::F32 => "F32",
+            NumberSuffix::F64 => "F64",
+        }
+    }
+}
+
+/// Returns `true` if the numeric literal has an explicit base, like `0x`.
+fn has_base_prefix(source: &str) -> bool {
+    let source = source.trim_start_matches(['+', '-']);
+    source.starts_with("0b") || source.starts_with("0o") || source.starts_with("0x")
+}
+
+/// Split a numeric literal into its digits and its type suffix, if it has one.
+///
+/// Float suffixes are not recognised in literals with an explicit base, as
+/// `f` is a hexadecimal digit.
+pub fn split_number_suffix(source: &str) -> (&str, Option<NumberSuffix>) {
+    for (suffix_str, suffix) in NumberSuffix::ALL.iter() {
+        if let Some(digits) = source.strip_suffix(suffix_str) {
+            let is_float = matches!(suffix, NumberSuffix::F32 | NumberSuffix::F64);
+            if !digits.is_empty() && (!is_float || !has_base_prefix(digits)) {
+                return (digits, Some(*suffix));
+            }
+        }
+    }
+    (source, None)
+}
+
+/// Returns `true` if the digits of a numeric literal have a fractional part
+/// or an exponent.
+pub fn is_float_number(digits: &str) -> bool {
+    !has_base_prefix(digits) && digits.contains(['.', 'e', 'E'])
+}
+
 /// Convert the first byte of the source string to a digit.
 fn ascii_digit<'source, Token>(lexer: &mut logos::Lexer<'source, Token>) -> Option<u8>
 where
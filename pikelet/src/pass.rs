@@ -6,5 +6,6 @@
 
 pub mod core_to_pretty;
 pub mod core_to_surface;
+pub mod staging;
 pub mod surface_to_core;
 pub mod surface_to_pretty;
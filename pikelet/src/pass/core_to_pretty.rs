@@ -4,7 +4,9 @@
 
 use pretty::{DocAllocator, DocBuilder};
 
-use crate::lang::core::{Constant, Term, TermData};
+use crate::lang::core::{Constant, Term, TermData, UniverseLevel};
+use crate::lang::{Plicity, Usage};
+use crate::pass::surface_to_pretty::{doc_comments, usage_prefix};
 
 /// The precedence of a term.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -38,6 +40,10 @@ where
             .append(alloc.text("local"))
             .append(alloc.space())
             .append(alloc.as_string(index.0)),
+        TermData::Meta(index) => (alloc.nil())
+            .append(alloc.text("meta"))
+            .append(alloc.space())
+            .append(alloc.as_string(index.0)),
 
         TermData::Ann(term, r#type) => paren(
             alloc,
@@ -54,20 +60,73 @@ where
                 ),
         ),
 
-        TermData::TypeType(level) => (alloc.nil())
+        TermData::Let(_, def_term, output_term) => paren(
+            alloc,
+            prec > Prec::Term,
+            (alloc.nil())
+                .append("let")
+                .append(alloc.space())
+                .append("_")
+                .append(alloc.space())
+                .append("=")
+                .group()
+                .append(
+                    (alloc.space())
+                        .append(from_term_prec(alloc, def_term, Prec::Term))
+                        .group()
+                        .nest(4),
+                )
+                .append(alloc.space())
+                .append("in")
+                .group()
+                .append(alloc.space())
+                .append(from_term_prec(alloc, output_term, Prec::Term)),
+        ),
+
+        TermData::TypeType(UniverseLevel::Finite(level)) => (alloc.nil())
             .append("Type")
             .append("^")
-            .append(alloc.as_string(level.0)),
+            .append(alloc.as_string(level)),
+        TermData::TypeType(UniverseLevel::Omega(level)) => (alloc.nil())
+            .append("Typeω")
+            .append("^")
+            .append(alloc.as_string(level)),
         TermData::Lift(term, shift) => (alloc.nil())
             .append(from_term_prec(alloc, term, Prec::Atomic))
             .append("^")
             .append(alloc.as_string(shift.0)),
 
-        TermData::FunctionType(_, input_type, output_type) => paren(
+        TermData::FunctionType(Plicity::Explicit, Usage::Many, _, input_type, output_type) => {
+            paren(
+                alloc,
+                prec > Prec::Arrow,
+                (alloc.nil())
+                    .append(from_term_prec(alloc, input_type, Prec::App))
+                    .append(alloc.space())
+                    .append("->")
+                    .append(alloc.space())
+                    .append(from_term_prec(alloc, output_type, Prec::Arrow)),
+            )
+        }
+        TermData::FunctionType(plicity, usage, _, input_type, output_type) => paren(
             alloc,
             prec > Prec::Arrow,
             (alloc.nil())
-                .append(from_term_prec(alloc, input_type, Prec::App))
+                .append(match plicity {
+                    Plicity::Explicit => "(",
+                    Plicity::Implicit => "{",
+                })
+                .append(match usage {
+                    Usage::Many => alloc.nil(),
+                    usage => usage_prefix(alloc, *usage)
+                        .append("_ :")
+                        .append(alloc.space()),
+                })
+                .append(from_term_prec(alloc, input_type, Prec::Term))
+                .append(match plicity {
+                    Plicity::Explicit => ")",
+                    Plicity::Implicit => "}",
+                })
                 .append(alloc.space())
                 .append("->")
                 .append(alloc.space())
@@ -86,12 +145,29 @@ where
                 .append(alloc.space())
                 .append(from_term_prec(alloc, output_term, Prec::Expr).nest(4)),
         ),
+        TermData::Fix(_, r#type, term) => paren(
+            alloc,
+            prec > Prec::Expr,
+            (alloc.nil())
+                .append("fix")
+                .append(alloc.space())
+                .append("_")
+                .append(alloc.space())
+                .append(":")
+                .append(alloc.space())
+                .append(from_term_prec(alloc, r#type, Prec::Arrow))
+                .append(alloc.space())
+                .append("=>")
+                .group()
+                .append(alloc.space())
+                .append(from_term_prec(alloc, term, Prec::Expr).nest(4)),
+        ),
         TermData::FunctionElim(head_term, input_term) => paren(
             alloc,
             prec > Prec::App,
             from_term_prec(alloc, head_term, Prec::App).append(
                 (alloc.space())
-                    .append(from_term_prec(alloc, input_term, Prec::Arrow))
+                    .append(from_term_prec(alloc, input_term, Prec::Atomic))
                     .group()
                     .nest(4),
             ),
@@ -102,6 +178,114 @@ where
             .append(alloc.space())
             .append("{")
             .group()
+            .append(alloc.concat(type_entries.iter().map(
+                |(label, docs, usage, r#type, default_term)| {
+                    (alloc.nil())
+                        .append(alloc.hardline())
+                        .append(doc_comments(alloc, docs))
+                        .append(usage_prefix(alloc, *usage))
+                        .append(alloc.text(label))
+                        .append(alloc.space())
+                        .append(":")
+                        .group()
+                        .append(
+                            (alloc.space())
+                                .append(from_term_prec(alloc, r#type, Prec::Term))
+                                .append(match default_term {
+                                    Some(default_term) => (alloc.space())
+                                        .append("=")
+                                        .append(alloc.space())
+                                        .append(from_term_prec(alloc, default_term, Prec::Term)),
+                                    None => alloc.nil(),
+                                })
+                                .append(",")
+                                .group()
+                                .nest(4),
+                        )
+                        .nest(4)
+                        .group()
+                },
+            )))
+            .append("}"),
+        TermData::RecordTerm(term_entries) => (alloc.nil())
+            .append("record")
+            .append(alloc.space())
+            .append("{")
+            .group()
+            .append(alloc.concat(term_entries.iter().map(|(label, term)| {
+                (alloc.nil())
+                    .append(alloc.hardline())
+                    .append(alloc.text(label))
+                    .append(alloc.space())
+                    .append("=")
+                    .group()
+                    .append(
+                        (alloc.space())
+                            .append(from_term_prec(alloc, term, Prec::Term))
+                            .append(",")
+                            .group()
+                            .nest(4),
+                    )
+                    .nest(4)
+                    .group()
+            })))
+            .append("}"),
+        TermData::RecordElim(head_term, label) => (alloc.nil())
+            .append(from_term_prec(alloc, head_term, Prec::Atomic))
+            .append(".")
+            .append(alloc.text(label)),
+
+        TermData::EnumType(labels) => (alloc.nil())
+            .append("Enum")
+            .append(alloc.space())
+            .append("{")
+            .append(match labels.len() {
+                0 => alloc.nil(),
+                _ => (alloc.space())
+                    .append(alloc.intersperse(
+                        labels.iter().map(|label| alloc.text(label)),
+                        alloc.text(",").append(alloc.space()),
+                    ))
+                    .append(alloc.space())
+                    .nest(4),
+            })
+            .append("}")
+            .group(),
+        TermData::EnumTerm(label) => (alloc.nil())
+            .append("enum")
+            .append(alloc.space())
+            .append(alloc.text(label)),
+        TermData::EnumElim(head_term, branches) => (alloc.nil())
+            .append("match")
+            .append(alloc.space())
+            .append(from_term_prec(alloc, head_term, Prec::Term))
+            .append(alloc.space())
+            .append("{")
+            .group()
+            .append(alloc.concat(branches.iter().map(|(label, term)| {
+                (alloc.nil())
+                    .append(alloc.hardline())
+                    .append(alloc.text(label))
+                    .append(alloc.space())
+                    .append("=>")
+                    .group()
+                    .append(
+                        (alloc.space())
+                            .append(from_term_prec(alloc, term, Prec::Term))
+                            .append(",")
+                            .group()
+                            .nest(4),
+                    )
+                    .nest(4)
+                    .group()
+            })))
+            .append("}"),
+
+        TermData::VariantType(type_entries) => (alloc.nil())
+            .append("Variant")
+            .append(alloc.space())
+            .append("{")
+            .group()
             .append(alloc.concat(type_entries.iter().map(|(label, r#type)| {
                 (alloc.nil())
                     .append(alloc.hardline())
@@ -120,17 +304,31 @@ where
                     .group()
             })))
             .append("}"),
-        TermData::RecordTerm(term_entries) => (alloc.nil())
-            .append("record")
+        TermData::VariantTerm(label, term) => paren(
+            alloc,
+            prec > Prec::App,
+            (alloc.nil())
+                .append("variant")
+                .append(alloc.space())
+                .append(alloc.text(label))
+                .append(alloc.space())
+                .append(from_term_prec(alloc, term, Prec::Atomic)),
+        ),
+        TermData::VariantElim(head_term, branches) => (alloc.nil())
+            .append("match")
+            .append(alloc.space())
+            .append(from_term_prec(alloc, head_term, Prec::Term))
             .append(alloc.space())
             .append("{")
             .group()
-            .append(alloc.concat(term_entries.iter().map(|(label, term)| {
+            .append(alloc.concat(branches.iter().map(|(label, _, term)| {
                 (alloc.nil())
                     .append(alloc.hardline())
                     .append(alloc.text(label))
                     .append(alloc.space())
-                    .append("=")
+                    .append("_")
+                    .append(alloc.space())
+                    .append("=>")
                     .group()
                     .append(
                         (alloc.space())
@@ -143,10 +341,173 @@ where
                     .group()
             })))
             .append("}"),
-        TermData::RecordElim(head_term, label) => (alloc.nil())
+
+        TermData::InductiveType(_, constructors) => (alloc.nil())
+            .append("Inductive")
+            .append(alloc.space())
+            .append("_")
+            .append(alloc.space())
+            .append("{")
+            .group()
+            .append(alloc.concat(constructors.iter().map(|(label, r#type)| {
+                (alloc.nil())
+                    .append(alloc.hardline())
+                    .append(alloc.text(label))
+                    .append(alloc.space())
+                    .append(":")
+                    .group()
+                    .append(
+                        (alloc.space())
+                            .append(from_term_prec(alloc, r#type, Prec::Term))
+                            .append(",")
+                            .group()
+                            .nest(4),
+                    )
+                    .nest(4)
+                    .group()
+            })))
+            .append("}"),
+        TermData::InductiveTerm(r#type, label, inputs) => paren(
+            alloc,
+            prec > Prec::App && !inputs.is_empty(),
+            (alloc.nil())
+                .append(from_term_prec(alloc, r#type, Prec::Atomic))
+                .append(".")
+                .append(alloc.text(label))
+                .append(
+                    alloc
+                        .concat(inputs.iter().map(|input| {
+                            alloc
+                                .space()
+                                .append(from_term_prec(alloc, input, Prec::Atomic))
+                        }))
+                        .group()
+                        .nest(4),
+                ),
+        ),
+        TermData::InductiveElim(head_term, motive, branches) => (alloc.nil())
+            .append("elim")
+            .append(alloc.space())
+            .append(from_term_prec(alloc, motive, Prec::Atomic))
+            .append(alloc.space())
             .append(from_term_prec(alloc, head_term, Prec::Atomic))
-            .append(".")
-            .append(alloc.text(label)),
+            .append(alloc.space())
+            .append("{")
+            .group()
+            .append(
+                alloc.concat(branches.iter().map(|(label, name_hints, term)| {
+                    (alloc.nil())
+                        .append(alloc.hardline())
+                        .append(alloc.text(label))
+                        .append(alloc.concat(name_hints.iter().map(|_| alloc.space().append("_"))))
+                        .append(alloc.space())
+                        .append("=>")
+                        .group()
+                        .append(
+                            (alloc.space())
+                                .append(from_term_prec(alloc, term, Prec::Term))
+                                .append(",")
+                                .group()
+                                .nest(4),
+                        )
+                        .nest(4)
+                        .group()
+                })),
+            )
+            .append("}"),
+
+        TermData::BoolElim(head_term, if_true, if_false) => paren(
+            alloc,
+            prec > Prec::Term,
+            (alloc.nil())
+                .append("if")
+                .append(alloc.space())
+                .append(from_term_prec(alloc, head_term, Prec::Term))
+                .append(alloc.space())
+                .append("then")
+                .group()
+                .append(
+                    (alloc.space())
+                        .append(from_term_prec(alloc, if_true, Prec::Term))
+                        .group()
+                        .nest(4),
+                )
+                .append(alloc.space())
+                .append("else")
+                .group()
+                .append(
+                    (alloc.space())
+                        .append(from_term_prec(alloc, if_false, Prec::Term))
+                        .group()
+                        .nest(4),
+                ),
+        ),
+
+        TermData::EqType(r#type, lhs, rhs) => paren(
+            alloc,
+            prec > Prec::App,
+            (alloc.nil()).append("Eq").append(
+                (alloc.nil())
+                    .append(alloc.space())
+                    .append(from_term_prec(alloc, r#type, Prec::Atomic))
+                    .append(alloc.space())
+                    .append(from_term_prec(alloc, lhs, Prec::Atomic))
+                    .append(alloc.space())
+                    .append(from_term_prec(alloc, rhs, Prec::Atomic))
+                    .group()
+                    .nest(4),
+            ),
+        ),
+        TermData::EqTerm => alloc.text("refl"),
+        TermData::EqElim(head_term, motive, term) => paren(
+            alloc,
+            prec > Prec::App,
+            (alloc.nil()).append("transport").append(
+                (alloc.nil())
+                    .append(alloc.space())
+                    .append(from_term_prec(alloc, motive, Prec::Atomic))
+                    .append(alloc.space())
+                    .append(from_term_prec(alloc, head_term, Prec::Atomic))
+                    .append(alloc.space())
+                    .append(from_term_prec(alloc, term, Prec::Atomic))
+                    .group()
+                    .nest(4),
+            ),
+        ),
+
+        TermData::CodeType(term) => paren(
+            alloc,
+            prec > Prec::App,
+            (alloc.nil()).append("Code").append(
+                (alloc.nil())
+                    .append(alloc.space())
+                    .append(from_term_prec(alloc, term, Prec::Atomic))
+                    .group()
+                    .nest(4),
+            ),
+        ),
+        TermData::CodeTerm(term) => paren(
+            alloc,
+            prec > Prec::App,
+            (alloc.nil()).append("quote").append(
+                (alloc.nil())
+                    .append(alloc.space())
+                    .append(from_term_prec(alloc, term, Prec::Atomic))
+                    .group()
+                    .nest(4),
+            ),
+        ),
+        TermData::CodeElim(term) => paren(
+            alloc,
+            prec > Prec::App,
+            (alloc.nil()).append("splice").append(
+                (alloc.nil())
+                    .append(alloc.space())
+                    .append(from_term_prec(alloc, term, Prec::Atomic))
+                    .group()
+                    .nest(4),
+            ),
+        ),
 
         TermData::ArrayTerm(term_entries) | TermData::ListTerm(term_entries) => (alloc.nil())
             .append("[")
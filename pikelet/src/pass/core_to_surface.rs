@@ -12,7 +12,7 @@ use std::collections::HashMap;
 
 use crate::lang::core::{Constant, Globals, Locals, Term, TermData, UniverseLevel, UniverseOffset};
 use crate::lang::surface;
-use crate::lang::Located;
+use crate::lang::{Located, Plicity};
 
 /// Distillation state.
 pub struct State<'me> {
@@ -128,36 +128,66 @@ impl<'me> State<'me> {
                 Some(name) => surface::TermData::Name(name.clone()),
                 None => surface::TermData::Error, // TODO: Log error?
             },
+            TermData::Meta(index) => surface::TermData::Hole(index.0.to_string()),
 
             TermData::Ann(term, r#type) => surface::TermData::Ann(
                 Box::new(self.from_term(term)),
                 Box::new(self.from_term(r#type)),
             ),
 
+            TermData::Let(name_hint, def_term, output_term) => {
+                let (def_type, def_term) = match &def_term.data {
+                    TermData::Ann(def_term, def_type) => (
+                        Some(Box::new(self.from_term(def_type))),
+                        self.from_term(def_term),
+                    ),
+                    _ => (None, self.from_term(def_term)),
+                };
+                let fresh_name = self.push_name(Some(name_hint));
+                let output_term = self.from_term(output_term);
+                self.pop_name();
+
+                surface::TermData::Let(
+                    Located::generated(fresh_name),
+                    def_type,
+                    Box::new(def_term),
+                    Box::new(output_term),
+                )
+            }
+
             TermData::TypeType(level) => {
                 let universe0 = match self.globals.get("Type") {
                     Some(_) => surface::TermData::Name("Type".to_owned()),
                     None => surface::TermData::Error, // TODO: Log error?
                 };
                 match level {
-                    UniverseLevel(0) => universe0,
-                    UniverseLevel(level) => {
+                    UniverseLevel::Finite(0) => universe0,
+                    UniverseLevel::Finite(level) => {
                         surface::TermData::Lift(Box::new(Located::generated(universe0)), *level)
                     }
+                    UniverseLevel::Omega(0) => surface::TermData::TypeOmega,
+                    UniverseLevel::Omega(level) => surface::TermData::Lift(
+                        Box::new(Located::generated(surface::TermData::TypeOmega)),
+                        *level,
+                    ),
                 }
             }
             TermData::Lift(term, UniverseOffset(offset)) => {
                 surface::TermData::Lift(Box::new(self.from_term(term)), *offset)
             }
 
-            TermData::FunctionType(input_name_hint, input_type, output_type) => {
+            TermData::FunctionType(plicity, usage, input_name_hint, input_type, output_type) => {
                 // FIXME: properly group inputs!
                 let input_type = self.from_term(input_type);
                 let fresh_input_name = self.push_name(input_name_hint.as_ref().map(String::as_str));
-                let input_type_groups =
-                    vec![(vec![Located::generated(fresh_input_name)], input_type)];
+                let input_type_groups = vec![(
+                    *plicity,
+                    *usage,
+                    vec![Located::generated(fresh_input_name)],
+                    input_type,
+                )];
                 let output_type = self.from_term(output_type);
-                self.pop_many_names(input_type_groups.iter().map(|(ns, _)| ns.len()).sum());
+                self.pop_many_names(input_type_groups.iter().map(|(_, _, ns, _)| ns.len()).sum());
 
                 surface::TermData::FunctionType(input_type_groups, Box::new(output_type))
             }
@@ -178,14 +208,28 @@ impl<'me> State<'me> {
                 let output_term = self.from_term(current_output_term);
                 self.pop_many_names(input_names.len());
 
-                surface::TermData::FunctionTerm(input_names, Box::new(output_term))
+                let input_name_groups = vec![(Plicity::Explicit, input_names, None)];
+                surface::TermData::FunctionTerm(input_name_groups, Box::new(output_term))
+            }
+            TermData::Fix(name_hint, r#type, term) => {
+                let r#type = self.from_term(r#type);
+                let fresh_name = self.push_name(Some(name_hint));
+                let term = self.from_term(term);
+                self.pop_name();
+
+                let fix_term =
+                    surface::TermData::Fix(Located::generated(fresh_name), Box::new(term));
+                surface::TermData::Ann(
+                    Box::new(surface::Term::generated(fix_term)),
+                    Box::new(r#type),
+                )
             }
             TermData::FunctionElim(head_term, input_term) => {
                 let mut current_head_term = head_term;
 
-                let mut input_terms = vec![self.from_term(input_term)];
+                let mut input_terms = vec![(None, self.from_term(input_term))];
                 while let TermData::FunctionElim(head_term, input_term) = &current_head_term.data {
-                    input_terms.push(self.from_term(input_term));
+                    input_terms.push((None, self.from_term(input_term)));
                     current_head_term = head_term;
                 }
                 input_terms.reverse();
@@ -197,15 +241,29 @@ impl<'me> State<'me> {
             TermData::RecordType(type_entries) => {
                 let type_entries = type_entries
                     .iter()
-                    .map(|(label, entry_type)| {
+                    .map(|(label, docs, usage, entry_type, default_term)| {
+                        let docs = docs.to_vec();
+                        let usage = *usage;
                         let entry_type = self.from_term(entry_type);
+                        let default_term = (default_term.as_ref())
+                            .map(|default_term| self.from_term(default_term));
                         let label = label.clone();
                         match self.push_name(Some(&label)) {
-                            name if name == label => (Located::generated(label), None, entry_type),
+                            name if name == label => (
+                                docs,
+                                usage,
+                                Located::generated(label),
+                                None,
+                                entry_type,
+                                default_term,
+                            ),
                             name => (
+                                docs,
+                                usage,
                                 Located::generated(label),
                                 Some(Located::generated(name)),
                                 entry_type,
+                                default_term,
                             ),
                         }
                     })
@@ -239,6 +297,136 @@ impl<'me> State<'me> {
                 Located::generated(label.clone()),
             ),
 
+            TermData::EnumType(labels) => surface::TermData::EnumType(
+                labels
+                    .iter()
+                    .map(|label| Located::generated(label.clone()))
+                    .collect(),
+            ),
+            TermData::EnumTerm(label) => {
+                surface::TermData::EnumTerm(Located::generated(label.clone()))
+            }
+            TermData::EnumElim(head_term, branches) => surface::TermData::EnumElim(
+                Box::new(self.from_term(head_term)),
+                branches
+                    .iter()
+                    .map(|(label, branch_term)| {
+                        (
+                            Located::generated(label.clone()),
+                            Vec::new(),
+                            self.from_term(branch_term),
+                        )
+                    })
+                    .collect(),
+            ),
+
+            TermData::VariantType(type_entries) => surface::TermData::VariantType(
+                type_entries
+                    .iter()
+                    .map(|(label, entry_type)| {
+                        (
+                            Located::generated(label.clone()),
+                            self.from_term(entry_type),
+                        )
+                    })
+                    .collect(),
+            ),
+            TermData::VariantTerm(label, term) => surface::TermData::VariantTerm(
+                Located::generated(label.clone()),
+                Box::new(self.from_term(term)),
+            ),
+            TermData::VariantElim(head_term, branches) => surface::TermData::EnumElim(
+                Box::new(self.from_term(head_term)),
+                branches
+                    .iter()
+                    .map(|(label, name_hint, branch_term)| {
+                        let name = self.push_name(name_hint.as_deref());
+                        let branch_term = self.from_term(branch_term);
+                        self.pop_name();
+
+                        (
+                            Located::generated(label.clone()),
+                            (name_hint.iter())
+                                .map(|_| Located::generated(name.clone()))
+                                .collect(),
+                            branch_term,
+                        )
+                    })
+                    .collect(),
+            ),
+
+            TermData::InductiveType(name_hint, constructors) => {
+                let name = self.push_name(Some(name_hint));
+                let constructors = constructors
+                    .iter()
+                    .map(|(label, r#type)| {
+                        (Located::generated(label.clone()), self.from_term(r#type))
+                    })
+                    .collect();
+                self.pop_name();
+
+                surface::TermData::InductiveType(Located::generated(name), constructors)
+            }
+            TermData::InductiveTerm(r#type, label, inputs) => {
+                let head_term = surface::TermData::RecordElim(
+                    Box::new(self.from_term(r#type)),
+                    Located::generated(label.clone()),
+                );
+                match inputs.len() {
+                    0 => head_term,
+                    _ => surface::TermData::FunctionElim(
+                        Box::new(surface::Term::generated(head_term)),
+                        (inputs.iter())
+                            .map(|input| (None, self.from_term(input)))
+                            .collect(),
+                    ),
+                }
+            }
+            TermData::InductiveElim(head_term, motive, branches) => {
+                surface::TermData::InductiveElim(
+                    Box::new(self.from_term(head_term)),
+                    Box::new(self.from_term(motive)),
+                    branches
+                        .iter()
+                        .map(|(label, name_hints, branch_term)| {
+                            let names = (name_hints.iter())
+                                .map(|name_hint| {
+                                    Located::generated(self.push_name(name_hint.as_deref()))
+                                })
+                                .collect::<Vec<_>>();
+                            let branch_term = self.from_term(branch_term);
+                            self.pop_many_names(names.len());
+
+                            (Located::generated(label.clone()), names, branch_term)
+                        })
+                        .collect(),
+                )
+            }
+
+            TermData::EqType(r#type, lhs, rhs) => surface::TermData::EqType(
+                Box::new(self.from_term(r#type)),
+                Box::new(self.from_term(lhs)),
+                Box::new(self.from_term(rhs)),
+            ),
+            TermData::EqTerm => surface::TermData::EqTerm,
+            TermData::EqElim(head_term, motive, term) => surface::TermData::EqElim(
+                Box::new(self.from_term(head_term)),
+                Box::new(self.from_term(motive)),
+                Box::new(self.from_term(term)),
+            ),
+
+            TermData::CodeType(r#type) => {
+                surface::TermData::CodeType(Box::new(self.from_term(r#type)))
+            }
+            TermData::CodeTerm(term) => surface::TermData::CodeTerm(Box::new(self.from_term(term))),
+            TermData::CodeElim(term) => surface::TermData::CodeElim(Box::new(self.from_term(term))),
+
+            TermData::BoolElim(head_term, if_true, if_false) => surface::TermData::BoolElim(
+                Box::new(self.from_term(head_term)),
+                Box::new(self.from_term(if_true)),
+                Box::new(self.from_term(if_false)),
+            ),
+
             TermData::ArrayTerm(entry_terms) | TermData::ListTerm(entry_terms) => {
                 let core_entry_terms = entry_terms
                     .iter()
@@ -272,7 +460,11 @@ impl<'me> State<'me> {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use super::*;
+    use crate::lang::core::Docs;
+    use crate::lang::Usage;
 
     #[test]
     fn push_default_name() {
@@ -358,4 +550,32 @@ mod tests {
         assert_eq!(state.push_name(Some("Type")), "Type-1");
         assert_eq!(state.push_name(Some("Type")), "Type-2");
     }
+
+    #[test]
+    fn record_type_docs() {
+        let globals = Globals::default();
+        let mut state = State::new(&globals);
+
+        let docs: Docs = Arc::new([" The carrier type".to_owned()]);
+        let r#type = Arc::new(Term::generated(TermData::TypeType(UniverseLevel::Finite(
+            0,
+        ))));
+        let term = Term::generated(TermData::RecordType(Arc::new([(
+            "Carrier".to_owned(),
+            docs,
+            Usage::Zero,
+            r#type,
+            None,
+        )])));
+
+        match state.from_term(&term).data {
+            surface::TermData::RecordType(type_entries) => {
+                let (docs, usage, label, _, _, _) = &type_entries[0];
+                assert_eq!(docs, &[" The carrier type".to_owned()]);
+                assert_eq!(*usage, Usage::Zero);
+                assert_eq!(label.data, "Carrier");
+            }
+            _ => panic!("expected a record type"),
+        }
+    }
 }
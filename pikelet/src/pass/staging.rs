@@ -0,0 +1,81 @@
+//! Evaluates the compile-time stages of the [core language], leaving residual
+//! runtime terms.
+//!
+//! Each splice that appears at runtime is evaluated, and if this results in a
+//! quoted term, the splice is replaced with the code that was generated.
+//! Splices that cannot be evaluated any further are left in place.
+//!
+//! [core language]: crate::lang::core
+
+use std::sync::Arc;
+
+use crate::lang::core::semantics::{self, Unfold, Value};
+use crate::lang::core::{Globals, Locals, Term, TermData, UniverseOffset};
+
+/// Stage a closed term, evaluating the splices that appear in it.
+pub fn stage_term(globals: &Globals, term: &Term) -> Term {
+    stage_term_in(globals, UniverseOffset(0), &mut Locals::new(), 0, term)
+}
+
+/// Stage a term in a local environment of the given depth.
+///
+/// Let-bound locals are bound to their values, so that the splices that refer
+/// to them can be evaluated. Every other local is bound to a fresh variable.
+fn stage_term_in(
+    globals: &Globals,
+    universe_offset: UniverseOffset,
+    locals: &mut Locals<Arc<Value>>,
+    depth: u32,
+    term: &Term,
+) -> Term {
+    match &term.data {
+        TermData::Let(name, def_term, output_term) => {
+            let def_term = stage_term_in(globals, universe_offset, locals, depth, def_term);
+            let def_value = semantics::eval_term(globals, universe_offset, locals, &def_term);
+            locals.push(def_value);
+            let output_term =
+                stage_term_in(globals, universe_offset, locals, depth + 1, output_term);
+            locals.pop();
+
+            Term::new(
+                term.location,
+                TermData::Let(name.clone(), Arc::new(def_term), Arc::new(output_term)),
+            )
+        }
+        TermData::CodeElim(head_term) => {
+            let head_value = semantics::eval_term(globals, universe_offset, locals, head_term);
+            match head_value.force(globals) {
+                Value::CodeTerm(value) => {
+                    let term_data =
+                        semantics::read_back_value(globals, locals.size(), Unfold::Never, value)
+                            .data;
+                    Term::new(term.location, term_data)
+                }
+                _ => term.clone(),
+            }
+        }
+        TermData::Lift(lifted_term, offset) => match universe_offset + *offset {
+            Some(universe_offset) => {
+                let lifted_term =
+                    stage_term_in(globals, universe_offset, locals, depth, lifted_term);
+                Term::new(
+                    term.location,
+                    TermData::Lift(Arc::new(lifted_term), *offset),
+                )
+            }
+            None => term.clone(),
+        },
+        _ => term
+            .try_map_subterms(depth, &mut |term, term_depth| {
+                let binder_count = (term_depth - depth) as usize;
+                for _ in 0..binder_count {
+                    let local = Arc::new(Value::local(locals.size().next_level(), []));
+                    locals.push(local);
+                }
+                let term = stage_term_in(globals, universe_offset, locals, term_depth, term);
+                locals.pop_many(binder_count);
+                Some(term)
+            })
+            .unwrap(), // Staging does not fail
+    }
+}
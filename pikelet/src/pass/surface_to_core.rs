@@ -5,46 +5,102 @@
 //! [surface language]: crate::lang::surface
 //! [core language]: crate::lang::core
 
+use codespan_reporting::files::SimpleFiles;
 use contracts::debug_ensures;
 use crossbeam_channel::Sender;
 use num_traits::{Float, PrimInt, Signed, Unsigned};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
-use crate::lang::core::semantics::{self, Elim, RecordClosure, Unfold, Value};
-use crate::lang::surface::{Term, TermData};
-use crate::lang::{core, Location};
+use crate::lang::core::semantics::{self, Elim, InductiveClosure, RecordClosure, Value};
+use crate::lang::core::typing::{self, ConstructorTypeError};
+use crate::lang::surface::{self, Term, TermData};
+use crate::lang::{core, Located, Location, Plicity, Usage};
 use crate::literal;
 use crate::pass::core_to_surface;
 use crate::reporting::{AmbiguousTerm, ExpectedType, Message, SurfaceToCoreMessage};
 
+mod unification;
+
+use self::unification::MetaSource;
+
 /// The state of the elaborator.
 pub struct State<'me> {
     /// Global definition environment.
     globals: &'me core::Globals,
     /// The current universe offset.
     universe_offset: core::UniverseOffset,
+    /// The current stage, where `0` is runtime, and later stages are
+    /// evaluated at compile time.
+    stage: u32,
+    /// Local stage environment (used for checking the stages of local variables).
+    local_stages: core::Locals<u32>,
+    /// The number of times each local variable has been used at runtime,
+    /// indexed by level.
+    local_uses: Vec<Usage>,
+    /// Whether the current term is erased at runtime.
+    erased: bool,
     /// Substitutions from the user-defined names to the level in which they were bound.
     local_levels: Vec<(Option<String>, core::LocalLevel)>,
     /// Local type environment (used for getting the types of local variables).
     local_declarations: core::Locals<Arc<Value>>,
     /// Local value environment (used for evaluation).
     local_definitions: core::Locals<Arc<Value>>,
+    /// Metavariables created during elaboration, along with their solutions.
+    metas: Vec<unification::MetaEntry>,
     /// Distillation state (used for pretty printing).
     core_to_surface: core_to_surface::State<'me>,
+    /// Source files (used for resolving imports).
+    files: &'me RefCell<SimpleFiles<String, String>>,
+    /// Files that have already been imported, along with the elaborated term
+    /// and type of each file.
+    imports: HashMap<PathBuf, (Arc<core::Term>, Arc<core::Term>)>,
+    /// Files that are currently being imported (used for detecting cycles).
+    pending_imports: Vec<PathBuf>,
     /// The diagnostic messages accumulated during elaboration.
     message_tx: Sender<Message>,
 }
 
+/// The branches of a match expression, in the order that their labels appear
+/// in the type of the head term.
+enum MatchBranches<'branches> {
+    /// Branches on an enumeration.
+    Enum(Vec<&'branches surface::Branch>),
+    /// Branches on a variant, along with the type of the payload to be bound
+    /// in each branch.
+    Variant(Vec<(&'branches surface::Branch, Arc<Value>)>),
+    /// Branches on an inductive type, along with the type of the head term.
+    Inductive(
+        Arc<Value>,
+        InductiveClosure,
+        Vec<&'branches surface::Branch>,
+    ),
+}
+
 impl<'me> State<'me> {
     /// Construct a new elaborator state.
-    pub fn new(globals: &'me core::Globals, message_tx: Sender<Message>) -> State<'me> {
+    pub fn new(
+        globals: &'me core::Globals,
+        files: &'me RefCell<SimpleFiles<String, String>>,
+        message_tx: Sender<Message>,
+    ) -> State<'me> {
         State {
             globals,
             universe_offset: core::UniverseOffset(0),
+            stage: 0,
+            local_stages: core::Locals::new(),
+            local_uses: Vec::new(),
+            erased: false,
             local_levels: Vec::new(),
             local_declarations: core::Locals::new(),
             local_definitions: core::Locals::new(),
+            metas: Vec::new(),
             core_to_surface: core_to_surface::State::new(globals),
+            files,
+            imports: HashMap::new(),
+            pending_imports: Vec::new(),
             message_tx,
         }
     }
@@ -69,6 +125,8 @@ impl<'me> State<'me> {
     fn push_local(&mut self, name: Option<&str>, value: Arc<Value>, r#type: Arc<Value>) {
         self.local_levels
             .push((name.map(str::to_owned), self.next_level()));
+        self.local_stages.push(self.stage);
+        self.local_uses.push(Usage::Zero);
         self.local_declarations.push(r#type);
         self.local_definitions.push(value);
         self.core_to_surface.push_name(name);
@@ -84,6 +142,8 @@ impl<'me> State<'me> {
     /// Pop a local entry.
     fn pop_local(&mut self) {
         self.local_levels.pop();
+        self.local_stages.pop();
+        self.local_uses.pop();
         self.local_declarations.pop();
         self.local_definitions.pop();
         self.core_to_surface.pop_name();
@@ -93,6 +153,9 @@ impl<'me> State<'me> {
     fn pop_many_locals(&mut self, count: usize) {
         self.local_levels
             .truncate(self.local_levels.len().saturating_sub(count));
+        self.local_stages.pop_many(count);
+        self.local_uses
+            .truncate(self.local_uses.len().saturating_sub(count));
         self.local_declarations.pop_many(count);
         self.local_definitions.pop_many(count);
         self.core_to_surface.pop_many_names(count);
@@ -103,6 +166,65 @@ impl<'me> State<'me> {
         self.message_tx.send(error.into()).unwrap();
     }
 
+    /// Run a callback in a context that is used the given number of times,
+    /// scaling the uses of the local variables that it contains.
+    fn with_usage<T>(&mut self, usage: Usage, f: impl FnOnce(&mut Self) -> T) -> T {
+        let initial_uses = vec![Usage::Zero; self.local_uses.len()];
+        let outer_uses = std::mem::replace(&mut self.local_uses, initial_uses);
+        let previous_erased = self.erased;
+        self.erased |= usage == Usage::Zero;
+
+        let output = f(self);
+
+        self.erased = previous_erased;
+        let inner_uses = std::mem::replace(&mut self.local_uses, outer_uses);
+        for (outer_use, inner_use) in Iterator::zip(self.local_uses.iter_mut(), inner_uses) {
+            *outer_use = *outer_use + usage * inner_use;
+        }
+        output
+    }
+
+    /// Run a callback for each of the alternatives of an elimination, where
+    /// only one alternative will be used at runtime, joining the uses of the
+    /// local variables from each alternative.
+    fn with_alternatives<T>(
+        &mut self,
+        count: usize,
+        mut f: impl FnMut(&mut Self, usize) -> T,
+    ) -> Vec<T> {
+        let initial_uses = self.local_uses.clone();
+        let mut joined_uses = None::<Vec<Usage>>;
+        let mut outputs = Vec::with_capacity(count);
+
+        for index in 0..count {
+            self.local_uses.clone_from(&initial_uses);
+            outputs.push(f(self, index));
+            joined_uses = Some(match joined_uses {
+                None => self.local_uses.clone(),
+                Some(joined_uses) => Iterator::zip(joined_uses.iter(), self.local_uses.iter())
+                    .map(|(use0, use1)| use0.join(*use1))
+                    .collect(),
+            });
+        }
+
+        self.local_uses = joined_uses.unwrap_or(initial_uses);
+        outputs
+    }
+
+    /// Check that the local variable at the given position in the local
+    /// environment was used the number of times allowed by its usage.
+    fn check_local_usage(&mut self, position: usize, usage: Usage, name: &Located<String>) {
+        let found_usage = self.local_uses[position];
+        if !usage.allows(found_usage) {
+            self.report(SurfaceToCoreMessage::UnexpectedLocalUsage {
+                location: name.location,
+                name: name.data.clone(),
+                usage,
+                found_usage,
+            });
+        }
+    }
+
     /// Evaluate a [`core::Term`] into a [`Value`].
     ///
     /// [`Value`]: crate::lang::core::semantics::Value
@@ -121,11 +243,22 @@ impl<'me> State<'me> {
         &self,
         head_value: Arc<Value>,
         label: &str,
-        closure: &RecordClosure,
+        closure: &RecordClosure<core::TypeEntry>,
     ) -> Option<Arc<Value>> {
         semantics::record_elim_type(self.globals, head_value, label, closure)
     }
 
+    /// Return the type of an equality elimination.
+    pub fn eq_elim_type(&self, motive: Arc<Value>, side: Arc<Value>) -> Arc<Value> {
+        semantics::eq_elim_type(self.globals, motive, side)
+    }
+
+    /// Return the type of the motive of an equality or inductive elimination.
+    fn motive_type(&self, r#type: Arc<Value>) -> Arc<Value> {
+        let locals = self.local_definitions.clone();
+        semantics::motive_type(self.universe_offset, locals, r#type)
+    }
+
     /// Fully normalize a [`core::Term`] using [normalization by evaluation].
     ///
     /// [`core::Term`]: crate::lang::core::Term
@@ -148,21 +281,25 @@ impl<'me> State<'me> {
     /// [`Value`]: crate::lang::core::semantics::Value
     /// [`core::Term`]: crate::lang::core::Term
     pub fn read_back_value(&self, value: &Value) -> core::Term {
-        semantics::read_back_value(
-            self.globals,
-            self.local_definitions.size(),
-            Unfold::Never,
-            value,
-        )
+        self.read_back_value_at(self.local_definitions.size(), value)
     }
 
-    /// Check that one [`Value`] is a subtype of another [`Value`].
+    /// Check that one [`Value`] is a subtype of another [`Value`], solving
+    /// metavariables where needed.
     ///
     /// Returns `false` if either value is not a type.
     ///
     /// [`Value`]: crate::lang::core::semantics::Value
-    pub fn is_subtype(&self, value0: &Value, value1: &Value) -> bool {
-        semantics::is_subtype(self.globals, self.local_definitions.size(), value0, value1)
+    pub fn is_subtype(&mut self, value0: &Arc<Value>, value1: &Arc<Value>) -> bool {
+        self.unify_subtype(self.local_definitions.size(), value0, value1)
+    }
+
+    /// Check that one [`Value`] is computationally equal to another [`Value`],
+    /// solving metavariables where needed.
+    ///
+    /// [`Value`]: crate::lang::core::semantics::Value
+    pub fn is_equal(&mut self, value0: &Arc<Value>, value1: &Arc<Value>) -> bool {
+        self.unify(self.local_definitions.size(), value0, value1)
     }
 
     /// Distill a [`core::Term`] into a [`surface::Term`].
@@ -186,19 +323,33 @@ impl<'me> State<'me> {
         self.core_to_surface_term(&core_term)
     }
 
+    /// Synthesize the type of a top-level surface term, and return the
+    /// elaborated term with the solutions of its metavariables substituted.
+    ///
+    /// Metavariables that could not be solved will be reported as errors.
+    pub fn synth_top_level_type(&mut self, term: &Term) -> (core::Term, Arc<Value>) {
+        let (core_term, r#type) = self.synth_type(term);
+        self.report_unsolved_metas();
+
+        let core_term = self.zonk_term(&core_term);
+        let core_type = self.read_back_value(&r#type);
+        (core_term, self.eval_term(&core_type))
+    }
+
     /// Check that a term is a type, and return the elaborated term and the
     /// universe level it inhabits.
     #[debug_ensures(self.universe_offset == old(self.universe_offset))]
+    #[debug_ensures(self.stage == old(self.stage))]
     #[debug_ensures(self.local_levels.len() == old(self.local_levels.len()))]
     #[debug_ensures(self.local_declarations.size() == old(self.local_declarations.size()))]
     #[debug_ensures(self.local_definitions.size() == old(self.local_definitions.size()))]
     pub fn is_type(&mut self, term: &Term) -> (core::Term, Option<core::UniverseLevel>) {
         let (core_term, r#type) = self.synth_type(term);
-        match r#type.force(self.globals) {
+        match self.force_value(&r#type).as_ref() {
             Value::TypeType(level) => (core_term, Some(*level)),
             Value::Error => (core::Term::new(term.location, core::TermData::Error), None),
             found_type => {
-                let found_type = self.read_back_to_surface_term(&found_type);
+                let found_type = self.read_back_to_surface_term(found_type);
                 self.report(SurfaceToCoreMessage::MismatchedTypes {
                     location: term.location,
                     found_type,
@@ -211,91 +362,297 @@ impl<'me> State<'me> {
 
     /// Check that a term is an element of a type, and return the elaborated term.
     #[debug_ensures(self.universe_offset == old(self.universe_offset))]
+    #[debug_ensures(self.stage == old(self.stage))]
     #[debug_ensures(self.local_levels.len() == old(self.local_levels.len()))]
     #[debug_ensures(self.local_declarations.size() == old(self.local_declarations.size()))]
     #[debug_ensures(self.local_definitions.size() == old(self.local_definitions.size()))]
     pub fn check_type(&mut self, term: &Term, expected_type: &Arc<Value>) -> core::Term {
-        match (&term.data, expected_type.force(self.globals)) {
+        match (&term.data, self.force_value(expected_type).as_ref()) {
             (_, Value::Error) => core::Term::new(term.location, core::TermData::Error),
 
-            (TermData::FunctionTerm(input_names, output_term), _) => {
-                let mut seen_input_count = 0;
-                let mut expected_type = expected_type.clone();
-                let mut pending_input_names = input_names.iter();
+            (TermData::Let(name, def_type, def_term, output_term), _) => {
+                let (core_def_term, def_type) = self.synth_def_type(def_type, def_term);
+                let def_value = self.eval_term(&core_def_term);
+
+                self.push_local(Some(&name.data), def_value, def_type);
+                let core_output_term = self.check_type(output_term, expected_type);
+                self.pop_local();
+
+                core::Term::new(
+                    term.location,
+                    core::TermData::Let(
+                        name.data.clone(),
+                        Arc::new(core_def_term),
+                        Arc::new(core_output_term),
+                    ),
+                )
+            }
+
+            (TermData::Hole(name), _) => {
+                self.report_hole(term.location, name, expected_type);
+                let (core_term, _) = self.fresh_meta(term.location, MetaSource::Hole);
+                core_term
+            }
+
+            // The expected type is not yet known, so we defer to synthesis.
+            (_, Value::Stuck(semantics::Head::Meta(_), _)) => {
+                self.check_type_by_synth(term, expected_type)
+            }
+
+            // Implicit inputs are inserted, unless they are bound explicitly
+            // by a function term.
+            (
+                _,
+                Value::FunctionType(
+                    Plicity::Implicit,
+                    _,
+                    input_name_hint,
+                    input_type,
+                    output_closure,
+                ),
+            ) if !binds_implicit_input(term) => {
+                let input_value = self.push_local_param(None, input_type.clone());
+                let output_type = output_closure.apply(self.globals, input_value);
+                let core_output_term = self.check_type(term, &output_type);
+                self.pop_local();
+
+                let input_name = input_name_hint.clone().unwrap_or_else(|| "_".to_owned());
+                core::Term::new(
+                    term.location,
+                    core::TermData::FunctionTerm(input_name, Arc::new(core_output_term)),
+                )
+            }
 
-                while let Some(input_name) = pending_input_names.next() {
-                    match expected_type.force(self.globals) {
-                        Value::FunctionType(_, input_type, output_closure) => {
+            (TermData::FunctionTerm(input_name_groups, output_term), _) => {
+                let mut core_input_names = Vec::new();
+                let mut named_inputs = Vec::new();
+                let mut expected_type = expected_type.clone();
+                let mut pending_input_names = (input_name_groups.iter())
+                    .flat_map(|(plicity, input_names, input_type)| {
+                        input_names
+                            .iter()
+                            .map(move |n| (*plicity, n, input_type.as_ref()))
+                    })
+                    .peekable();
+
+                while let Some((plicity, input_name, input_type_term)) =
+                    pending_input_names.peek().copied()
+                {
+                    match self.force_value(&expected_type).as_ref() {
+                        Value::FunctionType(
+                            input_plicity,
+                            usage,
+                            _,
+                            input_type,
+                            output_closure,
+                        ) if *input_plicity == plicity => {
+                            pending_input_names.next();
+                            if let Some(input_type_term) = input_type_term {
+                                self.check_input_annotation(input_type_term, input_type);
+                            }
+                            named_inputs.push((self.local_uses.len(), *usage, input_name));
                             let input_value =
                                 self.push_local_param(Some(&input_name.data), input_type.clone());
-                            seen_input_count += 1;
+                            core_input_names.push((input_name.location, input_name.data.clone()));
+                            expected_type = output_closure.apply(self.globals, input_value);
+                        }
+                        // Implicit inputs are inserted before the next explicit input.
+                        Value::FunctionType(
+                            Plicity::Implicit,
+                            _,
+                            input_name_hint,
+                            input_type,
+                            output_closure,
+                        ) => {
+                            let input_value = self.push_local_param(None, input_type.clone());
+                            let core_input_name =
+                                input_name_hint.clone().unwrap_or_else(|| "_".to_owned());
+                            core_input_names.push((input_name.location, core_input_name));
                             expected_type = output_closure.apply(self.globals, input_value);
                         }
+                        Value::FunctionType(Plicity::Explicit, _, _, _, _) => {
+                            self.report(SurfaceToCoreMessage::UnexpectedImplicitInput {
+                                input_location: input_name.location,
+                            });
+                            let mut input_count = core_input_names.len();
+                            for (_, input_name, _) in pending_input_names {
+                                let input_type = Arc::new(Value::Error);
+                                self.push_local_param(Some(&input_name.data), input_type);
+                                input_count += 1;
+                            }
+                            self.check_type(output_term, &Arc::new(Value::Error));
+                            self.pop_many_locals(input_count);
+                            return core::Term::new(term.location, core::TermData::Error);
+                        }
                         Value::Error => {
-                            self.pop_many_locals(seen_input_count);
+                            self.pop_many_locals(core_input_names.len());
                             return core::Term::new(term.location, core::TermData::Error);
                         }
                         _ => {
                             self.report(SurfaceToCoreMessage::TooManyInputsInFunctionTerm {
-                                unexpected_inputs: std::iter::once(input_name.location)
-                                    .chain(
-                                        pending_input_names.map(|input_name| input_name.location),
-                                    )
+                                unexpected_inputs: pending_input_names
+                                    .map(|(_, input_name, _)| input_name.location)
                                     .collect(),
                             });
                             self.check_type(output_term, &expected_type);
-                            self.pop_many_locals(seen_input_count);
+                            self.pop_many_locals(core_input_names.len());
                             return core::Term::new(term.location, core::TermData::Error);
                         }
                     }
                 }
 
                 let core_output_term = self.check_type(output_term, &expected_type);
-                self.pop_many_locals(seen_input_count);
-                (input_names.iter().rev()).fold(core_output_term, |core_output_term, input_name| {
-                    core::Term::new(
-                        Location::merge(input_name.location, core_output_term.location),
-                        core::TermData::FunctionTerm(
-                            input_name.data.clone(),
-                            Arc::new(core_output_term),
-                        ),
-                    )
-                })
+                for (position, usage, input_name) in named_inputs {
+                    self.check_local_usage(position, usage, input_name);
+                }
+                self.pop_many_locals(core_input_names.len());
+                (core_input_names.into_iter().rev()).fold(
+                    core_output_term,
+                    |core_output_term, (input_location, input_name)| {
+                        core::Term::new(
+                            Location::merge(input_location, core_output_term.location),
+                            core::TermData::FunctionTerm(input_name, Arc::new(core_output_term)),
+                        )
+                    },
+                )
+            }
+
+            (TermData::Fix(name, fix_term), _) => {
+                let core_fix_type = self.read_back_value(expected_type);
+                self.push_local_param(Some(&name.data), expected_type.clone());
+                let core_fix_term =
+                    self.with_usage(Usage::Many, |this| this.check_type(fix_term, expected_type));
+                self.pop_local();
+
+                core::Term::new(
+                    term.location,
+                    core::TermData::Fix(
+                        name.data.clone(),
+                        Arc::new(core_fix_type),
+                        Arc::new(core_fix_term),
+                    ),
+                )
             }
 
             (TermData::RecordTerm(term_entries), Value::RecordType(closure)) => {
-                let mut pending_term_entries = term_entries.iter();
                 let mut missing_labels = Vec::new();
-                let mut unexpected_labels = Vec::new();
-
+                let mut dependency_cycles = Vec::new();
+                // The source positions of the entries that were reported as
+                // part of a dependency cycle, or that depend on those entries.
+                let mut cyclic_positions = Vec::new();
+
+                // The source positions of the entries that have been checked,
+                // in the order that they were given in the type. Entries that
+                // were filled in from their defaults have no source position.
+                let mut checked_positions = Vec::with_capacity(term_entries.len());
                 let mut core_term_entries = Vec::with_capacity(term_entries.len());
-
-                closure.for_each_entry(self.globals, |label, entry_type| loop {
-                    match pending_term_entries.next() {
-                        Some((next_label, next_name, entry_term)) if next_label.data == label => {
-                            let next_name = next_name.as_ref().unwrap_or(next_label);
-                            let core_entry_term = self.check_type(entry_term, &entry_type);
-                            let core_entry_value = self.eval_term(&core_entry_term);
-
-                            self.push_local(
-                                Some(&next_name.data),
-                                core_entry_value.clone(),
-                                entry_type,
+                let mut entry_values = Vec::with_capacity(term_entries.len());
+                let locals_start = self.local_levels.len();
+
+                closure.for_each_entry(self.globals, |label, entry_type| {
+                    let position = (term_entries.iter())
+                        .position(|(entry_label, _, _)| entry_label.data == label);
+                    let type_entry = closure.entries().get(entry_values.len());
+                    let position = match (position, type_entry) {
+                        (Some(position), _) => position,
+                        (None, Some((_, _, _, _, Some(default_term)))) => {
+                            let entry_value = closure.eval_with_entries(
+                                self.globals,
+                                entry_values.iter().cloned(),
+                                default_term,
                             );
+                            let core_entry_term = self.read_back_value(&entry_value);
+
+                            self.push_local(None, entry_value.clone(), entry_type);
+                            checked_positions.push(None);
                             core_term_entries.push((label.to_owned(), Arc::new(core_entry_term)));
+                            entry_values.push(entry_value.clone());
 
-                            return core_entry_value;
+                            return entry_value;
                         }
-                        Some((next_label, _, _)) => unexpected_labels.push(next_label.location),
-                        None => {
+                        (None, _) => {
                             missing_labels.push(label.to_owned());
+                            entry_values.push(Arc::new(Value::Error));
                             return Arc::new(Value::Error);
                         }
-                    }
+                    };
+                    let (entry_label, entry_name, entry_term) = &term_entries[position];
+                    let entry_name = entry_name.as_ref().unwrap_or(entry_label);
+
+                    // Entries may refer to the entries that precede them in the
+                    // source, so those entries must already have been checked.
+                    let pending_dependency = (term_entries[..position].iter().enumerate())
+                        .filter(|(position, _)| !checked_positions.contains(&Some(*position)))
+                        .find(|(_, (dependency_label, dependency_name, _))| {
+                            let dependency_name = dependency_name.as_ref();
+                            let dependency_name = dependency_name.unwrap_or(dependency_label);
+                            entry_term.has_free_name(&dependency_name.data)
+                        });
+
+                    let core_entry_term = match pending_dependency {
+                        Some((_, (dependency_label, _, _))) => {
+                            cyclic_positions.push(position);
+                            dependency_cycles.push((
+                                entry_label.data.clone(),
+                                entry_label.location,
+                                dependency_label.data.clone(),
+                                dependency_label.location,
+                            ));
+                            core::Term::new(entry_term.location, core::TermData::Error)
+                        }
+                        None => {
+                            // Entries that depend on a reported cycle are also
+                            // part of that cycle.
+                            let depends_on_cycle = cyclic_positions.iter().any(|cyclic_position| {
+                                let (dependency_label, dependency_name, _) =
+                                    &term_entries[*cyclic_position];
+                                let dependency_name = dependency_name.as_ref();
+                                let dependency_name = dependency_name.unwrap_or(dependency_label);
+                                entry_term.has_free_name(&dependency_name.data)
+                            });
+                            if depends_on_cycle {
+                                cyclic_positions.push(position);
+                            }
+
+                            // Hide the entries that follow this entry in the
+                            // source, but that were checked before it. Entries
+                            // that are part of a reported cycle stay visible,
+                            // to avoid reporting the cycle a second time.
+                            let mut hidden_names = Vec::new();
+                            for (index, checked_position) in checked_positions.iter().enumerate() {
+                                if matches!(checked_position, Some(checked_position) if *checked_position > position && !cyclic_positions.contains(checked_position)) {
+                                    let (name, _) = &mut self.local_levels[locals_start + index];
+                                    hidden_names.push((index, name.take()));
+                                }
+                            }
+
+                            let usage = type_entry.map_or(Usage::Many, |(_, _, usage, _, _)| *usage);
+                            let core_entry_term = self.with_usage(usage, |this| {
+                                this.check_type(entry_term, &entry_type)
+                            });
+
+                            for (index, name) in hidden_names {
+                                self.local_levels[locals_start + index].0 = name;
+                            }
+
+                            core_entry_term
+                        }
+                    };
+                    let core_entry_value = self.eval_term(&core_entry_term);
+
+                    self.push_local(Some(&entry_name.data), core_entry_value.clone(), entry_type);
+                    checked_positions.push(Some(position));
+                    core_term_entries.push((label.to_owned(), Arc::new(core_entry_term)));
+                    entry_values.push(core_entry_value.clone());
+
+                    core_entry_value
                 });
 
                 self.pop_many_locals(core_term_entries.len());
-                unexpected_labels.extend(pending_term_entries.map(|(label, _, _)| label.location));
+                let unexpected_labels = (term_entries.iter().enumerate())
+                    .filter(|(position, _)| !checked_positions.contains(&Some(*position)))
+                    .map(|(_, (label, _, _))| label.location)
+                    .collect::<Vec<_>>();
 
                 if !missing_labels.is_empty() || !unexpected_labels.is_empty() {
                     self.report(SurfaceToCoreMessage::InvalidRecordTerm {
@@ -304,6 +661,16 @@ impl<'me> State<'me> {
                         unexpected_labels,
                     });
                 }
+                for (label, label_location, dependency_label, dependency_location) in
+                    dependency_cycles
+                {
+                    self.report(SurfaceToCoreMessage::CyclicRecordEntryDependency {
+                        label,
+                        label_location,
+                        dependency_label,
+                        dependency_location,
+                    });
+                }
 
                 core::Term::new(
                     term.location,
@@ -311,6 +678,183 @@ impl<'me> State<'me> {
                 )
             }
 
+            (TermData::EnumElim(head_term, branches), _) => {
+                match self.synth_match_head(head_term, branches, term.location) {
+                    Some((core_head_term, MatchBranches::Enum(branches))) => {
+                        let core_branches =
+                            self.with_alternatives(branches.len(), |this, index| {
+                                let (label, _, branch_term) = branches[index];
+                                let core_branch_term = this.check_type(branch_term, expected_type);
+                                (label.data.clone(), Arc::new(core_branch_term))
+                            });
+
+                        core::Term::new(
+                            term.location,
+                            core::TermData::EnumElim(
+                                Arc::new(core_head_term),
+                                core_branches.into(),
+                            ),
+                        )
+                    }
+                    Some((core_head_term, MatchBranches::Variant(branches))) => {
+                        let core_branches =
+                            self.with_alternatives(branches.len(), |this, index| {
+                                let ((label, names, branch_term), payload_type) = &branches[index];
+                                let name = names.first().map(|name| name.data.as_str());
+                                this.push_local_param(name, payload_type.clone());
+                                let core_branch_term = this.check_type(branch_term, expected_type);
+                                this.pop_local();
+
+                                let name = name.map(str::to_owned);
+                                (label.data.clone(), name, Arc::new(core_branch_term))
+                            });
+
+                        core::Term::new(
+                            term.location,
+                            core::TermData::VariantElim(
+                                Arc::new(core_head_term),
+                                core_branches.into(),
+                            ),
+                        )
+                    }
+                    Some((
+                        core_head_term,
+                        MatchBranches::Inductive(head_type, closure, branches),
+                    )) => {
+                        // Match expressions are not dependent, so the motive
+                        // ignores the term being matched on.
+                        self.push_local_param(None, head_type.clone());
+                        let output_type = self.read_back_value(expected_type);
+                        self.pop_local();
+                        let core_motive = core::Term::generated(core::TermData::FunctionTerm(
+                            "_".to_owned(),
+                            Arc::new(output_type),
+                        ));
+                        let motive_value = self.eval_term(&core_motive);
+                        let core_branches = self.check_inductive_branches(
+                            &head_type,
+                            &closure,
+                            &motive_value,
+                            branches,
+                        );
+
+                        core::Term::new(
+                            term.location,
+                            core::TermData::InductiveElim(
+                                Arc::new(core_head_term),
+                                Arc::new(core_motive),
+                                core_branches.into(),
+                            ),
+                        )
+                    }
+                    None => {
+                        for (_, names, branch_term) in branches {
+                            for name in names {
+                                self.push_local_param(Some(&name.data), Arc::new(Value::Error));
+                            }
+                            self.check_type(branch_term, expected_type);
+                            self.pop_many_locals(names.len());
+                        }
+                        core::Term::new(term.location, core::TermData::Error)
+                    }
+                }
+            }
+            (TermData::EnumTerm(label), Value::EnumType(labels)) => {
+                if labels.contains(&label.data) {
+                    core::Term::new(term.location, core::TermData::EnumTerm(label.data.clone()))
+                } else {
+                    let expected_type = self.read_back_to_surface_term(expected_type);
+                    self.report(SurfaceToCoreMessage::InvalidEnumTerm {
+                        label_location: label.location,
+                        label: label.data.clone(),
+                        expected_type,
+                    });
+                    core::Term::new(term.location, core::TermData::Error)
+                }
+            }
+            (TermData::VariantTerm(label, payload_term), Value::VariantType(type_entries)) => {
+                match type_entries.iter().find(|(l, _)| *l == label.data) {
+                    Some((_, payload_type)) => {
+                        let core_term = self.check_type(payload_term, payload_type);
+                        core::Term::new(
+                            term.location,
+                            core::TermData::VariantTerm(label.data.clone(), Arc::new(core_term)),
+                        )
+                    }
+                    None => {
+                        let expected_type = self.read_back_to_surface_term(expected_type);
+                        self.report(SurfaceToCoreMessage::InvalidVariantTerm {
+                            label_location: label.location,
+                            label: label.data.clone(),
+                            expected_type,
+                        });
+                        core::Term::new(term.location, core::TermData::Error)
+                    }
+                }
+            }
+
+            // The branches are checked against the same expected type, because
+            // if-then-else expressions do not have a dependent motive.
+            (TermData::BoolElim(head_term, if_true, if_false), _) => {
+                let bool_type = Arc::new(Value::global("Bool", 0, []));
+                let core_head_term = self.check_type(head_term, &bool_type);
+                let mut core_branches = self.with_alternatives(2, |this, index| {
+                    let branch_term = if index == 0 { if_true } else { if_false };
+                    this.check_type(branch_term, expected_type)
+                });
+                let core_if_false = core_branches.pop().unwrap();
+                let core_if_true = core_branches.pop().unwrap();
+
+                core::Term::new(
+                    term.location,
+                    core::TermData::BoolElim(
+                        Arc::new(core_head_term),
+                        Arc::new(core_if_true),
+                        Arc::new(core_if_false),
+                    ),
+                )
+            }
+
+            (TermData::EqTerm, Value::EqType(_, lhs, rhs)) if self.is_equal(lhs, rhs) => {
+                core::Term::new(term.location, core::TermData::EqTerm)
+            }
+            (TermData::EqTerm, _) => {
+                let expected_type = self.read_back_to_surface_term(expected_type);
+                self.report(SurfaceToCoreMessage::InvalidEqTerm {
+                    location: term.location,
+                    expected_type,
+                });
+                core::Term::new(term.location, core::TermData::Error)
+            }
+
+            (TermData::CodeTerm(code_term), Value::CodeType(r#type)) => match self.stage {
+                0 => {
+                    self.report(SurfaceToCoreMessage::UnexpectedCodeStage {
+                        location: term.location,
+                    });
+                    core::Term::new(term.location, core::TermData::Error)
+                }
+                stage => {
+                    let previous_stage = std::mem::replace(&mut self.stage, stage - 1);
+                    let core_code_term = self.check_type(code_term, r#type);
+                    self.stage = previous_stage;
+                    core::Term::new(
+                        term.location,
+                        core::TermData::CodeTerm(Arc::new(core_code_term)),
+                    )
+                }
+            },
+            (TermData::CodeElim(head_term), _) => {
+                let code_type = Arc::new(Value::CodeType(expected_type.clone()));
+                self.stage += 1;
+                let core_head_term = self.check_type(head_term, &code_type);
+                self.stage -= 1;
+                core::Term::new(
+                    term.location,
+                    core::TermData::CodeElim(Arc::new(core_head_term)),
+                )
+            }
+
             (TermData::SequenceTerm(entry_terms), forced_type) => match forced_type.try_global() {
                 Some(("Array", _, [Elim::Function(len), Elim::Function(core_entry_type)])) => {
                     let core_entry_type = core_entry_type.force(self.globals);
@@ -320,7 +864,7 @@ impl<'me> State<'me> {
                         .collect();
 
                     let len = len.force(self.globals);
-                    match len.as_ref() {
+                    match len.force(self.globals) {
                         Value::Constant(core::Constant::U32(len))
                             if *len as usize == entry_terms.len() =>
                         {
@@ -359,6 +903,10 @@ impl<'me> State<'me> {
                     core::Term::new(term.location, core::TermData::Error)
                 }
             },
+            // Suffixed literals have a known type, so we defer to synthesis.
+            (TermData::NumberTerm(data), _) if literal::split_number_suffix(data).1.is_some() => {
+                self.check_type_by_synth(term, expected_type)
+            }
             (TermData::NumberTerm(data), forced_type) => {
                 use crate::lang::core::Constant::*;
 
@@ -406,24 +954,275 @@ impl<'me> State<'me> {
                 }
             },
 
-            (_, _) => match self.synth_type(term) {
-                (term, found_type) if self.is_subtype(&found_type, expected_type) => term,
-                (_, found_type) => {
-                    let found_type = self.read_back_to_surface_term(&found_type);
-                    let expected_type = self.read_back_to_surface_term(expected_type);
-                    self.report(SurfaceToCoreMessage::MismatchedTypes {
-                        location: term.location,
-                        found_type,
-                        expected_type: ExpectedType::Type(expected_type),
-                    });
-                    core::Term::new(term.location, core::TermData::Error)
+            (_, _) => self.check_type_by_synth(term, expected_type),
+        }
+    }
+
+    /// Report the expected type of a hole, along with the names and types of
+    /// the local variables that are in scope.
+    fn report_hole(&mut self, location: Location, name: &str, expected_type: &Arc<Value>) {
+        let local_size = self.local_definitions.size();
+        let mut local_types = Vec::new();
+        for (position, (local_name, level)) in self.local_levels.iter().enumerate() {
+            let local_name = match local_name {
+                Some(local_name) => local_name,
+                None => continue,
+            };
+            // Skip locals that are shadowed by later locals.
+            if (self.local_levels[position + 1..].iter())
+                .any(|(n, _)| n.as_ref() == Some(local_name))
+            {
+                continue;
+            }
+            let index = level.to_index(local_size).unwrap(); // TODO: Handle overflow
+            if let Some(r#type) = self.local_declarations.get(index) {
+                local_types.push((local_name.clone(), r#type.clone()));
+            }
+        }
+
+        let mut locals = Vec::with_capacity(local_types.len());
+        for (local_name, r#type) in local_types {
+            locals.push((local_name, self.read_back_to_surface_term(&r#type)));
+        }
+        let expected_type = self.read_back_to_surface_term(expected_type);
+
+        self.report(SurfaceToCoreMessage::HoleGoal {
+            location,
+            name: name.to_owned(),
+            expected_type,
+            locals,
+        });
+    }
+
+    /// Check that the type annotation on a function term input is equal to
+    /// the expected input type.
+    fn check_input_annotation(&mut self, input_type_term: &Term, expected_type: &Arc<Value>) {
+        let (core_input_type, _) =
+            self.with_usage(Usage::Zero, |this| this.is_type(input_type_term));
+        let input_type = self.eval_term(&core_input_type);
+
+        if !self.is_equal(&input_type, expected_type) {
+            let found_type = self.read_back_to_surface_term(&input_type);
+            let expected_type = self.read_back_to_surface_term(expected_type);
+            self.report(SurfaceToCoreMessage::MismatchedTypes {
+                location: input_type_term.location,
+                found_type,
+                expected_type: ExpectedType::Type(expected_type),
+            });
+        }
+    }
+
+    /// Check that a term is an element of a type by synthesizing its type,
+    /// and then comparing it with the expected type.
+    fn check_type_by_synth(&mut self, term: &Term, expected_type: &Arc<Value>) -> core::Term {
+        let (core_term, found_type) = self.synth_type(term);
+        let mut implicit_inputs = Vec::new();
+        let (core_term, found_type) = self.insert_implicit_inputs(
+            term.location,
+            None,
+            core_term,
+            found_type,
+            &mut implicit_inputs,
+        );
+        let core_term = self.coerce(core_term, &found_type, expected_type);
+        self.count_implicit_input_uses(implicit_inputs);
+
+        if let Some(core_term) = core_term {
+            core_term
+        } else {
+            let found_type = self.read_back_to_surface_term(&found_type);
+            let expected_type = self.read_back_to_surface_term(expected_type);
+            self.report(SurfaceToCoreMessage::MismatchedTypes {
+                location: term.location,
+                found_type,
+                expected_type: ExpectedType::Type(expected_type),
+            });
+            core::Term::new(term.location, core::TermData::Error)
+        }
+    }
+
+    /// Coerce a term to a supertype of its type, returning `None` if the
+    /// expected type is not a supertype of the found type.
+    ///
+    /// Records are projected to the entries of the expected record type when
+    /// they contain extra entries.
+    fn coerce(
+        &mut self,
+        core_term: core::Term,
+        found_type: &Arc<Value>,
+        expected_type: &Arc<Value>,
+    ) -> Option<core::Term> {
+        if !self.needs_projection(found_type, expected_type) {
+            if self.is_subtype(found_type, expected_type) {
+                return Some(core_term);
+            } else {
+                return None;
+            }
+        }
+
+        let found_type = self.force_value(found_type);
+        let expected_type = self.force_value(expected_type);
+        let (closure0, closure1) = match (found_type.as_ref(), expected_type.as_ref()) {
+            (Value::RecordType(closure0), Value::RecordType(closure1)) => (closure0, closure1),
+            _ => return None,
+        };
+
+        // The record is bound to a local, so that the entries of the expected
+        // type can be projected from it.
+        let head_value = self.eval_term(&core_term);
+        self.push_local(None, head_value.clone(), found_type.clone());
+
+        let mut core_term_entries = Vec::new();
+        let mut is_subtype = true;
+
+        closure1.for_each_entry(self.globals, |label, entry_type1| {
+            let head_index = core::LocalIndex(core_term_entries.len() as u32);
+            let head = core::Term::generated(core::TermData::Local(head_index));
+            let core_entry_term =
+                core::Term::generated(core::TermData::RecordElim(Arc::new(head), label.to_owned()));
+
+            let core_entry_term = (self.record_elim_type(head_value.clone(), label, closure0))
+                .and_then(|entry_type0| self.coerce(core_entry_term, &entry_type0, &entry_type1))
+                .unwrap_or_else(|| {
+                    is_subtype = false;
+                    core::Term::generated(core::TermData::Error)
+                });
+            let core_entry_value = self.eval_term(&core_entry_term);
+
+            self.push_local(None, core_entry_value.clone(), entry_type1);
+            core_term_entries.push((label.to_owned(), Arc::new(core_entry_term)));
+
+            core_entry_value
+        });
+
+        self.pop_many_locals(core_term_entries.len() + 1);
+
+        if !is_subtype {
+            return None;
+        }
+
+        Some(core::Term::new(
+            core_term.location,
+            core::TermData::Let(
+                "head".to_owned(),
+                Arc::new(core_term),
+                Arc::new(core::Term::generated(core::TermData::RecordTerm(
+                    core_term_entries.into(),
+                ))),
+            ),
+        ))
+    }
+
+    /// Returns `true` if a term of the found type needs to be projected to
+    /// the entries of the expected type, at any depth.
+    fn needs_projection(&self, found_type: &Arc<Value>, expected_type: &Arc<Value>) -> bool {
+        match (
+            self.force_value(found_type).as_ref(),
+            self.force_value(expected_type).as_ref(),
+        ) {
+            (Value::RecordType(closure0), Value::RecordType(closure1)) => {
+                let mut needs_projection = !Iterator::eq(closure0.labels(), closure1.labels());
+                let is_subset = semantics::compare_record_subtype_entries(
+                    self.globals,
+                    self.local_definitions.size(),
+                    closure0,
+                    closure1,
+                    |_, entry_type0, entry_type1| {
+                        needs_projection |= self.needs_projection(entry_type0, entry_type1);
+                        true
+                    },
+                );
+
+                is_subset && needs_projection
+            }
+            (_, _) => false,
+        }
+    }
+
+    /// Returns `true` if the implicit inputs at the start of a function type
+    /// include an input with the given name.
+    fn has_implicit_input(&self, r#type: &Arc<Value>, name: &str) -> bool {
+        let mut local_size = self.local_definitions.size();
+        let mut r#type = r#type.clone();
+        while let Value::FunctionType(Plicity::Implicit, _, input_name_hint, _, output_closure) =
+            self.force_value(&r#type).as_ref()
+        {
+            if input_name_hint.as_deref() == Some(name) {
+                return true;
+            }
+            let input = Arc::new(Value::local(local_size.next_level(), []));
+            r#type = output_closure.apply(self.globals, input);
+            local_size = local_size.increment();
+        }
+        false
+    }
+
+    /// Apply a term to fresh metavariables for each of the implicit inputs at
+    /// the start of its type, stopping early at the implicit input with the
+    /// given name.
+    ///
+    /// The metavariables inserted for inputs that are used at runtime are
+    /// pushed onto `implicit_inputs`, so that the uses of local variables in
+    /// their solutions can be counted once they have been solved.
+    fn insert_implicit_inputs(
+        &mut self,
+        location: Location,
+        name: Option<&str>,
+        mut core_term: core::Term,
+        mut r#type: Arc<Value>,
+        implicit_inputs: &mut Vec<(Usage, core::Term)>,
+    ) -> (core::Term, Arc<Value>) {
+        while let Value::FunctionType(
+            Plicity::Implicit,
+            usage,
+            input_name_hint,
+            _,
+            output_closure,
+        ) = self.force_value(&r#type).as_ref()
+        {
+            if name.is_some() && input_name_hint.as_deref() == name {
+                break;
+            }
+
+            let source = MetaSource::ImplicitInput(input_name_hint.clone());
+            let (core_input, input_value) = self.fresh_meta(location, source);
+            if *usage != Usage::Zero {
+                implicit_inputs.push((*usage, core_input.clone()));
+            }
+            core_term = core::Term::new(
+                location,
+                core::TermData::FunctionElim(Arc::new(core_term), Arc::new(core_input)),
+            );
+            r#type = output_closure.apply(self.globals, input_value);
+        }
+
+        (core_term, r#type)
+    }
+
+    /// Count the uses of local variables in the solutions of metavariables
+    /// that were inserted for implicit inputs, scaled by the usage of each
+    /// input.
+    ///
+    /// The number of occurrences in a solution is not tracked, so any local
+    /// variable that occurs is counted as being used many times. Metavariables
+    /// that are still unsolved are assumed to use every local variable that
+    /// they depend on.
+    fn count_implicit_input_uses(&mut self, implicit_inputs: Vec<(Usage, core::Term)>) {
+        for (usage, core_input) in implicit_inputs {
+            let core_input = self.zonk_term(&core_input);
+            let local_count = self.local_uses.len();
+            for (position, local_use) in self.local_uses.iter_mut().enumerate() {
+                let index = core::LocalIndex((local_count - 1 - position) as u32);
+                if core_input.has_free_local(index) {
+                    *local_use = *local_use + usage * Usage::Many;
                 }
-            },
+            }
         }
     }
 
     /// Synthesize the type of a surface term, and return the elaborated term.
     #[debug_ensures(self.universe_offset == old(self.universe_offset))]
+    #[debug_ensures(self.stage == old(self.stage))]
     #[debug_ensures(self.local_levels.len() == old(self.local_levels.len()))]
     #[debug_ensures(self.local_declarations.size() == old(self.local_declarations.size()))]
     #[debug_ensures(self.local_definitions.size() == old(self.local_definitions.size()))]
@@ -435,8 +1234,21 @@ impl<'me> State<'me> {
         match &term.data {
             TermData::Name(name) => {
                 if let Some((index, r#type)) = self.get_local(name.as_ref()) {
+                    let r#type = r#type.clone();
+                    let position = self.local_uses.len() - 1 - index.0 as usize;
+                    self.local_uses[position] = self.local_uses[position] + Usage::One;
+                    let local_stage = *self.local_stages.get(index).unwrap();
+                    if local_stage < self.stage {
+                        self.report(SurfaceToCoreMessage::UnexpectedLocalStage {
+                            location: term.location,
+                            name: name.clone(),
+                            local_stage,
+                            current_stage: self.stage,
+                        });
+                        return (error_term(), Arc::new(Value::Error));
+                    }
                     let core_term = core::Term::new(term.location, core::TermData::Local(index));
-                    return (core_term, r#type.clone());
+                    return (core_term, r#type);
                 }
 
                 if let Some((r#type, _)) = self.globals.get(name.as_ref()) {
@@ -458,8 +1270,38 @@ impl<'me> State<'me> {
                 (error_term(), Arc::new(Value::Error))
             }
 
+            TermData::Hole(_) => {
+                self.report(SurfaceToCoreMessage::AmbiguousTerm {
+                    location: term.location,
+                    term: AmbiguousTerm::Hole,
+                });
+                (error_term(), Arc::new(Value::Error))
+            }
+
+            TermData::TypeOmega => {
+                let type_omega = core::Term::new(
+                    term.location,
+                    core::TermData::TypeType(core::UniverseLevel::Omega(0)),
+                );
+                let core_term = match self.universe_offset {
+                    core::UniverseOffset(0) => type_omega,
+                    offset => {
+                        core::Term::generated(core::TermData::Lift(Arc::new(type_omega), offset))
+                    }
+                };
+                match core::UniverseLevel::Omega(1) + self.universe_offset {
+                    Some(level) => (core_term, Arc::new(Value::type_type(level))),
+                    None => {
+                        self.report(SurfaceToCoreMessage::MaximumUniverseLevelReached {
+                            location: term.location,
+                        });
+                        (error_term(), Arc::new(Value::Error))
+                    }
+                }
+            }
+
             TermData::Ann(term, r#type) => {
-                let (core_type, _) = self.is_type(r#type);
+                let (core_type, _) = self.with_usage(Usage::Zero, |this| this.is_type(r#type));
                 let core_type_value = self.eval_term(&core_type);
                 let core_term = self.check_type(term, &core_type_value);
                 (
@@ -471,17 +1313,63 @@ impl<'me> State<'me> {
                 )
             }
 
-            TermData::Lift(inner_term, offset) => {
-                match self.universe_offset + core::UniverseOffset(*offset) {
-                    Some(new_offset) => {
-                        let old_offset = std::mem::replace(&mut self.universe_offset, new_offset);
-                        let (core_term, r#type) = self.synth_type(inner_term);
-                        self.universe_offset = old_offset;
-                        (core_term, r#type)
+            TermData::Import(data) => {
+                let path = literal::State::new(term.location, data, &self.message_tx)
+                    .quoted_to_utf8_string();
+                let path = match path {
+                    Some(path) => self.resolve_import_path(term.location, &path),
+                    None => return (error_term(), Arc::new(Value::Error)),
+                };
+
+                match self.import_file(term.location, &path) {
+                    Some((core_term, core_type)) => {
+                        let core_term = core::Term::new(
+                            term.location,
+                            core::TermData::Ann(core_term, core_type.clone()),
+                        );
+                        let core_term = match self.universe_offset {
+                            core::UniverseOffset(0) => core_term,
+                            offset => core::Term::generated(core::TermData::Lift(
+                                Arc::new(core_term),
+                                offset,
+                            )),
+                        };
+                        (core_term, self.eval_term(&core_type))
                     }
-                    None => {
-                        self.report(SurfaceToCoreMessage::MaximumUniverseLevelReached {
-                            location: term.location,
+                    None => (error_term(), Arc::new(Value::Error)),
+                }
+            }
+
+            TermData::Let(name, def_type, def_term, output_term) => {
+                let (core_def_term, def_type) = self.synth_def_type(def_type, def_term);
+                let def_value = self.eval_term(&core_def_term);
+
+                self.push_local(Some(&name.data), def_value, def_type);
+                let (core_output_term, output_type) = self.synth_type(output_term);
+                self.pop_local();
+
+                let core_term = core::Term::new(
+                    term.location,
+                    core::TermData::Let(
+                        name.data.clone(),
+                        Arc::new(core_def_term),
+                        Arc::new(core_output_term),
+                    ),
+                );
+                (core_term, output_type)
+            }
+
+            TermData::Lift(inner_term, offset) => {
+                match self.universe_offset + core::UniverseOffset(*offset) {
+                    Some(new_offset) => {
+                        let old_offset = std::mem::replace(&mut self.universe_offset, new_offset);
+                        let (core_term, r#type) = self.synth_type(inner_term);
+                        self.universe_offset = old_offset;
+                        (core_term, r#type)
+                    }
+                    None => {
+                        self.report(SurfaceToCoreMessage::MaximumUniverseLevelReached {
+                            location: term.location,
                         });
                         (error_term(), Arc::new(Value::Error))
                     }
@@ -489,21 +1377,21 @@ impl<'me> State<'me> {
             }
 
             TermData::FunctionType(input_type_groups, output_type) => {
-                let mut max_level = Some(core::UniverseLevel(0));
+                let mut max_level = Some(core::UniverseLevel::Finite(0));
                 let update_level = |max_level, next_level| match (max_level, next_level) {
                     (Some(max_level), Some(pl)) => Some(std::cmp::max(max_level, pl)),
                     (None, _) | (_, None) => None,
                 };
                 let mut core_inputs = Vec::new();
 
-                for (input_names, input_type) in input_type_groups {
+                for (plicity, usage, input_names, input_type) in input_type_groups {
                     for input_name in input_names {
                         let (core_input_type, input_level) = self.is_type(input_type);
                         max_level = update_level(max_level, input_level);
 
                         let core_input_type_value = self.eval_term(&core_input_type);
                         self.push_local_param(Some(&input_name.data), core_input_type_value);
-                        core_inputs.push((input_name.clone(), core_input_type));
+                        core_inputs.push((*plicity, *usage, input_name.clone(), core_input_type));
                     }
                 }
 
@@ -516,10 +1404,14 @@ impl<'me> State<'me> {
                     None => (error_term(), Arc::new(Value::Error)),
                     Some(max_level) => {
                         let mut core_type = core_output_type;
-                        for (input_name, input_type) in core_inputs.into_iter().rev() {
+                        for (plicity, usage, input_name, input_type) in
+                            core_inputs.into_iter().rev()
+                        {
                             core_type = core::Term::new(
                                 Location::merge(input_name.location, output_type.location),
                                 core::TermData::FunctionType(
+                                    plicity,
+                                    usage,
                                     Some(input_name.data),
                                     Arc::new(input_type),
                                     Arc::new(core_type),
@@ -547,6 +1439,8 @@ impl<'me> State<'me> {
                         core::Term::new(
                             term.location,
                             core::TermData::FunctionType(
+                                Plicity::Explicit,
+                                Usage::Many,
                                 None,
                                 Arc::new(core_input_type),
                                 Arc::new(core_output_type),
@@ -557,10 +1451,68 @@ impl<'me> State<'me> {
                     (_, _) => (error_term(), Arc::new(Value::Error)),
                 }
             }
-            TermData::FunctionTerm(_, _) => {
+            TermData::FunctionTerm(input_name_groups, output_term) => {
+                let input_name_groups = (input_name_groups.iter())
+                    .map(|(plicity, input_names, input_type)| {
+                        Some((*plicity, input_names, input_type.as_ref()?))
+                    })
+                    .collect::<Option<Vec<_>>>();
+                let input_name_groups = match input_name_groups {
+                    Some(input_name_groups) => input_name_groups,
+                    None => {
+                        self.report(SurfaceToCoreMessage::AmbiguousTerm {
+                            location: term.location,
+                            term: AmbiguousTerm::FunctionTerm,
+                        });
+                        return (error_term(), Arc::new(Value::Error));
+                    }
+                };
+
+                let mut core_inputs = Vec::new();
+                for (plicity, input_names, input_type) in input_name_groups {
+                    for input_name in input_names {
+                        let (core_input_type, _) =
+                            self.with_usage(Usage::Zero, |this| this.is_type(input_type));
+                        let input_type_value = self.eval_term(&core_input_type);
+                        self.push_local_param(Some(&input_name.data), input_type_value);
+                        core_inputs.push((plicity, input_name, core_input_type));
+                    }
+                }
+
+                let (core_output_term, output_type) = self.synth_type(output_term);
+                let core_output_type = self.read_back_value(&output_type);
+                self.pop_many_locals(core_inputs.len());
+
+                let mut core_term = core_output_term;
+                let mut core_type = core_output_type;
+                for (plicity, input_name, core_input_type) in core_inputs.into_iter().rev() {
+                    let location = Location::merge(input_name.location, core_term.location);
+                    core_term = core::Term::new(
+                        location,
+                        core::TermData::FunctionTerm(input_name.data.clone(), Arc::new(core_term)),
+                    );
+                    core_type = core::Term::generated(core::TermData::FunctionType(
+                        plicity,
+                        Usage::Many,
+                        Some(input_name.data.clone()),
+                        Arc::new(core_input_type),
+                        Arc::new(core_type),
+                    ));
+                }
+
+                let r#type = self.eval_term(&core_type);
+                (
+                    core::Term::new(
+                        term.location,
+                        core::TermData::Ann(Arc::new(core_term), Arc::new(core_type)),
+                    ),
+                    r#type,
+                )
+            }
+            TermData::Fix(_, _) => {
                 self.report(SurfaceToCoreMessage::AmbiguousTerm {
                     location: term.location,
-                    term: AmbiguousTerm::FunctionTerm,
+                    term: AmbiguousTerm::Fix,
                 });
                 (error_term(), Arc::new(Value::Error))
             }
@@ -568,12 +1520,41 @@ impl<'me> State<'me> {
                 let mut head_location = head_term.location;
                 let (mut core_head_term, mut head_type) = self.synth_type(head_term);
                 let mut input_terms = input_terms.iter();
+                let mut implicit_inputs = Vec::new();
+
+                while let Some((input_name, input)) = input_terms.next() {
+                    if let Some(input_name) = input_name {
+                        if !self.has_implicit_input(&head_type, &input_name.data) {
+                            if let Value::Error = self.force_value(&head_type).as_ref() {
+                                return (error_term(), Arc::new(Value::Error));
+                            }
+                            let head_type = self.read_back_to_surface_term(&head_type);
+                            self.report(SurfaceToCoreMessage::ImplicitInputNotFound {
+                                head_location: Location::merge(head_term.location, head_location),
+                                head_type,
+                                input_name: input_name.data.clone(),
+                                input_name_location: input_name.location,
+                            });
+                            return (error_term(), Arc::new(Value::Error));
+                        }
+                    }
 
-                while let Some(input) = input_terms.next() {
-                    match head_type.force(self.globals) {
-                        Value::FunctionType(_, input_type, output_closure) => {
+                    let implicit_name = input_name.as_ref().map(|name| name.data.as_str());
+                    let (core_term, r#type) = self.insert_implicit_inputs(
+                        input.location,
+                        implicit_name,
+                        core_head_term,
+                        head_type,
+                        &mut implicit_inputs,
+                    );
+                    core_head_term = core_term;
+                    head_type = r#type;
+
+                    match self.force_value(&head_type).as_ref() {
+                        Value::FunctionType(_, usage, _, input_type, output_closure) => {
                             head_location = input.location;
-                            let core_input = self.check_type(input, &input_type);
+                            let core_input =
+                                self.with_usage(*usage, |this| this.check_type(input, &input_type));
                             let core_input_value = self.eval_term(&core_input);
                             core_head_term = core::Term::new(
                                 Location::merge(head_location, input.location),
@@ -588,7 +1569,7 @@ impl<'me> State<'me> {
                         _ => {
                             let head_type = self.read_back_to_surface_term(&head_type);
                             let unexpected_input_terms =
-                                input_terms.map(|arg| arg.location).collect();
+                                input_terms.map(|(_, input)| input.location).collect();
                             self.report(SurfaceToCoreMessage::TooManyInputsInFunctionElim {
                                 head_location,
                                 head_type,
@@ -599,36 +1580,69 @@ impl<'me> State<'me> {
                     }
                 }
 
+                self.count_implicit_input_uses(implicit_inputs);
                 (core_head_term, head_type)
             }
 
             TermData::RecordTerm(term_entries) => {
-                if term_entries.is_empty() {
-                    (
-                        core::Term::new(term.location, core::TermData::RecordTerm(Arc::new([]))),
-                        Arc::from(Value::RecordType(RecordClosure::new(
-                            self.universe_offset,
-                            self.local_definitions.clone(),
-                            Arc::new([]),
-                        ))),
-                    )
-                } else {
-                    self.report(SurfaceToCoreMessage::AmbiguousTerm {
+                let mut type_size = self.local_definitions.size();
+                let mut core_type_entries = Vec::with_capacity(term_entries.len());
+                let mut core_term_entries = Vec::with_capacity(term_entries.len());
+                let mut unexpected_labels = Vec::new();
+
+                // The types of the entries are synthesized in the order that
+                // they were given in the source. Entries are bound to their
+                // values, so later entries can refer to the earlier entries.
+                for (position, term_entry @ (label, _, _)) in term_entries.iter().enumerate() {
+                    let is_duplicate = (term_entries[..position].iter())
+                        .any(|(seen_label, _, _)| seen_label.data == label.data);
+                    if is_duplicate {
+                        unexpected_labels.push(label.location);
+                        continue;
+                    }
+
+                    let (core_type_entry, core_term_entry) =
+                        self.synth_record_term_entry(type_size, term_entry);
+                    core_type_entries.push(core_type_entry);
+                    core_term_entries.push(core_term_entry);
+                    type_size = type_size.increment();
+                }
+
+                self.pop_many_locals(core_term_entries.len());
+
+                if !unexpected_labels.is_empty() {
+                    self.report(SurfaceToCoreMessage::InvalidRecordTerm {
                         location: term.location,
-                        term: AmbiguousTerm::RecordTerm,
+                        missing_labels: Vec::new(),
+                        unexpected_labels,
                     });
-                    (error_term(), Arc::new(Value::Error))
                 }
+
+                let core_term = core::Term::new(
+                    term.location,
+                    core::TermData::RecordTerm(core_term_entries.into()),
+                );
+                let core_type =
+                    core::Term::generated(core::TermData::RecordType(core_type_entries.into()));
+                let r#type = self.eval_term(&core_type);
+
+                (
+                    core::Term::new(
+                        term.location,
+                        core::TermData::Ann(Arc::new(core_term), Arc::new(core_type)),
+                    ),
+                    r#type,
+                )
             }
             TermData::RecordType(type_entries) => {
                 use std::collections::btree_map::Entry;
 
-                let mut max_level = core::UniverseLevel(0);
+                let mut max_level = core::UniverseLevel::Finite(0);
                 let mut duplicate_labels = Vec::new();
                 let mut seen_labels = BTreeMap::new();
                 let mut core_type_entries = Vec::new();
 
-                for (label, name, entry_type) in type_entries {
+                for (docs, usage, label, name, entry_type, default_term) in type_entries {
                     let name = name.as_ref().unwrap_or(label);
                     match seen_labels.entry(label.data.as_str()) {
                         Entry::Vacant(entry) => {
@@ -642,7 +1656,16 @@ impl<'me> State<'me> {
                             };
                             let core_type = Arc::new(core_type);
                             let core_type_value = self.eval_term(&core_type);
-                            core_type_entries.push((label.data.clone(), core_type));
+                            let core_default_term = (default_term.as_ref()).map(|default_term| {
+                                self.check_type(default_term, &core_type_value)
+                            });
+                            core_type_entries.push((
+                                label.data.clone(),
+                                docs.as_slice().into(),
+                                *usage,
+                                core_type,
+                                core_default_term.map(Arc::new),
+                            ));
                             self.push_local_param(Some(&name.data), core_type_value);
                             entry.insert(label.location);
                         }
@@ -650,7 +1673,11 @@ impl<'me> State<'me> {
                             let seen_range = *entry.get();
                             let current_range = label.location;
                             duplicate_labels.push((label.data.clone(), seen_range, current_range));
-                            self.is_type(entry_type);
+                            let (core_type, _) = self.is_type(entry_type);
+                            if let Some(default_term) = default_term {
+                                let core_type_value = self.eval_term(&core_type);
+                                self.check_type(default_term, &core_type_value);
+                            }
                         }
                     }
                 }
@@ -671,13 +1698,23 @@ impl<'me> State<'me> {
             TermData::RecordElim(head_term, label) => {
                 let (core_head_term, head_type) = self.synth_type(head_term);
 
-                match head_type.force(self.globals) {
+                match self.force_value(&head_type).as_ref() {
                     Value::RecordType(closure) => {
                         let head_value = self.eval_term(&core_head_term);
 
                         if let Some(entry_type) =
                             self.record_elim_type(head_value, &label.data, closure)
                         {
+                            let usage = (closure.entries().iter())
+                                .find(|(entry_label, _, _, _, _)| *entry_label == label.data)
+                                .map(|(_, _, usage, _, _)| *usage);
+                            if usage == Some(Usage::Zero) && !self.erased {
+                                self.report(SurfaceToCoreMessage::UnexpectedErasedLabel {
+                                    label_location: label.location,
+                                    label: label.data.clone(),
+                                });
+                            }
+
                             let core_head_term = Arc::new(core_head_term);
                             let core_term = core::Term::new(
                                 term.location,
@@ -686,6 +1723,18 @@ impl<'me> State<'me> {
                             return (core_term, entry_type);
                         }
                     }
+                    Value::TypeType(_) => {
+                        let head_value = self.eval_term(&core_head_term);
+                        if let Value::InductiveType(closure) =
+                            self.force_value(&head_value).as_ref()
+                        {
+                            if let Some(constructor) =
+                                self.constructor_term(head_value.clone(), &label.data, closure)
+                            {
+                                return constructor;
+                            }
+                        }
+                    }
                     Value::Error => return (error_term(), Arc::new(Value::Error)),
                     _ => {}
                 }
@@ -699,22 +1748,720 @@ impl<'me> State<'me> {
                 });
                 (error_term(), Arc::new(Value::Error))
             }
+            TermData::RecordUpdate(head_term, term_entries) => {
+                let (core_head_term, head_type) = self.synth_type(head_term);
+                let head_type = self.force_value(&head_type);
+                let closure = match head_type.as_ref() {
+                    Value::RecordType(closure) => closure,
+                    Value::Error => return (error_term(), Arc::new(Value::Error)),
+                    _ => {
+                        let head_type = self.read_back_to_surface_term(&head_type);
+                        self.report(SurfaceToCoreMessage::UnexpectedRecordUpdateHead {
+                            head_location: head_term.location,
+                            head_type,
+                        });
+                        return (error_term(), Arc::new(Value::Error));
+                    }
+                };
+
+                let mut duplicate_labels = Vec::new();
+                let mut seen_labels = BTreeMap::new();
+                for (label, _, _) in term_entries {
+                    match seen_labels.get(label.data.as_str()) {
+                        None => {
+                            seen_labels.insert(label.data.as_str(), label.location);
+                        }
+                        Some(seen_range) => {
+                            let current_range = label.location;
+                            duplicate_labels.push((label.data.clone(), *seen_range, current_range));
+                        }
+                    }
+                }
+
+                // The updated record has the same type as the head, extended
+                // with the types of any new entries.
+                let mut type_size = self.local_definitions.size();
+                let mut core_entry_types = Vec::with_capacity(closure.entries().len());
+                let mut entry_locals = Vec::with_capacity(closure.entries().len());
+                closure.for_each_entry(self.globals, |_, entry_type| {
+                    let core_entry_type = self.read_back_value_at(type_size, &entry_type);
+                    core_entry_types.push(Arc::new(core_entry_type));
+                    let local = Arc::new(Value::local(type_size.next_level(), []));
+                    entry_locals.push((type_size, local.clone()));
+                    type_size = type_size.increment();
+                    local
+                });
+                let mut core_type_entries = (closure.entries().iter().zip(core_entry_types))
+                    .enumerate()
+                    .map(
+                        |(index, ((label, docs, usage, _, default_term), core_entry_type))| {
+                            let core_default_term = default_term.as_ref().map(|default_term| {
+                                let (local_size, _) = entry_locals[index];
+                                let entry_locals =
+                                    entry_locals[..index].iter().map(|(_, local)| local.clone());
+                                let default_value = closure.eval_with_entries(
+                                    self.globals,
+                                    entry_locals,
+                                    default_term,
+                                );
+                                Arc::new(self.read_back_value_at(local_size, &default_value))
+                            });
+                            (
+                                label.clone(),
+                                docs.clone(),
+                                *usage,
+                                core_entry_type,
+                                core_default_term,
+                            )
+                        },
+                    )
+                    .collect::<Vec<_>>();
+
+                // The head is bound to a local, so that the entries that are
+                // not updated can be copied from it.
+                let head_value = self.eval_term(&core_head_term);
+                self.push_local(None, head_value.clone(), head_type.clone());
+
+                let mut core_term_entries = Vec::with_capacity(term_entries.len());
+                let mut dependent_labels = Vec::new();
+                let mut updated_labels = Vec::with_capacity(term_entries.len());
+
+                closure.for_each_entry(self.globals, |label, entry_type| {
+                    let (name, core_entry_term) = match (term_entries.iter())
+                        .find(|(entry_label, _, _)| entry_label.data == label)
+                    {
+                        Some((entry_label, entry_name, entry_term)) => {
+                            let name = entry_name.as_ref().unwrap_or(entry_label);
+                            let usage = (closure.entries().get(core_term_entries.len()))
+                                .map_or(Usage::Many, |(_, _, usage, _, _)| *usage);
+                            updated_labels.push(label);
+                            let core_entry_term = self
+                                .with_usage(usage, |this| this.check_type(entry_term, &entry_type));
+                            (name.data.as_str(), core_entry_term)
+                        }
+                        None => {
+                            // The type of the entry might depend on entries
+                            // that were updated, in which case it also needs
+                            // to be updated.
+                            match self.record_elim_type(head_value.clone(), label, closure) {
+                                Some(r#type) if self.is_subtype(&r#type, &entry_type) => {
+                                    let head_index =
+                                        core::LocalIndex(core_term_entries.len() as u32);
+                                    let head =
+                                        core::Term::generated(core::TermData::Local(head_index));
+                                    let core_entry_term =
+                                        core::Term::generated(core::TermData::RecordElim(
+                                            Arc::new(head),
+                                            label.to_owned(),
+                                        ));
+                                    (label, core_entry_term)
+                                }
+                                _ => {
+                                    dependent_labels.push(label.to_owned());
+                                    (label, core::Term::generated(core::TermData::Error))
+                                }
+                            }
+                        }
+                    };
+                    let core_entry_value = self.eval_term(&core_entry_term);
+
+                    self.push_local(Some(name), core_entry_value.clone(), entry_type);
+                    core_term_entries.push((label.to_owned(), Arc::new(core_entry_term)));
 
-            TermData::SequenceTerm(_) => {
+                    core_entry_value
+                });
+
+                // Extend the record with the entries that were not in the head.
+                for (position, term_entry @ (label, _, _)) in term_entries.iter().enumerate() {
+                    let is_duplicate = (term_entries[..position].iter())
+                        .any(|(seen_label, _, _)| seen_label.data == label.data);
+                    if is_duplicate || updated_labels.contains(&label.data.as_str()) {
+                        continue;
+                    }
+
+                    let (core_type_entry, core_term_entry) =
+                        self.synth_record_term_entry(type_size, term_entry);
+                    core_type_entries.push(core_type_entry);
+                    core_term_entries.push(core_term_entry);
+                    type_size = type_size.increment();
+                }
+
+                self.pop_many_locals(core_term_entries.len() + 1);
+
+                if !duplicate_labels.is_empty() || !dependent_labels.is_empty() {
+                    self.report(SurfaceToCoreMessage::InvalidRecordUpdate {
+                        location: term.location,
+                        duplicate_labels,
+                        dependent_labels,
+                    });
+                }
+
+                let core_term = core::Term::new(
+                    term.location,
+                    core::TermData::Let(
+                        "head".to_owned(),
+                        Arc::new(core_head_term),
+                        Arc::new(core::Term::new(
+                            term.location,
+                            core::TermData::RecordTerm(core_term_entries.into()),
+                        )),
+                    ),
+                );
+                let core_type =
+                    core::Term::generated(core::TermData::RecordType(core_type_entries.into()));
+                let r#type = self.eval_term(&core_type);
+
+                (
+                    core::Term::new(
+                        term.location,
+                        core::TermData::Ann(Arc::new(core_term), Arc::new(core_type)),
+                    ),
+                    r#type,
+                )
+            }
+
+            TermData::EnumType(labels) => {
+                use std::collections::btree_map::Entry;
+
+                let mut duplicate_labels = Vec::new();
+                let mut seen_labels = BTreeMap::new();
+                let mut core_labels = Vec::with_capacity(labels.len());
+
+                for label in labels {
+                    match seen_labels.entry(label.data.as_str()) {
+                        Entry::Vacant(entry) => {
+                            core_labels.push(label.data.clone());
+                            entry.insert(label.location);
+                        }
+                        Entry::Occupied(entry) => {
+                            let seen_range = *entry.get();
+                            let current_range = label.location;
+                            duplicate_labels.push((label.data.clone(), seen_range, current_range));
+                        }
+                    }
+                }
+
+                if !duplicate_labels.is_empty() {
+                    self.report(SurfaceToCoreMessage::InvalidEnumType { duplicate_labels });
+                }
+
+                (
+                    core::Term::new(term.location, core::TermData::EnumType(core_labels.into())),
+                    Arc::new(Value::TypeType(core::UniverseLevel::Finite(0))),
+                )
+            }
+            TermData::EnumTerm(label) => (
+                core::Term::new(term.location, core::TermData::EnumTerm(label.data.clone())),
+                Arc::new(Value::EnumType(Arc::new([label.data.clone()]))),
+            ),
+            TermData::EnumElim(head_term, branches) => {
+                match self.synth_match_head(head_term, branches, term.location) {
+                    Some((core_head_term, MatchBranches::Enum(branches))) => {
+                        match branches.split_first() {
+                            Some(((_, _, first_branch_term), _)) => {
+                                let mut r#type = Arc::new(Value::Error);
+                                let core_branches =
+                                    self.with_alternatives(branches.len(), |this, index| {
+                                        let (label, _, branch_term) = branches[index];
+                                        let core_branch_term = match index {
+                                            0 => {
+                                                let (core_branch_term, branch_type) =
+                                                    this.synth_type(first_branch_term);
+                                                r#type = branch_type;
+                                                core_branch_term
+                                            }
+                                            _ => this.check_type(branch_term, &r#type),
+                                        };
+                                        (label.data.clone(), Arc::new(core_branch_term))
+                                    });
+
+                                let core_term = core::Term::new(
+                                    term.location,
+                                    core::TermData::EnumElim(
+                                        Arc::new(core_head_term),
+                                        core_branches.into(),
+                                    ),
+                                );
+                                (core_term, r#type)
+                            }
+                            None => {
+                                self.report(SurfaceToCoreMessage::AmbiguousTerm {
+                                    location: term.location,
+                                    term: AmbiguousTerm::EnumElim,
+                                });
+                                (error_term(), Arc::new(Value::Error))
+                            }
+                        }
+                    }
+                    Some((core_head_term, MatchBranches::Variant(branches))) => {
+                        match branches.is_empty() {
+                            false => {
+                                let mut r#type = Arc::new(Value::Error);
+                                let core_branches =
+                                    self.with_alternatives(branches.len(), |this, index| {
+                                        let ((label, names, branch_term), payload_type) =
+                                            &branches[index];
+                                        let name = names.first().map(|name| name.data.as_str());
+                                        this.push_local_param(name, payload_type.clone());
+                                        let core_branch_term = match index {
+                                            0 => {
+                                                let (core_branch_term, branch_type) =
+                                                    this.synth_type(branch_term);
+                                                r#type = branch_type;
+                                                core_branch_term
+                                            }
+                                            _ => this.check_type(branch_term, &r#type),
+                                        };
+                                        this.pop_local();
+                                        (
+                                            label.data.clone(),
+                                            name.map(str::to_owned),
+                                            Arc::new(core_branch_term),
+                                        )
+                                    });
+
+                                let core_term = core::Term::new(
+                                    term.location,
+                                    core::TermData::VariantElim(
+                                        Arc::new(core_head_term),
+                                        core_branches.into(),
+                                    ),
+                                );
+                                (core_term, r#type)
+                            }
+                            true => {
+                                self.report(SurfaceToCoreMessage::AmbiguousTerm {
+                                    location: term.location,
+                                    term: AmbiguousTerm::EnumElim,
+                                });
+                                (error_term(), Arc::new(Value::Error))
+                            }
+                        }
+                    }
+                    Some((_, MatchBranches::Inductive(_, _, _))) => {
+                        self.report(SurfaceToCoreMessage::AmbiguousTerm {
+                            location: term.location,
+                            term: AmbiguousTerm::EnumElim,
+                        });
+                        (error_term(), Arc::new(Value::Error))
+                    }
+                    None => (error_term(), Arc::new(Value::Error)),
+                }
+            }
+
+            TermData::VariantType(type_entries) => {
+                use std::collections::btree_map::Entry;
+
+                let mut duplicate_labels = Vec::new();
+                let mut seen_labels = BTreeMap::new();
+                let mut core_type_entries = Vec::with_capacity(type_entries.len());
+                let mut max_level = core::UniverseLevel::Finite(0);
+
+                for (label, entry_type) in type_entries {
+                    let (core_entry_type, level) = self.is_type(entry_type);
+                    max_level = match level {
+                        Some(level) => std::cmp::max(max_level, level),
+                        None => return (error_term(), Arc::new(Value::Error)),
+                    };
+
+                    match seen_labels.entry(label.data.as_str()) {
+                        Entry::Vacant(entry) => {
+                            core_type_entries.push((label.data.clone(), Arc::new(core_entry_type)));
+                            entry.insert(label.location);
+                        }
+                        Entry::Occupied(entry) => {
+                            let seen_range = *entry.get();
+                            let current_range = label.location;
+                            duplicate_labels.push((label.data.clone(), seen_range, current_range));
+                        }
+                    }
+                }
+
+                if !duplicate_labels.is_empty() {
+                    self.report(SurfaceToCoreMessage::InvalidVariantType { duplicate_labels });
+                }
+
+                (
+                    core::Term::new(
+                        term.location,
+                        core::TermData::VariantType(core_type_entries.into()),
+                    ),
+                    Arc::new(Value::TypeType(max_level)),
+                )
+            }
+            TermData::VariantTerm(_, _) => {
                 self.report(SurfaceToCoreMessage::AmbiguousTerm {
                     location: term.location,
-                    term: AmbiguousTerm::Sequence,
+                    term: AmbiguousTerm::VariantTerm,
                 });
                 (error_term(), Arc::new(Value::Error))
             }
 
-            TermData::NumberTerm(_) => {
+            TermData::InductiveType(type_name, constructors) => {
+                use std::collections::btree_map::Entry;
+
+                let mut duplicate_labels = Vec::new();
+                let mut seen_labels = BTreeMap::new();
+                let mut core_constructors = Vec::with_capacity(constructors.len());
+                let mut max_level = core::UniverseLevel::Finite(0);
+
+                // Recursive occurrences of the inductive type do not raise
+                // its universe level, so it is bound as a small type.
+                let type_type = Arc::new(Value::TypeType(core::UniverseLevel::Finite(0)));
+                self.push_local_param(Some(&type_name.data), type_type);
+                for (label, constructor_type) in constructors {
+                    let (core_constructor_type, level) = self.is_type(constructor_type);
+                    max_level = match level {
+                        Some(level) => std::cmp::max(max_level, level),
+                        None => {
+                            self.pop_local();
+                            return (error_term(), Arc::new(Value::Error));
+                        }
+                    };
+
+                    match typing::check_constructor_type(&core_constructor_type) {
+                        Ok(()) => {}
+                        Err(ConstructorTypeError::UnsupportedOccurrence(term)) => {
+                            self.report(SurfaceToCoreMessage::UnsupportedRecursiveOccurrence {
+                                location: term.location,
+                                type_name: type_name.data.clone(),
+                            });
+                        }
+                        Err(ConstructorTypeError::UnexpectedOutputType(term)) => {
+                            self.report(SurfaceToCoreMessage::UnexpectedConstructorOutputType {
+                                location: term.location,
+                                type_name: type_name.data.clone(),
+                            });
+                        }
+                    }
+
+                    match seen_labels.entry(label.data.as_str()) {
+                        Entry::Vacant(entry) => {
+                            let core_constructor_type = Arc::new(core_constructor_type);
+                            core_constructors.push((label.data.clone(), core_constructor_type));
+                            entry.insert(label.location);
+                        }
+                        Entry::Occupied(entry) => {
+                            let seen_range = *entry.get();
+                            let current_range = label.location;
+                            duplicate_labels.push((label.data.clone(), seen_range, current_range));
+                        }
+                    }
+                }
+                self.pop_local();
+
+                if !duplicate_labels.is_empty() {
+                    self.report(SurfaceToCoreMessage::InvalidInductiveType { duplicate_labels });
+                }
+
+                (
+                    core::Term::new(
+                        term.location,
+                        core::TermData::InductiveType(
+                            type_name.data.clone(),
+                            core_constructors.into(),
+                        ),
+                    ),
+                    Arc::new(Value::TypeType(max_level)),
+                )
+            }
+            TermData::InductiveElim(head_term, motive, branches) => {
+                let (core_head_term, head_type) = self.synth_type(head_term);
+                let closure = match self.force_value(&head_type).as_ref() {
+                    Value::InductiveType(closure) => closure.clone(),
+                    Value::Error => return (error_term(), Arc::new(Value::Error)),
+                    _ => {
+                        let head_type = self.read_back_to_surface_term(&head_type);
+                        self.report(SurfaceToCoreMessage::UnexpectedInductiveElimHead {
+                            head_location: head_term.location,
+                            head_type,
+                        });
+                        return (error_term(), Arc::new(Value::Error));
+                    }
+                };
+
+                let motive_type = self.motive_type(head_type.clone());
+                let core_motive =
+                    self.with_usage(Usage::Zero, |this| this.check_type(motive, &motive_type));
+                let motive_value = self.eval_term(&core_motive);
+
+                let branches = self.order_match_branches(closure.labels(), branches, term.location);
+                let core_branches =
+                    self.check_inductive_branches(&head_type, &closure, &motive_value, branches);
+
+                let head_value = self.eval_term(&core_head_term);
+                let r#type = semantics::inductive_elim_type(self.globals, motive_value, head_value);
+                let core_term = core::Term::new(
+                    term.location,
+                    core::TermData::InductiveElim(
+                        Arc::new(core_head_term),
+                        Arc::new(core_motive),
+                        core_branches.into(),
+                    ),
+                );
+                (core_term, r#type)
+            }
+
+            TermData::BoolElim(head_term, if_true, if_false) => {
+                let bool_type = Arc::new(Value::global("Bool", 0, []));
+                let core_head_term = self.check_type(head_term, &bool_type);
+                let mut r#type = Arc::new(Value::Error);
+                let mut core_branches = self.with_alternatives(2, |this, index| match index {
+                    0 => {
+                        let (core_if_true, if_true_type) = this.synth_type(if_true);
+                        r#type = if_true_type;
+                        core_if_true
+                    }
+                    _ => this.check_type(if_false, &r#type),
+                });
+                let core_if_false = core_branches.pop().unwrap();
+                let core_if_true = core_branches.pop().unwrap();
+
+                let core_term = core::Term::new(
+                    term.location,
+                    core::TermData::BoolElim(
+                        Arc::new(core_head_term),
+                        Arc::new(core_if_true),
+                        Arc::new(core_if_false),
+                    ),
+                );
+                (core_term, r#type)
+            }
+
+            TermData::EqType(r#type, lhs, rhs) => {
+                let (core_type, level) = self.is_type(r#type);
+                let type_value = match level {
+                    None => Arc::new(Value::Error),
+                    Some(_) => self.eval_term(&core_type),
+                };
+                let core_lhs = self.check_type(lhs, &type_value);
+                let core_rhs = self.check_type(rhs, &type_value);
+
+                match level {
+                    Some(level) => (
+                        core::Term::new(
+                            term.location,
+                            core::TermData::EqType(
+                                Arc::new(core_type),
+                                Arc::new(core_lhs),
+                                Arc::new(core_rhs),
+                            ),
+                        ),
+                        Arc::new(Value::TypeType(level)),
+                    ),
+                    None => (error_term(), Arc::new(Value::Error)),
+                }
+            }
+            TermData::EqTerm => {
                 self.report(SurfaceToCoreMessage::AmbiguousTerm {
                     location: term.location,
-                    term: AmbiguousTerm::NumberLiteral,
+                    term: AmbiguousTerm::EqTerm,
                 });
                 (error_term(), Arc::new(Value::Error))
             }
+            TermData::EqElim(head_term, motive, eq_term) => {
+                let (core_head_term, head_type) = self.synth_type(head_term);
+                match self.force_value(&head_type).as_ref() {
+                    Value::EqType(r#type, lhs, rhs) => {
+                        let motive_type = self.motive_type(r#type.clone());
+                        let core_motive = self
+                            .with_usage(Usage::Zero, |this| this.check_type(motive, &motive_type));
+                        let motive_value = self.eval_term(&core_motive);
+                        let lhs_type = self.eq_elim_type(motive_value.clone(), lhs.clone());
+                        let core_eq_term = self.check_type(eq_term, &lhs_type);
+
+                        let core_term = core::Term::new(
+                            term.location,
+                            core::TermData::EqElim(
+                                Arc::new(core_head_term),
+                                Arc::new(core_motive),
+                                Arc::new(core_eq_term),
+                            ),
+                        );
+                        (core_term, self.eq_elim_type(motive_value, rhs.clone()))
+                    }
+                    Value::Error => (error_term(), Arc::new(Value::Error)),
+                    _ => {
+                        let head_type = self.read_back_to_surface_term(&head_type);
+                        self.report(SurfaceToCoreMessage::UnexpectedEqElimHead {
+                            head_location: head_term.location,
+                            head_type,
+                        });
+                        (error_term(), Arc::new(Value::Error))
+                    }
+                }
+            }
+
+            TermData::CodeType(r#type) => match self.stage {
+                0 => {
+                    self.report(SurfaceToCoreMessage::UnexpectedCodeStage {
+                        location: term.location,
+                    });
+                    (error_term(), Arc::new(Value::Error))
+                }
+                stage => {
+                    let previous_stage = std::mem::replace(&mut self.stage, stage - 1);
+                    let (core_type, level) = self.is_type(r#type);
+                    self.stage = previous_stage;
+                    match level {
+                        Some(level) => (
+                            core::Term::new(
+                                term.location,
+                                core::TermData::CodeType(Arc::new(core_type)),
+                            ),
+                            Arc::new(Value::TypeType(level)),
+                        ),
+                        None => (error_term(), Arc::new(Value::Error)),
+                    }
+                }
+            },
+            TermData::CodeTerm(code_term) => match self.stage {
+                0 => {
+                    self.report(SurfaceToCoreMessage::UnexpectedCodeStage {
+                        location: term.location,
+                    });
+                    (error_term(), Arc::new(Value::Error))
+                }
+                stage => {
+                    let previous_stage = std::mem::replace(&mut self.stage, stage - 1);
+                    let (core_code_term, r#type) = self.synth_type(code_term);
+                    self.stage = previous_stage;
+                    let core_term = core::Term::new(
+                        term.location,
+                        core::TermData::CodeTerm(Arc::new(core_code_term)),
+                    );
+                    (core_term, Arc::new(Value::CodeType(r#type)))
+                }
+            },
+            TermData::CodeElim(head_term) => {
+                self.stage += 1;
+                let (core_head_term, head_type) = self.synth_type(head_term);
+                self.stage -= 1;
+                match self.force_value(&head_type).as_ref() {
+                    Value::CodeType(r#type) => {
+                        let core_term = core::Term::new(
+                            term.location,
+                            core::TermData::CodeElim(Arc::new(core_head_term)),
+                        );
+                        (core_term, r#type.clone())
+                    }
+                    Value::Error => (error_term(), Arc::new(Value::Error)),
+                    _ => {
+                        let head_type = self.read_back_to_surface_term(&head_type);
+                        self.report(SurfaceToCoreMessage::UnexpectedCodeElimHead {
+                            head_location: head_term.location,
+                            head_type,
+                        });
+                        (error_term(), Arc::new(Value::Error))
+                    }
+                }
+            }
+
+            TermData::SequenceTerm(entry_terms) => {
+                let (first_term, rest_terms) = match entry_terms.split_first() {
+                    Some(entry_terms) => entry_terms,
+                    None => {
+                        self.report(SurfaceToCoreMessage::AmbiguousTerm {
+                            location: term.location,
+                            term: AmbiguousTerm::Sequence,
+                        });
+                        return (error_term(), Arc::new(Value::Error));
+                    }
+                };
+
+                // The type of the first entry determines the type of the
+                // remaining entries.
+                let (core_first_term, entry_type) = self.synth_type(first_term);
+                let mut core_entry_terms = Vec::with_capacity(entry_terms.len());
+                core_entry_terms.push(Arc::new(core_first_term));
+                for entry_term in rest_terms {
+                    core_entry_terms.push(Arc::new(self.check_type(entry_term, &entry_type)));
+                }
+
+                let is_error = matches!(entry_type.as_ref(), Value::Error)
+                    || (core_entry_terms.iter())
+                        .any(|core_term| matches!(core_term.data, core::TermData::Error));
+                if is_error {
+                    return (error_term(), Arc::new(Value::Error));
+                }
+
+                let core_entry_type = Arc::new(self.read_back_value(&entry_type));
+                let global = |name: &str| {
+                    Arc::new(core::Term::generated(core::TermData::Global(
+                        name.to_owned(),
+                    )))
+                };
+                let core_type = core::Term::generated(core::TermData::FunctionElim(
+                    global("List"),
+                    core_entry_type.clone(),
+                ));
+                let r#type = self.eval_term(&core_type);
+
+                // Suggest the equivalent array type, in case the length of the
+                // sequence was meant to be fixed.
+                let core_len = core::Constant::U32(entry_terms.len() as u32);
+                let core_array_type = core::Term::generated(core::TermData::FunctionElim(
+                    Arc::new(core::Term::generated(core::TermData::FunctionElim(
+                        global("Array"),
+                        Arc::new(core::Term::generated(core::TermData::from(core_len))),
+                    ))),
+                    core_entry_type,
+                ));
+                let array_type = self.eval_term(&core_array_type);
+                let list_type = self.read_back_to_surface_term(&r#type);
+                let array_type = self.read_back_to_surface_term(&array_type);
+                self.report(SurfaceToCoreMessage::ListSequenceSynthesized {
+                    location: term.location,
+                    list_type,
+                    array_type,
+                });
+
+                let core_term =
+                    core::Term::new(term.location, core::TermData::ListTerm(core_entry_terms));
+
+                (
+                    core::Term::new(
+                        term.location,
+                        core::TermData::Ann(Arc::new(core_term), Arc::new(core_type)),
+                    ),
+                    r#type,
+                )
+            }
+
+            TermData::NumberTerm(data) => {
+                use crate::lang::core::Constant::*;
+                use crate::literal::NumberSuffix;
+
+                // Literals without a suffix default to `F64` if they have a
+                // fractional part or an exponent, and to `S32` otherwise.
+                let (digits, suffix) = literal::split_number_suffix(data);
+                let suffix = suffix.unwrap_or_else(|| {
+                    if literal::is_float_number(digits) {
+                        NumberSuffix::F64
+                    } else {
+                        NumberSuffix::S32
+                    }
+                });
+
+                let location = term.location;
+                let core_term = match suffix {
+                    NumberSuffix::U8 => self.parse_unsigned(location, digits, U8),
+                    NumberSuffix::U16 => self.parse_unsigned(location, digits, U16),
+                    NumberSuffix::U32 => self.parse_unsigned(location, digits, U32),
+                    NumberSuffix::U64 => self.parse_unsigned(location, digits, U64),
+                    NumberSuffix::S8 => self.parse_signed(location, digits, S8),
+                    NumberSuffix::S16 => self.parse_signed(location, digits, S16),
+                    NumberSuffix::S32 => self.parse_signed(location, digits, S32),
+                    NumberSuffix::S64 => self.parse_signed(location, digits, S64),
+                    NumberSuffix::F32 => self.parse_float(location, digits, F32),
+                    NumberSuffix::F64 => self.parse_float(location, digits, F64),
+                };
+
+                (
+                    core_term,
+                    Arc::new(Value::global(suffix.type_name(), 0, [])),
+                )
+            }
             TermData::CharTerm(data) => (
                 self.parse_char(term.location, data),
                 Arc::new(Value::global("Char", 0, [])),
@@ -728,6 +2475,445 @@ impl<'me> State<'me> {
         }
     }
 
+    /// Elaborate the file at the given path, returning the elaborated term and
+    /// its type.
+    ///
+    /// The file is elaborated in an empty local environment, and the result is
+    /// cached, so files that are imported more than once will only be
+    /// elaborated once.
+    pub fn synth_file_type(&mut self, path: &Path) -> (core::Term, Arc<Value>) {
+        match self.import_file(Location::generated(), path) {
+            Some((core_term, core_type)) => {
+                let core_term = core::Term::new(
+                    core_term.location,
+                    core::TermData::Ann(core_term, core_type.clone()),
+                );
+                (core_term, self.eval_term(&core_type))
+            }
+            None => (
+                core::Term::generated(core::TermData::Error),
+                Arc::new(Value::Error),
+            ),
+        }
+    }
+
+    /// Resolve the path of an import, relative to the file that the import
+    /// was found in.
+    fn resolve_import_path(&self, location: Location, path: &str) -> PathBuf {
+        let files = self.files.borrow();
+        let parent = match location {
+            Location::FileRange(file_id, _) => {
+                (files.get(file_id)).and_then(|file| Path::new(file.name()).parent())
+            }
+            Location::Generated => None,
+        };
+
+        match parent {
+            Some(parent) => parent.join(path),
+            None => PathBuf::from(path),
+        }
+    }
+
+    /// Elaborate an imported file, returning the elaborated term and type of
+    /// the file, or `None` if the file could not be imported.
+    fn import_file(
+        &mut self,
+        location: Location,
+        path: &Path,
+    ) -> Option<(Arc<core::Term>, Arc<core::Term>)> {
+        let import_not_found = |error: std::io::Error| SurfaceToCoreMessage::ImportNotFound {
+            location,
+            path: path.display().to_string(),
+            error: error.to_string(),
+        };
+
+        let canonical_path = match std::fs::canonicalize(path) {
+            Ok(canonical_path) => canonical_path,
+            Err(error) => {
+                self.report(import_not_found(error));
+                return None;
+            }
+        };
+
+        if let Some((core_term, core_type)) = self.imports.get(&canonical_path) {
+            return Some((core_term.clone(), core_type.clone()));
+        }
+
+        if self.pending_imports.contains(&canonical_path) {
+            self.report(SurfaceToCoreMessage::CyclicImport {
+                location,
+                path: path.display().to_string(),
+            });
+            return None;
+        }
+
+        let source = match std::fs::read_to_string(&canonical_path) {
+            Ok(source) => source,
+            Err(error) => {
+                self.report(import_not_found(error));
+                return None;
+            }
+        };
+
+        let file_id = (self.files.borrow_mut()).add(path.display().to_string(), source.clone());
+        let surface_term = surface::Term::from_str(file_id, &source, &self.message_tx);
+
+        // Imported files are elaborated in a fresh environment, sharing the
+        // imports that have been elaborated so far.
+        let mut state = State::new(self.globals, self.files, self.message_tx.clone());
+        state.imports = std::mem::take(&mut self.imports);
+        state.pending_imports = std::mem::take(&mut self.pending_imports);
+
+        state.pending_imports.push(canonical_path.clone());
+        let (core_term, r#type) = state.synth_top_level_type(&surface_term);
+        let core_type = state.read_back_value(&r#type);
+        state.pending_imports.pop();
+
+        self.imports = state.imports;
+        self.pending_imports = state.pending_imports;
+
+        let import = (Arc::new(core_term), Arc::new(core_type));
+        self.imports.insert(canonical_path, import.clone());
+
+        Some(import)
+    }
+
+    /// Synthesize the type of the head of a match expression, checking that
+    /// it is an enumeration or a variant, and that there is exactly one branch
+    /// for each of its labels.
+    ///
+    /// Returns the elaborated head term, along with the branches in the order
+    /// that the labels appear in the type of the head, or `None` if the head
+    /// was not an enumeration or a variant.
+    fn synth_match_head<'branches>(
+        &mut self,
+        head_term: &Term,
+        branches: &'branches [surface::Branch],
+        location: Location,
+    ) -> Option<(core::Term, MatchBranches<'branches>)> {
+        let (core_head_term, head_type) = self.synth_type(head_term);
+
+        match self.force_value(&head_type).as_ref() {
+            Value::EnumType(labels) => {
+                for (_, names, _) in branches {
+                    for name in names {
+                        self.report(SurfaceToCoreMessage::UnexpectedBranchName {
+                            name_location: name.location,
+                        });
+                    }
+                }
+
+                let labels = labels.iter().map(String::as_str);
+                let branches = self.order_match_branches(labels, branches, location);
+                Some((core_head_term, MatchBranches::Enum(branches)))
+            }
+            Value::VariantType(type_entries) => {
+                for (label, names, _) in branches {
+                    if names.len() > 1 {
+                        self.report(SurfaceToCoreMessage::MismatchedBranchNames {
+                            label_location: label.location,
+                            label: label.data.clone(),
+                            expected_counts: vec![0, 1],
+                            found_count: names.len(),
+                        });
+                    }
+                }
+
+                let labels = type_entries.iter().map(|(label, _)| label.as_str());
+                let branches = (self
+                    .order_match_branches(labels, branches, location)
+                    .into_iter())
+                .filter_map(|branch @ (label, _, _)| {
+                    let (_, payload_type) = type_entries.iter().find(|(l, _)| *l == label.data)?;
+                    Some((branch, payload_type.clone()))
+                })
+                .collect();
+                Some((core_head_term, MatchBranches::Variant(branches)))
+            }
+            Value::InductiveType(closure) => {
+                let branches = self.order_match_branches(closure.labels(), branches, location);
+                let closure = closure.clone();
+                Some((
+                    core_head_term,
+                    MatchBranches::Inductive(head_type, closure, branches),
+                ))
+            }
+            Value::Error => None,
+            _ => {
+                let head_type = self.read_back_to_surface_term(&head_type);
+                self.report(SurfaceToCoreMessage::UnexpectedEnumElimHead {
+                    head_location: head_term.location,
+                    head_type,
+                });
+                None
+            }
+        }
+    }
+
+    /// Elaborate the constructor of an inductive type with the given label,
+    /// returning a function that applies the constructor to its inputs, along
+    /// with the type of the constructor.
+    fn constructor_term(
+        &mut self,
+        inductive_type: Arc<Value>,
+        label: &str,
+        closure: &InductiveClosure,
+    ) -> Option<(core::Term, Arc<Value>)> {
+        let input_count = closure.recursive_inputs(label)?.len();
+        let constructor_type =
+            closure.constructor_type(self.globals, label, inductive_type.clone())?;
+
+        let mut local_size = self.local_definitions.size();
+        let mut input_names = Vec::with_capacity(input_count);
+        let mut current_type = constructor_type.clone();
+        for _ in 0..input_count {
+            match self.force_value(&current_type).as_ref() {
+                Value::FunctionType(_, _, input_name_hint, _, output_closure) => {
+                    let input = Arc::new(Value::local(local_size.next_level(), []));
+                    input_names.push(input_name_hint.clone().unwrap_or_else(|| "_".to_owned()));
+                    current_type = output_closure.apply(self.globals, input);
+                    local_size = local_size.increment();
+                }
+                _ => return None,
+            }
+        }
+
+        let core_inductive_type = self.read_back_value_at(local_size, &inductive_type);
+        let core_inputs = (0..input_count)
+            .rev()
+            .map(|index| {
+                Arc::new(core::Term::generated(core::TermData::Local(
+                    core::LocalIndex(index as u32),
+                )))
+            })
+            .collect();
+        let core_term = core::Term::generated(core::TermData::InductiveTerm(
+            Arc::new(core_inductive_type),
+            label.to_owned(),
+            core_inputs,
+        ));
+        let core_term = (input_names.into_iter().rev()).fold(core_term, |core_term, input_name| {
+            core::Term::generated(core::TermData::FunctionTerm(
+                input_name,
+                Arc::new(core_term),
+            ))
+        });
+        // The types of function terms can't be synthesized in the core
+        // language, so constructors with inputs are annotated with their type.
+        let core_term = match input_count {
+            0 => core_term,
+            _ => core::Term::generated(core::TermData::Ann(
+                Arc::new(core_term),
+                Arc::new(self.read_back_value(&constructor_type)),
+            )),
+        };
+
+        Some((core_term, constructor_type))
+    }
+
+    /// Check the branches of an inductive elimination against the given
+    /// motive, binding the inputs of each constructor, followed by the results
+    /// of eliminating each of its recursive inputs.
+    ///
+    /// The names bound by a branch can either cover only the inputs of the
+    /// constructor, or the inputs along with the recursive results.
+    fn check_inductive_branches(
+        &mut self,
+        inductive_type: &Arc<Value>,
+        closure: &InductiveClosure,
+        motive: &Arc<Value>,
+        branches: Vec<&surface::Branch>,
+    ) -> Vec<core::InductiveBranch> {
+        self.with_alternatives(branches.len(), |this, index| {
+            this.check_inductive_branch(inductive_type, closure, motive, branches[index])
+        })
+    }
+
+    /// Check a branch of an inductive elimination against the given motive.
+    fn check_inductive_branch(
+        &mut self,
+        inductive_type: &Arc<Value>,
+        closure: &InductiveClosure,
+        motive: &Arc<Value>,
+        (label, names, branch_term): &surface::Branch,
+    ) -> core::InductiveBranch {
+        let recursive_inputs = closure.recursive_inputs(&label.data).unwrap_or_default();
+        let mut constructor_type = (closure)
+            .constructor_type(self.globals, &label.data, inductive_type.clone())
+            .unwrap_or_else(|| Arc::new(Value::Error));
+
+        let input_count = recursive_inputs.len();
+        let local_count = input_count + recursive_inputs.iter().filter(|r| **r).count();
+        if names.len() != input_count && names.len() != local_count {
+            self.report(SurfaceToCoreMessage::MismatchedBranchNames {
+                label_location: label.location,
+                label: label.data.clone(),
+                expected_counts: if input_count == local_count {
+                    vec![input_count]
+                } else {
+                    vec![input_count, local_count]
+                },
+                found_count: names.len(),
+            });
+        }
+        let mut names = names.iter().map(|name| name.data.as_str());
+
+        let mut name_hints = Vec::with_capacity(local_count);
+        let mut inputs = Vec::with_capacity(input_count);
+        for _ in 0..input_count {
+            let (input_type, output_closure) = match self.force_value(&constructor_type).as_ref() {
+                Value::FunctionType(_, _, _, input_type, output_closure) => {
+                    (input_type.clone(), output_closure.clone())
+                }
+                _ => break,
+            };
+            let name = names.next();
+            let input = self.push_local_param(name, input_type);
+            constructor_type = output_closure.apply(self.globals, input.clone());
+            name_hints.push(name.map(str::to_owned));
+            inputs.push(input);
+        }
+        for (input, _) in Iterator::zip(inputs.iter(), recursive_inputs).filter(|(_, r)| *r) {
+            let name = names.next();
+            let output_type =
+                semantics::inductive_elim_type(self.globals, motive.clone(), input.clone());
+            self.push_local_param(name, output_type);
+            name_hints.push(name.map(str::to_owned));
+        }
+
+        let inductive_term = Arc::new(Value::InductiveTerm(
+            inductive_type.clone(),
+            label.data.clone(),
+            inputs,
+        ));
+        let branch_type =
+            semantics::inductive_elim_type(self.globals, motive.clone(), inductive_term);
+        let core_branch_term = self.check_type(branch_term, &branch_type);
+        self.pop_many_locals(name_hints.len());
+
+        (
+            label.data.clone(),
+            name_hints.into(),
+            Arc::new(core_branch_term),
+        )
+    }
+
+    /// Order the branches of a match expression by the given labels,
+    /// reporting any branches that are missing, unexpected, or duplicated.
+    fn order_match_branches<'label, 'branches>(
+        &mut self,
+        labels: impl Iterator<Item = &'label str> + Clone,
+        branches: &'branches [surface::Branch],
+        location: Location,
+    ) -> Vec<&'branches surface::Branch> {
+        use std::collections::btree_map::{BTreeMap, Entry};
+
+        let mut unexpected_labels = Vec::new();
+        let mut duplicate_labels = Vec::new();
+        let mut seen_labels = BTreeMap::new();
+
+        for (label, _, _) in branches {
+            if !labels.clone().any(|l| l == label.data) {
+                unexpected_labels.push(label.location);
+                continue;
+            }
+            match seen_labels.entry(label.data.as_str()) {
+                Entry::Vacant(entry) => {
+                    entry.insert(label.location);
+                }
+                Entry::Occupied(entry) => {
+                    let seen_range = *entry.get();
+                    let current_range = label.location;
+                    duplicate_labels.push((label.data.clone(), seen_range, current_range));
+                }
+            }
+        }
+
+        let mut ordered_branches = Vec::new();
+        let mut missing_labels = Vec::new();
+
+        for label in labels {
+            match branches.iter().find(|(l, _, _)| l.data == label) {
+                Some(branch) => ordered_branches.push(branch),
+                None => missing_labels.push(label.to_owned()),
+            }
+        }
+
+        if !missing_labels.is_empty()
+            || !unexpected_labels.is_empty()
+            || !duplicate_labels.is_empty()
+        {
+            self.report(SurfaceToCoreMessage::InvalidEnumElim {
+                location,
+                missing_labels,
+                unexpected_labels,
+                duplicate_labels,
+            });
+        }
+
+        ordered_branches
+    }
+
+    /// Synthesize the type of an entry in a record term, binding the entry to
+    /// a local so that the entries that follow it can refer to it.
+    ///
+    /// Returns the entry of the record type, read back in an environment of
+    /// the given size, along with the elaborated entry of the record term.
+    fn synth_record_term_entry(
+        &mut self,
+        type_size: core::LocalSize,
+        (label, name, entry_term): &surface::TermEntry,
+    ) -> (core::TypeEntry, (String, Arc<core::Term>)) {
+        let (core_entry_term, entry_type) =
+            self.with_usage(Usage::Many, |this| this.synth_type(entry_term));
+        let core_entry_value = self.eval_term(&core_entry_term);
+        let core_entry_type = self.read_back_value_at(type_size, &entry_type);
+
+        let name = name.as_ref().unwrap_or(label);
+        self.push_local(Some(&name.data), core_entry_value, entry_type);
+
+        (
+            (
+                label.data.clone(),
+                Arc::new([]),
+                Usage::Many,
+                Arc::new(core_entry_type),
+                None,
+            ),
+            (label.data.clone(), Arc::new(core_entry_term)),
+        )
+    }
+
+    /// Synthesize the type of a definition in a let expression, using the
+    /// type annotation if one was supplied.
+    ///
+    /// Definitions may be used any number of times, so the uses of the local
+    /// variables in the definition are scaled accordingly.
+    fn synth_def_type(
+        &mut self,
+        def_type: &Option<Box<Term>>,
+        def_term: &Term,
+    ) -> (core::Term, Arc<Value>) {
+        match def_type {
+            None => self.with_usage(Usage::Many, |this| this.synth_type(def_term)),
+            Some(def_type) => {
+                let (core_def_type, _) =
+                    self.with_usage(Usage::Zero, |this| this.is_type(def_type));
+                let core_def_type_value = self.eval_term(&core_def_type);
+                let core_def_term = self.with_usage(Usage::Many, |this| {
+                    this.check_type(def_term, &core_def_type_value)
+                });
+                (
+                    core::Term::new(
+                        Location::merge(def_type.location, def_term.location),
+                        core::TermData::Ann(Arc::new(core_def_term), Arc::new(core_def_type)),
+                    ),
+                    core_def_type_value,
+                )
+            }
+        }
+    }
+
     fn parse_float<T: Float + From<u8>>(
         &mut self,
         location: Location,
@@ -788,3 +2974,14 @@ impl<'me> State<'me> {
         core::Term::new(location, term_data)
     }
 }
+
+/// Returns `true` if the term is a function term that starts by binding an
+/// implicit input.
+fn binds_implicit_input(term: &Term) -> bool {
+    match &term.data {
+        TermData::FunctionTerm(input_name_groups, _) => {
+            matches!(input_name_groups.first(), Some((Plicity::Implicit, _, _)))
+        }
+        _ => false,
+    }
+}
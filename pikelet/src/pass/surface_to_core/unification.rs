@@ -0,0 +1,669 @@
+//! Metavariables and unification, used for inferring implicit inputs.
+//!
+//! Metavariables are applied to the local parameters that are in scope where
+//! they are created, allowing them to be solved with closed terms using
+//! [pattern unification]. This is layered on top of the comparisons in
+//! [`semantics`], falling back to a structural comparison only when there
+//! might be metavariables left to solve.
+//!
+//! [pattern unification]: https://github.com/AndrasKovacs/elaboration-zoo/
+//! [`semantics`]: crate::lang::core::semantics
+
+use std::sync::Arc;
+
+use crate::lang::core::semantics::{
+    self, Elim, Head, LazyValue, RecordClosure, RecordEntry, Unfold, Value,
+};
+use crate::lang::core::{self, LocalLevel, LocalSize, Locals, MetaIndex, UniverseOffset};
+use crate::lang::Location;
+use crate::pass::surface_to_core::State;
+use crate::reporting::SurfaceToCoreMessage;
+
+/// The reason that a metavariable was created.
+pub enum MetaSource {
+    /// An implicit input, with an optional name.
+    ImplicitInput(Option<String>),
+    /// A hole written by the programmer.
+    Hole,
+}
+
+/// An entry in the table of metavariables.
+pub struct MetaEntry {
+    /// The location of the term that the metavariable was created for.
+    location: Location,
+    /// The reason that the metavariable was created.
+    source: MetaSource,
+    /// The solution of the metavariable, as a closed term along with its value.
+    solution: Option<(Arc<core::Term>, Arc<Value>)>,
+}
+
+impl<'me> State<'me> {
+    /// Create a fresh metavariable, applied to the local parameters in scope.
+    pub(super) fn fresh_meta(
+        &mut self,
+        location: Location,
+        source: MetaSource,
+    ) -> (core::Term, Arc<Value>) {
+        let index = MetaIndex(self.metas.len() as u32); // TODO: Handle overflow
+        self.metas.push(MetaEntry {
+            location,
+            source,
+            solution: None,
+        });
+
+        let local_size = self.local_definitions.size();
+        let mut term = core::Term::new(location, core::TermData::Meta(index));
+        for index in (0..self.local_levels.len() as u32).rev() {
+            let local_index = core::LocalIndex(index);
+            let local_level = local_index.to_level(local_size);
+            // Definitions are skipped, because their values will have already
+            // been substituted into any values that might be compared with
+            // the metavariable.
+            match self.local_definitions.get(local_index).map(Arc::as_ref) {
+                Some(Value::Stuck(Head::Local(level), spine))
+                    if spine.is_empty() && Some(*level) == local_level =>
+                {
+                    term = core::Term::new(
+                        location,
+                        core::TermData::FunctionElim(
+                            Arc::new(term),
+                            Arc::new(core::Term::new(
+                                location,
+                                core::TermData::Local(local_index),
+                            )),
+                        ),
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        let value = self.eval_term(&term);
+        (term, value)
+    }
+
+    /// Force a value, substituting the solutions of any solved metavariables
+    /// at the head of the value.
+    pub(super) fn force_value(&self, value: &Arc<Value>) -> Arc<Value> {
+        let mut value = value.clone();
+        loop {
+            value = match value.as_ref() {
+                Value::Unstuck(_, _, lazy_value) => lazy_value.force(self.globals).clone(),
+                Value::Stuck(Head::Meta(index), spine) => match self.meta_solution(*index) {
+                    Some(solution) => self.apply_spine(solution.clone(), spine),
+                    None => return value,
+                },
+                _ => return value,
+            };
+        }
+    }
+
+    /// Report the implicit inputs that have not been solved, replacing their
+    /// solutions with errors.
+    ///
+    /// Holes are left unsolved, because their goals will have already been
+    /// reported when they were elaborated.
+    pub(super) fn report_unsolved_metas(&mut self) {
+        for meta in &mut self.metas {
+            if let (None, MetaSource::ImplicitInput(input_name)) = (&meta.solution, &meta.source) {
+                let message = SurfaceToCoreMessage::UnsolvedMeta {
+                    location: meta.location,
+                    input_name: input_name.clone(),
+                };
+                self.message_tx.send(message.into()).unwrap();
+
+                let error_term = Arc::new(core::Term::generated(core::TermData::Error));
+                meta.solution = Some((error_term, Arc::new(Value::Error)));
+            }
+        }
+    }
+
+    fn meta_solution(&self, index: MetaIndex) -> Option<&Arc<Value>> {
+        let meta = self.metas.get(index.0 as usize)?;
+        meta.solution.as_ref().map(|(_, value)| value)
+    }
+
+    fn apply_spine(&self, head_value: Arc<Value>, spine: &[Elim]) -> Arc<Value> {
+        spine.iter().fold(head_value, |head_value, elim| {
+            let head_value = Arc::new(LazyValue::new(head_value));
+            let value = LazyValue::apply_elim(head_value, elim.clone());
+            value.force(self.globals).clone()
+        })
+    }
+
+    /// Check that one value is a subtype of another value, solving any
+    /// metavariables that are needed for this to be the case.
+    pub(super) fn unify_subtype(
+        &mut self,
+        local_size: LocalSize,
+        value0: &Arc<Value>,
+        value1: &Arc<Value>,
+    ) -> bool {
+        if semantics::is_subtype(self.globals, local_size, value0, value1) {
+            return true;
+        }
+
+        let value0 = self.force_value(value0);
+        let value1 = self.force_value(value1);
+
+        match (value0.as_ref(), value1.as_ref()) {
+            (Value::Stuck(Head::Meta(_), _), _) | (_, Value::Stuck(Head::Meta(_), _)) => {
+                self.unify(local_size, &value0, &value1)
+            }
+
+            (Value::TypeType(level0), Value::TypeType(level1)) => level0 <= level1,
+
+            (
+                Value::FunctionType(plicity0, usage0, _, input_type0, output_closure0),
+                Value::FunctionType(plicity1, usage1, _, input_type1, output_closure1),
+            ) => {
+                if plicity0 != plicity1
+                    || usage0 != usage1
+                    || !self.unify_subtype(local_size, input_type1, input_type0)
+                {
+                    return false;
+                }
+
+                let local = Arc::new(Value::local(local_size.next_level(), []));
+                let output_type0 = output_closure0.apply(self.globals, local.clone());
+                let output_type1 = output_closure1.apply(self.globals, local);
+                self.unify_subtype(local_size.increment(), &output_type0, &output_type1)
+            }
+
+            (Value::RecordType(closure0), Value::RecordType(closure1)) => {
+                semantics::compare_record_subtype_entries(
+                    self.globals,
+                    local_size,
+                    closure0,
+                    closure1,
+                    |local_size, entry_type0, entry_type1| {
+                        self.unify_subtype(local_size, entry_type0, entry_type1)
+                    },
+                )
+            }
+
+            (Value::VariantType(type_entries0), Value::VariantType(type_entries1)) => {
+                type_entries0.iter().all(|(label0, type0)| {
+                    match type_entries1.iter().find(|(label1, _)| label0 == label1) {
+                        Some((_, type1)) => self.unify_subtype(local_size, type0, type1),
+                        None => false,
+                    }
+                })
+            }
+
+            (_, _) => self.unify(local_size, &value0, &value1),
+        }
+    }
+
+    /// Check that one value is equal to another value, solving any
+    /// metavariables that are needed for this to be the case.
+    pub(super) fn unify(
+        &mut self,
+        local_size: LocalSize,
+        value0: &Arc<Value>,
+        value1: &Arc<Value>,
+    ) -> bool {
+        if semantics::is_equal(self.globals, local_size, value0, value1) {
+            return true;
+        }
+
+        let value0 = self.force_value(value0);
+        let value1 = self.force_value(value1);
+
+        match (value0.as_ref(), value1.as_ref()) {
+            (
+                Value::Stuck(Head::Meta(index0), spine0),
+                Value::Stuck(Head::Meta(index1), spine1),
+            ) if index0 == index1 => {
+                self.unify_spines(local_size, &Head::Meta(*index0), spine0, spine1)
+            }
+            (Value::Stuck(Head::Meta(index), spine), _) => {
+                self.solve_meta(local_size, *index, spine, &value1)
+            }
+            (_, Value::Stuck(Head::Meta(index), spine)) => {
+                self.solve_meta(local_size, *index, spine, &value0)
+            }
+            (Value::Stuck(head0, spine0), Value::Stuck(head1, spine1)) => {
+                let head_value0 = Value::Stuck(head0.clone(), Vec::new());
+                let head_value1 = Value::Stuck(head1.clone(), Vec::new());
+
+                semantics::is_equal(self.globals, local_size, &head_value0, &head_value1)
+                    && self.unify_spines(local_size, head0, spine0, spine1)
+            }
+
+            (
+                Value::FunctionType(plicity0, usage0, _, input_type0, output_closure0),
+                Value::FunctionType(plicity1, usage1, _, input_type1, output_closure1),
+            ) => {
+                if plicity0 != plicity1
+                    || usage0 != usage1
+                    || !self.unify(local_size, input_type0, input_type1)
+                {
+                    return false;
+                }
+
+                let local = Arc::new(Value::local(local_size.next_level(), []));
+                let output_type0 = output_closure0.apply(self.globals, local.clone());
+                let output_type1 = output_closure1.apply(self.globals, local);
+                self.unify(local_size.increment(), &output_type0, &output_type1)
+            }
+            (Value::FunctionTerm(_, output_closure0), Value::FunctionTerm(_, output_closure1)) => {
+                let local = Arc::new(Value::local(local_size.next_level(), []));
+                let output_term0 = output_closure0.apply(self.globals, local.clone());
+                let output_term1 = output_closure1.apply(self.globals, local);
+                self.unify(local_size.increment(), &output_term0, &output_term1)
+            }
+            (Value::FunctionTerm(_, output_closure0), _) => {
+                let local = Arc::new(Value::local(local_size.next_level(), []));
+                let output_term0 = output_closure0.apply(self.globals, local.clone());
+                let input = Arc::new(LazyValue::new(local));
+                let output_term1 = self.apply_spine(value1.clone(), &[Elim::Function(input)]);
+                self.unify(local_size.increment(), &output_term0, &output_term1)
+            }
+            (_, Value::FunctionTerm(_, output_closure1)) => {
+                let local = Arc::new(Value::local(local_size.next_level(), []));
+                let input = Arc::new(LazyValue::new(local.clone()));
+                let output_term0 = self.apply_spine(value0.clone(), &[Elim::Function(input)]);
+                let output_term1 = output_closure1.apply(self.globals, local);
+                self.unify(local_size.increment(), &output_term0, &output_term1)
+            }
+
+            (Value::RecordType(closure0), Value::RecordType(closure1)) => {
+                let usages0 = closure0.entries().iter().map(|(_, _, usage, _, _)| usage);
+                let usages1 = closure1.entries().iter().map(|(_, _, usage, _, _)| usage);
+                Iterator::eq(usages0, usages1)
+                    && self.unify_record_entries(local_size, closure0, closure1)
+            }
+            (Value::RecordTerm(closure0), Value::RecordTerm(closure1)) => {
+                self.unify_record_entries(local_size, closure0, closure1)
+            }
+
+            (Value::VariantType(type_entries0), Value::VariantType(type_entries1)) => {
+                type_entries0.len() == type_entries1.len()
+                    && type_entries0.iter().all(|(label0, type0)| {
+                        match type_entries1.iter().find(|(label1, _)| label0 == label1) {
+                            Some((_, type1)) => self.unify(local_size, type0, type1),
+                            None => false,
+                        }
+                    })
+            }
+            (Value::VariantTerm(label0, value0), Value::VariantTerm(label1, value1)) => {
+                label0 == label1 && self.unify(local_size, value0, value1)
+            }
+
+            (
+                Value::InductiveTerm(type0, label0, inputs0),
+                Value::InductiveTerm(type1, label1, inputs1),
+            ) => {
+                label0 == label1
+                    && inputs0.len() == inputs1.len()
+                    && self.unify(local_size, type0, type1)
+                    && Iterator::zip(inputs0.iter(), inputs1.iter())
+                        .all(|(input0, input1)| self.unify(local_size, input0, input1))
+            }
+
+            (Value::EqType(type0, lhs0, rhs0), Value::EqType(type1, lhs1, rhs1)) => {
+                self.unify(local_size, type0, type1)
+                    && self.unify(local_size, lhs0, lhs1)
+                    && self.unify(local_size, rhs0, rhs1)
+            }
+
+            (Value::CodeType(type0), Value::CodeType(type1)) => {
+                self.unify(local_size, type0, type1)
+            }
+            (Value::CodeTerm(value0), Value::CodeTerm(value1)) => {
+                self.unify(local_size, value0, value1)
+            }
+
+            (Value::ArrayTerm(value_entries0), Value::ArrayTerm(value_entries1))
+            | (Value::ListTerm(value_entries0), Value::ListTerm(value_entries1)) => {
+                value_entries0.len() == value_entries1.len()
+                    && Iterator::zip(value_entries0.iter(), value_entries1.iter()).all(
+                        |(value_entry0, value_entry1)| {
+                            self.unify(local_size, value_entry0, value_entry1)
+                        },
+                    )
+            }
+
+            // Anything else has already been compared by `semantics::is_equal`.
+            (_, _) => false,
+        }
+    }
+
+    /// Unify the spines of two stuck values with equal heads.
+    fn unify_spines(
+        &mut self,
+        local_size: LocalSize,
+        head: &Head,
+        spine0: &[Elim],
+        spine1: &[Elim],
+    ) -> bool {
+        if spine0.len() != spine1.len() {
+            return false;
+        }
+
+        Iterator::zip(spine0.iter(), spine1.iter()).all(|(elim0, elim1)| match (elim0, elim1) {
+            (Elim::Function(input0), Elim::Function(input1)) => {
+                let input0 = input0.force(self.globals).clone();
+                let input1 = input1.force(self.globals).clone();
+                self.unify(local_size, &input0, &input1)
+            }
+            (Elim::Eq(motive0, value0), Elim::Eq(motive1, value1)) => {
+                self.unify(local_size, motive0, motive1) && self.unify(local_size, value0, value1)
+            }
+            (Elim::Code, Elim::Code) => true,
+            (_, _) => {
+                // Compare the remaining eliminators using a common head.
+                let value0 = Value::Stuck(head.clone(), vec![elim0.clone()]);
+                let value1 = Value::Stuck(head.clone(), vec![elim1.clone()]);
+                semantics::is_equal(self.globals, local_size, &value0, &value1)
+            }
+        })
+    }
+
+    /// Unify the entries of two record closures, in order.
+    fn unify_record_entries<Entry: RecordEntry>(
+        &mut self,
+        local_size: LocalSize,
+        closure0: &RecordClosure<Entry>,
+        closure1: &RecordClosure<Entry>,
+    ) -> bool {
+        let entries0 = self.record_entries(local_size, closure0);
+        let entries1 = self.record_entries(local_size, closure1);
+        if entries0.len() != entries1.len() {
+            return false;
+        }
+
+        let mut local_size = local_size;
+        for ((label0, entry_value0), (label1, entry_value1)) in
+            Iterator::zip(entries0.iter(), entries1.iter())
+        {
+            if label0 != label1 || !self.unify(local_size, entry_value0, entry_value1) {
+                return false;
+            }
+            local_size = local_size.increment();
+        }
+
+        true
+    }
+
+    /// Evaluate the entries of a record closure, binding each entry to a fresh
+    /// local variable in the subsequent entries.
+    fn record_entries<'closure, Entry: RecordEntry>(
+        &self,
+        local_size: LocalSize,
+        closure: &'closure RecordClosure<Entry>,
+    ) -> Vec<(&'closure str, Arc<Value>)> {
+        let mut local_size = local_size;
+        let mut entries = Vec::new();
+
+        closure.for_each_entry(self.globals, |label, entry_value| {
+            entries.push((label, entry_value));
+            let local = Arc::new(Value::local(local_size.next_level(), []));
+            local_size = local_size.increment();
+            local
+        });
+
+        entries
+    }
+
+    /// Attempt to solve a metavariable applied to a spine of distinct local
+    /// variables, returning `false` if this was not possible.
+    fn solve_meta(
+        &mut self,
+        local_size: LocalSize,
+        index: MetaIndex,
+        spine: &[Elim],
+        value: &Arc<Value>,
+    ) -> bool {
+        let mut renaming = Vec::with_capacity(spine.len());
+        for elim in spine {
+            let input = match elim {
+                Elim::Function(input) => self.force_value(input.force(self.globals)),
+                _ => return false,
+            };
+            match input.as_ref() {
+                Value::Stuck(Head::Local(level), spine)
+                    if spine.is_empty() && !renaming.contains(level) =>
+                {
+                    renaming.push(*level);
+                }
+                _ => return false,
+            }
+        }
+
+        let term = self.read_back_value_at(local_size, value);
+        let term = match self.rename_term(index, local_size, &renaming, 0, &term) {
+            Some(term) => term,
+            None => return false,
+        };
+        let solution = (renaming.iter()).fold(term, |term, _| {
+            core::Term::generated(core::TermData::FunctionTerm("_".to_owned(), Arc::new(term)))
+        });
+        let solution_value = semantics::eval_term(
+            self.globals,
+            UniverseOffset(0),
+            &mut Locals::new(),
+            &solution,
+        );
+
+        self.metas[index.0 as usize].solution = Some((Arc::new(solution), solution_value));
+        true
+    }
+
+    /// Read back a value at the given size of the local environment,
+    /// substituting the solutions of metavariables.
+    pub(super) fn read_back_value_at(&self, local_size: LocalSize, value: &Value) -> core::Term {
+        let term = semantics::read_back_value(self.globals, local_size, Unfold::Never, value);
+        self.zonk_term(&term)
+    }
+
+    /// Rename the free local variables in a term to refer to the inputs of the
+    /// solution of a metavariable, returning `None` if the term refers to
+    /// local variables that are not in the renaming, or to the metavariable
+    /// itself.
+    fn rename_term(
+        &self,
+        meta_index: MetaIndex,
+        local_size: LocalSize,
+        renaming: &[LocalLevel],
+        depth: u32,
+        term: &core::Term,
+    ) -> Option<core::Term> {
+        match &term.data {
+            core::TermData::Local(local_index) if local_index.0 < depth => Some(term.clone()),
+            core::TermData::Local(local_index) => {
+                let level = core::LocalIndex(local_index.0 - depth).to_level(local_size)?;
+                let position = renaming.iter().position(|l| *l == level)?;
+                let local_index = core::LocalIndex((renaming.len() - position - 1) as u32 + depth);
+                Some(core::Term::new(
+                    term.location,
+                    core::TermData::Local(local_index),
+                ))
+            }
+            core::TermData::Meta(index) if *index == meta_index => None,
+            _ => term.try_map_subterms(depth, &mut |term, depth| {
+                self.rename_term(meta_index, local_size, renaming, depth, term)
+            }),
+        }
+    }
+
+    /// Replace the solved metavariables in a term with their solutions.
+    ///
+    /// The redexes introduced by substituting the solutions are reduced, but
+    /// the redexes that were already present in the term are left alone.
+    pub(super) fn zonk_term(&self, term: &core::Term) -> core::Term {
+        match &term.data {
+            core::TermData::Meta(index) => match &self.metas[index.0 as usize].solution {
+                Some((solution, _)) => self.zonk_term(solution),
+                None => term.clone(),
+            },
+            core::TermData::FunctionElim(_, _) => {
+                let mut head_term = term;
+                let mut input_terms = Vec::new();
+                while let core::TermData::FunctionElim(next_head_term, input_term) = &head_term.data
+                {
+                    input_terms.push((head_term.location, self.zonk_term(input_term)));
+                    head_term = next_head_term;
+                }
+                input_terms.reverse();
+
+                let solution = match &head_term.data {
+                    core::TermData::Meta(index) => self.metas[index.0 as usize].solution.as_ref(),
+                    _ => None,
+                };
+                let (mut term, input_terms) = match solution {
+                    // Apply the solution to as many of the inputs as it has
+                    // function terms for, substituting them into its body.
+                    Some((solution, _)) => {
+                        let solution = self.zonk_term(solution);
+                        let mut output_term = &solution;
+                        let mut input_count = 0;
+                        while let core::TermData::FunctionTerm(_, next_output_term) =
+                            &output_term.data
+                        {
+                            if input_count == input_terms.len() {
+                                break;
+                            }
+                            output_term = next_output_term;
+                            input_count += 1;
+                        }
+
+                        let (substituted_inputs, input_terms) = input_terms.split_at(input_count);
+                        let substituted_inputs = substituted_inputs.iter();
+                        let substituted_inputs: Vec<_> =
+                            substituted_inputs.map(|(_, term)| term).collect();
+                        let term = substitute_term(&substituted_inputs, 0, output_term);
+                        (term, input_terms.to_vec())
+                    }
+                    None => (self.zonk_term(head_term), input_terms),
+                };
+
+                for (location, input_term) in input_terms {
+                    term = core::Term::new(
+                        location,
+                        core::TermData::FunctionElim(Arc::new(term), Arc::new(input_term)),
+                    );
+                }
+                term
+            }
+            _ => {
+                let term = term.try_map_subterms(0, &mut |term, _| Some(self.zonk_term(term)));
+                term.unwrap() // Zonking does not fail
+            }
+        }
+    }
+}
+
+/// Substitute terms for the local variables that are bound outside of a term,
+/// where the last of the input terms replaces the most recently bound local.
+///
+/// The `depth` is the number of variables bound inside the term so far.
+fn substitute_term(input_terms: &[&core::Term], depth: u32, term: &core::Term) -> core::Term {
+    match &term.data {
+        core::TermData::Local(local_index) if local_index.0 >= depth => {
+            let position = input_terms.len() - (local_index.0 - depth) as usize - 1;
+            shift_term(depth, 0, input_terms[position])
+        }
+        _ => {
+            let term = term.try_map_subterms(depth, &mut |term, depth| {
+                Some(substitute_term(input_terms, depth, term))
+            });
+            term.unwrap() // Substitution does not fail
+        }
+    }
+}
+
+/// Shift the local variables that are bound outside of a term, so that the
+/// term can be used underneath `amount` more bindings.
+///
+/// The `depth` is the number of variables bound inside the term so far.
+fn shift_term(amount: u32, depth: u32, term: &core::Term) -> core::Term {
+    match &term.data {
+        core::TermData::Local(local_index) if local_index.0 >= depth => core::Term::new(
+            term.location,
+            core::TermData::Local(core::LocalIndex(local_index.0 + amount)),
+        ),
+        _ => {
+            let term = term.try_map_subterms(depth, &mut |term, depth| {
+                Some(shift_term(amount, depth, term))
+            });
+            term.unwrap() // Shifting does not fail
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lang::core::{Globals, LocalIndex, Term, TermData};
+    use codespan_reporting::files::SimpleFiles;
+    use std::cell::RefCell;
+
+    fn local(index: u32) -> Arc<Term> {
+        Arc::new(Term::generated(TermData::Local(LocalIndex(index))))
+    }
+
+    fn function_term(output_term: Arc<Term>) -> Arc<Term> {
+        Arc::new(Term::generated(TermData::FunctionTerm(
+            "_".to_owned(),
+            output_term,
+        )))
+    }
+
+    fn function_elim(head_term: Arc<Term>, input_term: Arc<Term>) -> Arc<Term> {
+        Arc::new(Term::generated(TermData::FunctionElim(
+            head_term, input_term,
+        )))
+    }
+
+    /// Zonk a term in a local environment with a single parameter, where the
+    /// term contains a metavariable that is solved with `solution`.
+    fn zonk_with_solution(solution: Arc<Term>, term: impl FnOnce(Arc<Term>) -> Arc<Term>) -> Term {
+        let globals = Globals::default();
+        let files = RefCell::new(SimpleFiles::new());
+        let (message_tx, _message_rx) = crossbeam_channel::unbounded();
+        let mut state = State::new(&globals, &files, message_tx);
+
+        state.push_local_param(None, Arc::new(Value::Error));
+        let (meta_term, _) = state.fresh_meta(Location::generated(), MetaSource::Hole);
+        let solution_value = state.eval_term(&solution);
+        state.metas[0].solution = Some((solution, solution_value));
+
+        state.zonk_term(&term(Arc::new(meta_term)))
+    }
+
+    #[test]
+    fn zonk_reduces_solution_redexes() {
+        // `?m x` where `?m := fun a => a` zonks to `x`
+        let term = zonk_with_solution(function_term(local(0)), |meta_term| meta_term);
+
+        assert_eq!(format!("{:?}", term), format!("{:?}", local(0)));
+    }
+
+    #[test]
+    fn zonk_shifts_substituted_inputs() {
+        // `fun z => ?m x` where `?m := fun a b => a` zonks to `fun z b => x`
+        let solution = function_term(function_term(local(1)));
+        let term = zonk_with_solution(solution, |meta_term| {
+            // The meta term was created outside of `z`
+            let meta_term = Arc::new(shift_term(1, 0, &meta_term));
+            function_term(meta_term)
+        });
+
+        let expected = function_term(function_term(local(2)));
+        assert_eq!(format!("{:?}", term), format!("{:?}", expected));
+    }
+
+    #[test]
+    fn zonk_preserves_existing_redexes() {
+        // `(fun y => y) (?m x)` where `?m := fun a => a` zonks to
+        // `(fun y => y) x`
+        let term = zonk_with_solution(function_term(local(0)), |meta_term| {
+            function_elim(function_term(local(0)), meta_term)
+        });
+
+        let expected = function_elim(function_term(local(0)), local(0));
+        assert_eq!(format!("{:?}", term), format!("{:?}", expected));
+    }
+}
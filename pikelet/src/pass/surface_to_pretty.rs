@@ -4,7 +4,8 @@
 
 use pretty::{DocAllocator, DocBuilder};
 
-use crate::lang::surface::{Term, TermData};
+use crate::lang::surface::{Branch, Term, TermData, TermEntry};
+use crate::lang::{Plicity, Usage};
 
 /// The precedence of a term.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -31,6 +32,8 @@ where
 {
     match &term.data {
         TermData::Name(name) => alloc.text(name),
+        TermData::Hole(name) => alloc.text("?").append(alloc.text(name)),
+        TermData::TypeOmega => alloc.text("Typeω"),
 
         TermData::Ann(term, r#type) => paren(
             alloc,
@@ -47,44 +50,89 @@ where
                 ),
         ),
 
-        TermData::Lift(term, shift) => (alloc.nil())
-            .append(from_term_prec(alloc, term, Prec::Atomic))
-            .append("^")
-            .append(shift.to_string()),
+        TermData::Import(path) => (alloc.nil())
+            .append("import")
+            .append(alloc.space())
+            .append(alloc.text(path)),
 
-        TermData::FunctionType(input_type_groups, output_type) => paren(
+        TermData::Let(name, def_type, def_term, output_term) => paren(
             alloc,
-            prec > Prec::Arrow,
+            prec > Prec::Term,
             (alloc.nil())
-                .append("Fun")
-                .append(alloc.space())
-                .append(alloc.intersperse(
-                    input_type_groups.iter().map(|(input_names, input_type)| {
-                        (alloc.nil())
-                            .append("(")
-                            .append(alloc.intersperse(
-                                input_names.iter().map(|input_name| &input_name.data),
-                                alloc.space(),
-                            ))
-                            .append(alloc.space())
-                            .append(":")
-                            .append(alloc.space())
-                            .append(from_term_prec(alloc, input_type, Prec::Term))
-                            .append(")")
-                    }),
-                    alloc.space(),
-                ))
+                .append("let")
                 .append(alloc.space())
-                .append("->")
+                .append(&name.data)
+                .append(match def_type {
+                    None => alloc.nil(),
+                    Some(def_type) => (alloc.nil())
+                        .append(alloc.space())
+                        .append(":")
+                        .append(alloc.space())
+                        .append(from_term_prec(alloc, def_type, Prec::Arrow)),
+                })
+                .append(alloc.space())
+                .append("=")
                 .group()
                 .append(
-                    (alloc.nil()).append(alloc.space()).append(
-                        from_term_prec(alloc, output_type, Prec::Arrow)
-                            .group()
-                            .nest(4),
-                    ),
-                ),
+                    (alloc.space())
+                        .append(from_term_prec(alloc, def_term, Prec::Term))
+                        .group()
+                        .nest(4),
+                )
+                .append(alloc.space())
+                .append("in")
+                .group()
+                .append(alloc.space())
+                .append(from_term_prec(alloc, output_term, Prec::Term)),
         ),
+
+        TermData::Lift(term, shift) => (alloc.nil())
+            .append(from_term_prec(alloc, term, Prec::Atomic))
+            .append("^")
+            .append(shift.to_string()),
+
+        TermData::FunctionType(input_type_groups, output_type) => {
+            paren(
+                alloc,
+                prec > Prec::Arrow,
+                (alloc.nil())
+                    .append("Fun")
+                    .append(alloc.space())
+                    .append(alloc.intersperse(
+                        input_type_groups.iter().map(
+                            |(plicity, usage, input_names, input_type)| {
+                                let (open, close) = match plicity {
+                                    Plicity::Explicit => ("(", ")"),
+                                    Plicity::Implicit => ("{", "}"),
+                                };
+                                (alloc.nil())
+                                    .append(open)
+                                    .append(usage_prefix(alloc, *usage))
+                                    .append(alloc.intersperse(
+                                        input_names.iter().map(|input_name| &input_name.data),
+                                        alloc.space(),
+                                    ))
+                                    .append(alloc.space())
+                                    .append(":")
+                                    .append(alloc.space())
+                                    .append(from_term_prec(alloc, input_type, Prec::Term))
+                                    .append(close)
+                            },
+                        ),
+                        alloc.space(),
+                    ))
+                    .append(alloc.space())
+                    .append("->")
+                    .group()
+                    .append(
+                        (alloc.nil()).append(alloc.space()).append(
+                            from_term_prec(alloc, output_type, Prec::Arrow)
+                                .group()
+                                .nest(4),
+                        ),
+                    ),
+            )
+        }
         TermData::FunctionArrowType(input_type, output_type) => paren(
             alloc,
             prec > Prec::Arrow,
@@ -95,16 +143,39 @@ where
                 .append(alloc.space())
                 .append(from_term_prec(alloc, output_type, Prec::Arrow)),
         ),
-        TermData::FunctionTerm(input_names, output_term) => paren(
+        TermData::FunctionTerm(input_name_groups, output_term) => paren(
             alloc,
             prec > Prec::Expr,
             (alloc.nil())
                 .append("fun")
                 .append(alloc.space())
-                .append(alloc.intersperse(
-                    input_names.iter().map(|input_name| &input_name.data),
-                    alloc.space(),
-                ))
+                .append(
+                    alloc.intersperse(
+                        input_name_groups
+                            .iter()
+                            .map(|(plicity, input_names, input_type)| {
+                                let input_names = alloc.intersperse(
+                                    input_names.iter().map(|input_name| &input_name.data),
+                                    alloc.space(),
+                                );
+                                let input_names = match input_type {
+                                    None => input_names,
+                                    Some(input_type) => (alloc.nil())
+                                        .append(input_names)
+                                        .append(alloc.space())
+                                        .append(":")
+                                        .append(alloc.space())
+                                        .append(from_term_prec(alloc, input_type, Prec::Term)),
+                                };
+                                match (plicity, input_type) {
+                                    (Plicity::Explicit, None) => input_names,
+                                    (Plicity::Explicit, Some(_)) => input_names.enclose("(", ")"),
+                                    (Plicity::Implicit, _) => input_names.enclose("{", "}"),
+                                }
+                            }),
+                        alloc.space(),
+                    ),
+                )
                 .append(alloc.space())
                 .append("=>")
                 .group()
@@ -116,16 +187,46 @@ where
                     ),
                 ),
         ),
+        TermData::Fix(name, term) => paren(
+            alloc,
+            prec > Prec::Expr,
+            (alloc.nil())
+                .append("fix")
+                .append(alloc.space())
+                .append(&name.data)
+                .append(alloc.space())
+                .append("=>")
+                .group()
+                .append(
+                    (alloc.nil())
+                        .append(alloc.space())
+                        .append(from_term_prec(alloc, term, Prec::Expr).group().nest(4)),
+                ),
+        ),
         TermData::FunctionElim(head_term, input_terms) => paren(
             alloc,
             prec > Prec::App,
             from_term_prec(alloc, head_term, Prec::App).append(
                 (alloc.nil())
-                    .append(alloc.concat(input_terms.iter().map(|input_term| {
-                        alloc
-                            .space()
-                            .append(from_term_prec(alloc, input_term, Prec::Arrow))
-                    })))
+                    .append(
+                        alloc.concat(input_terms.iter().map(|(input_name, input_term)| {
+                            match input_name {
+                                None => alloc.space().append(from_term_prec(
+                                    alloc,
+                                    input_term,
+                                    Prec::Atomic,
+                                )),
+                                Some(input_name) => (alloc.space())
+                                    .append("{")
+                                    .append(&input_name.data)
+                                    .append(alloc.space())
+                                    .append("=")
+                                    .append(alloc.space())
+                                    .append(from_term_prec(alloc, input_term, Prec::Term))
+                                    .append("}"),
+                            }
+                        })),
+                    )
                     .group()
                     .nest(4),
             ),
@@ -136,10 +237,12 @@ where
             .append(alloc.space())
             .append("{")
             .group()
-            .append(
-                alloc.concat(type_entries.iter().map(|(label, name, entry_type)| {
+            .append(alloc.concat(type_entries.iter().map(
+                |(docs, usage, label, name, entry_type, default_term)| {
                     (alloc.nil())
                         .append(alloc.hardline())
+                        .append(doc_comments(alloc, docs))
+                        .append(usage_prefix(alloc, *usage))
                         .append(match name {
                             None => alloc.text(&label.data).append(alloc.space()),
                             Some(name) => alloc
@@ -155,53 +258,241 @@ where
                         .append(
                             (alloc.space())
                                 .append(from_term_prec(alloc, entry_type, Prec::Term))
+                                .append(match default_term {
+                                    None => alloc.nil(),
+                                    Some(default_term) => (alloc.space())
+                                        .append("=")
+                                        .append(alloc.space())
+                                        .append(from_term_prec(alloc, default_term, Prec::Term)),
+                                })
                                 .append(",")
                                 .group()
                                 .nest(4),
                         )
                         .nest(4)
                         .group()
-                })),
-            )
+                },
+            )))
             .append("}"),
         TermData::RecordTerm(term_entries) => (alloc.nil())
             .append("record")
             .append(alloc.space())
             .append("{")
             .group()
-            .append(
-                alloc.concat(term_entries.iter().map(|(label, name, entry_term)| {
-                    (alloc.nil())
-                        .append(alloc.hardline())
-                        .append(match name {
-                            None => alloc.text(&label.data).append(alloc.space()),
-                            Some(name) => alloc
-                                .text(&label.data)
-                                .append(alloc.space())
-                                .append("as")
-                                .append(alloc.space())
-                                .append(&name.data)
-                                .append(alloc.space()),
-                        })
-                        .append("=")
-                        .group()
-                        .append(
-                            (alloc.space())
-                                .append(from_term_prec(alloc, entry_term, Prec::Term))
-                                .append(",")
-                                .group()
-                                .nest(4),
-                        )
-                        .nest(4)
-                        .group()
-                })),
-            )
+            .append(from_term_entries(alloc, term_entries))
             .append("}"),
+        TermData::RecordUpdate(head_term, term_entries) => paren(
+            alloc,
+            prec > Prec::App,
+            (alloc.nil())
+                .append(from_term_prec(alloc, head_term, Prec::App))
+                .append(alloc.space())
+                .append("with")
+                .append(alloc.space())
+                .append("{")
+                .group()
+                .append(from_term_entries(alloc, term_entries))
+                .append("}"),
+        ),
         TermData::RecordElim(head_term, label) => (alloc.nil())
             .append(from_term_prec(alloc, head_term, Prec::Atomic))
             .append(".")
             .append(&label.data),
 
+        TermData::EnumType(labels) => (alloc.nil())
+            .append("Enum")
+            .append(alloc.space())
+            .append("{")
+            .append(match labels.len() {
+                0 => alloc.nil(),
+                _ => (alloc.space())
+                    .append(alloc.intersperse(
+                        labels.iter().map(|label| &label.data),
+                        alloc.text(",").append(alloc.space()),
+                    ))
+                    .append(alloc.space())
+                    .nest(4),
+            })
+            .append("}")
+            .group(),
+        TermData::EnumTerm(label) => (alloc.nil())
+            .append("enum")
+            .append(alloc.space())
+            .append(&label.data),
+        TermData::EnumElim(head_term, branches) => (alloc.nil())
+            .append("match")
+            .append(alloc.space())
+            .append(from_term_prec(alloc, head_term, Prec::Term))
+            .append(alloc.space())
+            .append("{")
+            .group()
+            .append(from_branches(alloc, branches))
+            .append("}"),
+
+        TermData::VariantType(type_entries) => (alloc.nil())
+            .append("Variant")
+            .append(alloc.space())
+            .append("{")
+            .group()
+            .append(alloc.concat(type_entries.iter().map(|(label, entry_type)| {
+                (alloc.nil())
+                    .append(alloc.hardline())
+                    .append(&label.data)
+                    .append(alloc.space())
+                    .append(":")
+                    .group()
+                    .append(
+                        (alloc.space())
+                            .append(from_term_prec(alloc, entry_type, Prec::Term))
+                            .append(",")
+                            .group()
+                            .nest(4),
+                    )
+                    .nest(4)
+                    .group()
+            })))
+            .append("}"),
+        TermData::VariantTerm(label, term) => paren(
+            alloc,
+            prec > Prec::App,
+            (alloc.nil())
+                .append("variant")
+                .append(alloc.space())
+                .append(&label.data)
+                .append(alloc.space())
+                .append(from_term_prec(alloc, term, Prec::Atomic)),
+        ),
+
+        TermData::InductiveType(name, constructors) => (alloc.nil())
+            .append("Inductive")
+            .append(alloc.space())
+            .append(&name.data)
+            .append(alloc.space())
+            .append("{")
+            .group()
+            .append(alloc.concat(constructors.iter().map(|(label, r#type)| {
+                (alloc.nil())
+                    .append(alloc.hardline())
+                    .append(&label.data)
+                    .append(alloc.space())
+                    .append(":")
+                    .group()
+                    .append(
+                        (alloc.space())
+                            .append(from_term_prec(alloc, r#type, Prec::Term))
+                            .append(",")
+                            .group()
+                            .nest(4),
+                    )
+                    .nest(4)
+                    .group()
+            })))
+            .append("}"),
+        TermData::InductiveElim(head_term, motive, branches) => (alloc.nil())
+            .append("elim")
+            .append(alloc.space())
+            .append(from_term_prec(alloc, motive, Prec::Atomic))
+            .append(alloc.space())
+            .append(from_term_prec(alloc, head_term, Prec::Atomic))
+            .append(alloc.space())
+            .append("{")
+            .group()
+            .append(from_branches(alloc, branches))
+            .append("}"),
+
+        TermData::BoolElim(head_term, if_true, if_false) => paren(
+            alloc,
+            prec > Prec::Term,
+            (alloc.nil())
+                .append("if")
+                .append(alloc.space())
+                .append(from_term_prec(alloc, head_term, Prec::Term))
+                .append(alloc.space())
+                .append("then")
+                .group()
+                .append(
+                    (alloc.space())
+                        .append(from_term_prec(alloc, if_true, Prec::Term))
+                        .group()
+                        .nest(4),
+                )
+                .append(alloc.space())
+                .append("else")
+                .group()
+                .append(
+                    (alloc.space())
+                        .append(from_term_prec(alloc, if_false, Prec::Term))
+                        .group()
+                        .nest(4),
+                ),
+        ),
+
+        TermData::EqType(r#type, lhs, rhs) => paren(
+            alloc,
+            prec > Prec::App,
+            (alloc.nil()).append("Eq").append(
+                (alloc.nil())
+                    .append(alloc.space())
+                    .append(from_term_prec(alloc, r#type, Prec::Atomic))
+                    .append(alloc.space())
+                    .append(from_term_prec(alloc, lhs, Prec::Atomic))
+                    .append(alloc.space())
+                    .append(from_term_prec(alloc, rhs, Prec::Atomic))
+                    .group()
+                    .nest(4),
+            ),
+        ),
+        TermData::EqTerm => alloc.text("refl"),
+        TermData::EqElim(head_term, motive, term) => paren(
+            alloc,
+            prec > Prec::App,
+            (alloc.nil()).append("transport").append(
+                (alloc.nil())
+                    .append(alloc.space())
+                    .append(from_term_prec(alloc, motive, Prec::Atomic))
+                    .append(alloc.space())
+                    .append(from_term_prec(alloc, head_term, Prec::Atomic))
+                    .append(alloc.space())
+                    .append(from_term_prec(alloc, term, Prec::Atomic))
+                    .group()
+                    .nest(4),
+            ),
+        ),
+
+        TermData::CodeType(term) => paren(
+            alloc,
+            prec > Prec::App,
+            (alloc.nil()).append("Code").append(
+                (alloc.nil())
+                    .append(alloc.space())
+                    .append(from_term_prec(alloc, term, Prec::Atomic))
+                    .group()
+                    .nest(4),
+            ),
+        ),
+        TermData::CodeTerm(term) => paren(
+            alloc,
+            prec > Prec::App,
+            (alloc.nil()).append("quote").append(
+                (alloc.nil())
+                    .append(alloc.space())
+                    .append(from_term_prec(alloc, term, Prec::Atomic))
+                    .group()
+                    .nest(4),
+            ),
+        ),
+        TermData::CodeElim(term) => paren(
+            alloc,
+            prec > Prec::App,
+            (alloc.nil()).append("splice").append(
+                (alloc.nil())
+                    .append(alloc.space())
+                    .append(from_term_prec(alloc, term, Prec::Atomic))
+                    .group()
+                    .nest(4),
+            ),
+        ),
+
         TermData::SequenceTerm(term_entries) => (alloc.nil())
             .append("[")
             .group()
@@ -234,3 +525,86 @@ where
         doc
     }
 }
+
+fn from_term_entries<'a, D>(alloc: &'a D, term_entries: &'a [TermEntry]) -> DocBuilder<'a, D>
+where
+    D: DocAllocator<'a>,
+    D::Doc: Clone,
+{
+    alloc.concat(term_entries.iter().map(|(label, name, entry_term)| {
+        (alloc.nil())
+            .append(alloc.hardline())
+            .append(match name {
+                None => alloc.text(&label.data).append(alloc.space()),
+                Some(name) => alloc
+                    .text(&label.data)
+                    .append(alloc.space())
+                    .append("as")
+                    .append(alloc.space())
+                    .append(&name.data)
+                    .append(alloc.space()),
+            })
+            .append("=")
+            .group()
+            .append(
+                (alloc.space())
+                    .append(from_term_prec(alloc, entry_term, Prec::Term))
+                    .append(",")
+                    .group()
+                    .nest(4),
+            )
+            .nest(4)
+            .group()
+    }))
+}
+
+fn from_branches<'a, D>(alloc: &'a D, branches: &'a [Branch]) -> DocBuilder<'a, D>
+where
+    D: DocAllocator<'a>,
+    D::Doc: Clone,
+{
+    alloc.concat(branches.iter().map(|(label, names, branch_term)| {
+        (alloc.nil())
+            .append(alloc.hardline())
+            .append(&label.data)
+            .append(alloc.concat(names.iter().map(|name| alloc.space().append(&name.data))))
+            .append(alloc.space())
+            .append("=>")
+            .group()
+            .append(
+                (alloc.space())
+                    .append(from_term_prec(alloc, branch_term, Prec::Term))
+                    .append(",")
+                    .group()
+                    .nest(4),
+            )
+            .nest(4)
+            .group()
+    }))
+}
+
+/// Prints the usage of a binder, omitting the default usage.
+pub(crate) fn usage_prefix<'a, D>(alloc: &'a D, usage: Usage) -> DocBuilder<'a, D>
+where
+    D: DocAllocator<'a>,
+    D::Doc: Clone,
+{
+    match usage {
+        Usage::Many => alloc.nil(),
+        usage => alloc.as_string(usage).append(alloc.space()),
+    }
+}
+
+/// Prints doc comments, with each line preceded by `|||`.
+pub(crate) fn doc_comments<'a, D>(alloc: &'a D, docs: &'a [String]) -> DocBuilder<'a, D>
+where
+    D: DocAllocator<'a>,
+    D::Doc: Clone,
+{
+    alloc.concat(docs.iter().map(|line| {
+        (alloc.nil())
+            .append("|||")
+            .append(line.as_str())
+            .append(alloc.hardline())
+    }))
+}
@@ -3,7 +3,7 @@
 use codespan_reporting::diagnostic::{Diagnostic, Label};
 use pretty::DocAllocator;
 
-use crate::lang::{core, surface, FileId, Location};
+use crate::lang::{core, surface, FileId, Location, Usage};
 use crate::literal;
 
 /// Global diagnostic messages
@@ -107,6 +107,7 @@ impl Message {
 #[derive(Debug, Clone)]
 pub enum LexerError {
     InvalidToken { location: Location },
+    InvalidUsage { location: Location },
 }
 
 impl LexerError {
@@ -115,6 +116,10 @@ impl LexerError {
             LexerError::InvalidToken { location } => Diagnostic::error()
                 .with_message("invalid token")
                 .with_labels(option_to_vec(primary(location))),
+            LexerError::InvalidUsage { location } => Diagnostic::error()
+                .with_message("invalid usage")
+                .with_labels(option_to_vec(primary(location)))
+                .with_notes(vec!["expected one of `0`, `1`, or `ω`".to_owned()]),
         }
     }
 }
@@ -301,19 +306,29 @@ impl LiteralParseMessage {
 
 #[derive(Clone, Debug)]
 pub enum AmbiguousTerm {
-    NumberLiteral,
     Sequence,
     FunctionTerm,
+    Fix,
     RecordTerm,
+    EnumElim,
+    VariantTerm,
+    VariantElim,
+    EqTerm,
+    Hole,
 }
 
 impl AmbiguousTerm {
     fn description(&self) -> &'static str {
         match self {
-            AmbiguousTerm::NumberLiteral => "numeric literal",
             AmbiguousTerm::Sequence => "sequence",
             AmbiguousTerm::FunctionTerm => "function term",
+            AmbiguousTerm::Fix => "recursive term",
             AmbiguousTerm::RecordTerm => "record term",
+            AmbiguousTerm::EnumElim => "match expression",
+            AmbiguousTerm::VariantTerm => "variant term",
+            AmbiguousTerm::VariantElim => "variant elimination",
+            AmbiguousTerm::EqTerm => "reflexivity proof",
+            AmbiguousTerm::Hole => "hole",
         }
     }
 }
@@ -332,6 +347,14 @@ pub enum CoreTypingMessage {
         name: String,
     },
     UnboundLocal,
+    UnexpectedLocalStage {
+        local_stage: u32,
+        current_stage: u32,
+    },
+    UnexpectedLocalUsage {
+        usage: Usage,
+        found_usage: Usage,
+    },
     InvalidRecordType {
         duplicate_labels: Vec<String>,
     },
@@ -343,10 +366,74 @@ pub enum CoreTypingMessage {
         expected_label: String,
         head_type: core::Term,
     },
+    UnexpectedErasedLabel {
+        label: String,
+    },
     TooManyInputsInFunctionTerm,
     TooManyInputsInFunctionElim {
         head_type: core::Term,
     },
+    InvalidEnumType {
+        duplicate_labels: Vec<String>,
+    },
+    InvalidEnumTerm {
+        label: String,
+        expected_type: core::Term,
+    },
+    InvalidEnumElim {
+        missing_labels: Vec<String>,
+        unexpected_labels: Vec<String>,
+    },
+    UnexpectedEnumElimHead {
+        head_type: core::Term,
+    },
+    InvalidVariantType {
+        duplicate_labels: Vec<String>,
+    },
+    InvalidVariantTerm {
+        label: String,
+        expected_type: core::Term,
+    },
+    InvalidVariantElim {
+        missing_labels: Vec<String>,
+        unexpected_labels: Vec<String>,
+    },
+    UnexpectedVariantElimHead {
+        head_type: core::Term,
+    },
+    InvalidInductiveType {
+        duplicate_labels: Vec<String>,
+    },
+    InvalidConstructorType {
+        label: String,
+    },
+    InvalidInductiveTerm {
+        label: String,
+        expected_count: usize,
+        found_count: usize,
+    },
+    InvalidInductiveBranch {
+        label: String,
+        expected_count: usize,
+        found_count: usize,
+    },
+    InvalidInductiveElim {
+        missing_labels: Vec<String>,
+        unexpected_labels: Vec<String>,
+    },
+    UnexpectedInductiveElimHead {
+        head_type: core::Term,
+    },
+    InvalidEqTerm {
+        expected_type: core::Term,
+    },
+    UnexpectedEqElimHead {
+        head_type: core::Term,
+    },
+    UnexpectedCodeStage,
+    UnexpectedCodeElimHead {
+        head_type: core::Term,
+    },
     UnexpectedArrayTerm {
         expected_type: core::Term,
     },
@@ -384,6 +471,21 @@ impl CoreTypingMessage {
             CoreTypingMessage::UnboundLocal => {
                 Diagnostic::bug().with_message("unbound local variable")
             }
+            CoreTypingMessage::UnexpectedLocalStage {
+                local_stage,
+                current_stage,
+            } => Diagnostic::bug()
+                .with_message("local variable used at an unexpected stage")
+                .with_notes(vec![format!(
+                    "expected a local bound at stage `{}` or later, found a local bound at stage `{}`",
+                    current_stage, local_stage,
+                )]),
+            CoreTypingMessage::UnexpectedLocalUsage { usage, found_usage } => Diagnostic::bug()
+                .with_message("local variable used an unexpected number of times")
+                .with_notes(vec![format!(
+                    "expected a local with usage `{}`, found `{}` uses",
+                    usage, found_usage,
+                )]),
             CoreTypingMessage::InvalidRecordType { duplicate_labels } => Diagnostic::bug()
                 .with_message("invalid record type")
                 .with_notes(
@@ -428,6 +530,8 @@ impl CoreTypingMessage {
                     "eliminating a term of type `{}`",
                     to_doc(head_type).pretty(std::usize::MAX),
                 )]),
+            CoreTypingMessage::UnexpectedErasedLabel { label } => Diagnostic::bug()
+                .with_message(format!("erased label `{}` used at runtime", label)),
             CoreTypingMessage::TooManyInputsInFunctionTerm => {
                 Diagnostic::bug().with_message("too many inputs in function term")
             }
@@ -437,6 +541,196 @@ impl CoreTypingMessage {
                     "eliminating a term of type `{}`",
                     to_doc(head_type).pretty(std::usize::MAX),
                 )]),
+            CoreTypingMessage::InvalidEnumType { duplicate_labels } => Diagnostic::bug()
+                .with_message("invalid enumeration type")
+                .with_notes(
+                    duplicate_labels
+                        .iter()
+                        .map(|name| format!("label `{}` was used more than once", name))
+                        .collect(),
+                ),
+            CoreTypingMessage::InvalidEnumTerm {
+                label,
+                expected_type,
+            } => Diagnostic::bug()
+                .with_message("invalid enumeration term")
+                .with_notes(vec![format!(
+                    "label `{}` not found in `{}`",
+                    label,
+                    to_doc(expected_type).pretty(std::usize::MAX),
+                )]),
+            CoreTypingMessage::InvalidEnumElim {
+                missing_labels,
+                unexpected_labels,
+            } => Diagnostic::bug()
+                .with_message("invalid match expression")
+                .with_notes({
+                    let mut notes = Vec::with_capacity(
+                        unexpected_labels.len() + if missing_labels.is_empty() { 0 } else { 1 },
+                    );
+
+                    for label in unexpected_labels {
+                        notes.push(format!("unexpected branch label `{}`", label));
+                    }
+
+                    if !missing_labels.is_empty() {
+                        notes.push(format!(
+                            "missing branches for the labels {} in this match expression",
+                            missing_labels
+                                .iter()
+                                // TODO: reduce string allocations
+                                .map(|label| format!("`{}`", label))
+                                .format(", "),
+                        ));
+                    }
+
+                    notes
+                }),
+            CoreTypingMessage::UnexpectedEnumElimHead { head_type } => Diagnostic::bug()
+                .with_message("unexpected head of match expression")
+                .with_notes(vec![format!(
+                    "expected an enumeration type, found `{}`",
+                    to_doc(head_type).pretty(std::usize::MAX),
+                )]),
+            CoreTypingMessage::InvalidVariantType { duplicate_labels } => Diagnostic::bug()
+                .with_message("invalid variant type")
+                .with_notes(
+                    duplicate_labels
+                        .iter()
+                        .map(|name| format!("label `{}` was used more than once", name))
+                        .collect(),
+                ),
+            CoreTypingMessage::InvalidVariantTerm {
+                label,
+                expected_type,
+            } => Diagnostic::bug()
+                .with_message("invalid variant term")
+                .with_notes(vec![format!(
+                    "label `{}` not found in `{}`",
+                    label,
+                    to_doc(expected_type).pretty(std::usize::MAX),
+                )]),
+            CoreTypingMessage::InvalidVariantElim {
+                missing_labels,
+                unexpected_labels,
+            } => Diagnostic::bug()
+                .with_message("invalid variant elimination")
+                .with_notes({
+                    let mut notes = Vec::with_capacity(
+                        unexpected_labels.len() + if missing_labels.is_empty() { 0 } else { 1 },
+                    );
+
+                    for label in unexpected_labels {
+                        notes.push(format!("unexpected branch label `{}`", label));
+                    }
+
+                    if !missing_labels.is_empty() {
+                        notes.push(format!(
+                            "missing branches for the labels {} in this variant elimination",
+                            missing_labels
+                                .iter()
+                                // TODO: reduce string allocations
+                                .map(|label| format!("`{}`", label))
+                                .format(", "),
+                        ));
+                    }
+
+                    notes
+                }),
+            CoreTypingMessage::UnexpectedVariantElimHead { head_type } => Diagnostic::bug()
+                .with_message("unexpected head of variant elimination")
+                .with_notes(vec![format!(
+                    "expected a variant type, found `{}`",
+                    to_doc(head_type).pretty(std::usize::MAX),
+                )]),
+            CoreTypingMessage::InvalidInductiveType { duplicate_labels } => Diagnostic::bug()
+                .with_message("invalid inductive type")
+                .with_notes(
+                    duplicate_labels
+                        .iter()
+                        .map(|name| format!("constructor `{}` was defined more than once", name))
+                        .collect(),
+                ),
+            CoreTypingMessage::InvalidConstructorType { label } => Diagnostic::bug()
+                .with_message("invalid constructor type")
+                .with_notes(vec![format!(
+                    "the type of constructor `{}` is not strictly positive",
+                    label,
+                )]),
+            CoreTypingMessage::InvalidInductiveTerm {
+                label,
+                expected_count,
+                found_count,
+            } => Diagnostic::bug()
+                .with_message("invalid constructor application")
+                .with_notes(vec![format!(
+                    "constructor `{}` expected {} inputs, found {}",
+                    label, expected_count, found_count,
+                )]),
+            CoreTypingMessage::InvalidInductiveBranch {
+                label,
+                expected_count,
+                found_count,
+            } => Diagnostic::bug()
+                .with_message("invalid branch of eliminator")
+                .with_notes(vec![format!(
+                    "branch `{}` expected {} bindings, found {}",
+                    label, expected_count, found_count,
+                )]),
+            CoreTypingMessage::InvalidInductiveElim {
+                missing_labels,
+                unexpected_labels,
+            } => Diagnostic::bug()
+                .with_message("invalid eliminator")
+                .with_notes({
+                    let mut notes = Vec::with_capacity(
+                        unexpected_labels.len() + if missing_labels.is_empty() { 0 } else { 1 },
+                    );
+
+                    for label in unexpected_labels {
+                        notes.push(format!("unexpected branch constructor `{}`", label));
+                    }
+
+                    if !missing_labels.is_empty() {
+                        notes.push(format!(
+                            "missing branches for the constructors {} in this eliminator",
+                            missing_labels
+                                .iter()
+                                // TODO: reduce string allocations
+                                .map(|label| format!("`{}`", label))
+                                .format(", "),
+                        ));
+                    }
+
+                    notes
+                }),
+            CoreTypingMessage::UnexpectedInductiveElimHead { head_type } => Diagnostic::bug()
+                .with_message("unexpected head of eliminator")
+                .with_notes(vec![format!(
+                    "expected an inductive type, found `{}`",
+                    to_doc(head_type).pretty(std::usize::MAX),
+                )]),
+            CoreTypingMessage::InvalidEqTerm { expected_type } => Diagnostic::bug()
+                .with_message("invalid reflexivity proof")
+                .with_notes(vec![format!(
+                    "expected `{}`, found `refl`",
+                    to_doc(expected_type).pretty(std::usize::MAX),
+                )]),
+            CoreTypingMessage::UnexpectedEqElimHead { head_type } => Diagnostic::bug()
+                .with_message("unexpected head of transport expression")
+                .with_notes(vec![format!(
+                    "expected an equality type, found `{}`",
+                    to_doc(head_type).pretty(std::usize::MAX),
+                )]),
+            CoreTypingMessage::UnexpectedCodeStage => {
+                Diagnostic::bug().with_message("code used outside of a splice")
+            }
+            CoreTypingMessage::UnexpectedCodeElimHead { head_type } => Diagnostic::bug()
+                .with_message("unexpected head of splice")
+                .with_notes(vec![format!(
+                    "expected a code type, found `{}`",
+                    to_doc(head_type).pretty(std::usize::MAX),
+                )]),
             CoreTypingMessage::UnexpectedArrayTerm { expected_type } => Diagnostic::bug()
                 .with_message("unexpected array term")
                 .with_notes(vec![format!(
@@ -482,6 +776,27 @@ pub enum SurfaceToCoreMessage {
         location: Location,
         name: String,
     },
+    UnexpectedLocalStage {
+        location: Location,
+        name: String,
+        local_stage: u32,
+        current_stage: u32,
+    },
+    UnexpectedLocalUsage {
+        location: Location,
+        name: String,
+        usage: Usage,
+        found_usage: Usage,
+    },
+    ImportNotFound {
+        location: Location,
+        path: String,
+        error: String,
+    },
+    CyclicImport {
+        location: Location,
+        path: String,
+    },
     InvalidRecordType {
         duplicate_labels: Vec<(String, Location, Location)>,
     },
@@ -490,12 +805,31 @@ pub enum SurfaceToCoreMessage {
         missing_labels: Vec<String>,
         unexpected_labels: Vec<Location>,
     },
+    CyclicRecordEntryDependency {
+        label: String,
+        label_location: Location,
+        dependency_label: String,
+        dependency_location: Location,
+    },
     LabelNotFound {
         head_location: Location,
         label_location: Location,
         expected_label: String,
         head_type: surface::Term,
     },
+    UnexpectedErasedLabel {
+        label_location: Location,
+        label: String,
+    },
+    UnexpectedRecordUpdateHead {
+        head_location: Location,
+        head_type: surface::Term,
+    },
+    InvalidRecordUpdate {
+        location: Location,
+        duplicate_labels: Vec<(String, Location, Location)>,
+        dependent_labels: Vec<String>,
+    },
     TooManyInputsInFunctionTerm {
         unexpected_inputs: Vec<Location>,
     },
@@ -504,6 +838,80 @@ pub enum SurfaceToCoreMessage {
         head_type: surface::Term,
         unexpected_input_terms: Vec<Location>,
     },
+    UnexpectedImplicitInput {
+        input_location: Location,
+    },
+    ImplicitInputNotFound {
+        head_location: Location,
+        head_type: surface::Term,
+        input_name: String,
+        input_name_location: Location,
+    },
+    InvalidEnumType {
+        duplicate_labels: Vec<(String, Location, Location)>,
+    },
+    InvalidEnumTerm {
+        label_location: Location,
+        label: String,
+        expected_type: surface::Term,
+    },
+    InvalidEnumElim {
+        location: Location,
+        missing_labels: Vec<String>,
+        unexpected_labels: Vec<Location>,
+        duplicate_labels: Vec<(String, Location, Location)>,
+    },
+    UnexpectedEnumElimHead {
+        head_location: Location,
+        head_type: surface::Term,
+    },
+    UnexpectedBranchName {
+        name_location: Location,
+    },
+    InvalidVariantType {
+        duplicate_labels: Vec<(String, Location, Location)>,
+    },
+    InvalidVariantTerm {
+        label_location: Location,
+        label: String,
+        expected_type: surface::Term,
+    },
+    MismatchedBranchNames {
+        label_location: Location,
+        label: String,
+        expected_counts: Vec<usize>,
+        found_count: usize,
+    },
+    InvalidInductiveType {
+        duplicate_labels: Vec<(String, Location, Location)>,
+    },
+    UnsupportedRecursiveOccurrence {
+        location: Location,
+        type_name: String,
+    },
+    UnexpectedConstructorOutputType {
+        location: Location,
+        type_name: String,
+    },
+    UnexpectedInductiveElimHead {
+        head_location: Location,
+        head_type: surface::Term,
+    },
+    InvalidEqTerm {
+        location: Location,
+        expected_type: surface::Term,
+    },
+    UnexpectedEqElimHead {
+        head_location: Location,
+        head_type: surface::Term,
+    },
+    UnexpectedCodeStage {
+        location: Location,
+    },
+    UnexpectedCodeElimHead {
+        head_location: Location,
+        head_type: surface::Term,
+    },
     NoLiteralConversion {
         location: Location,
         expected_type: surface::Term,
@@ -517,10 +925,25 @@ pub enum SurfaceToCoreMessage {
         location: Location,
         expected_type: surface::Term,
     },
+    ListSequenceSynthesized {
+        location: Location,
+        list_type: surface::Term,
+        array_type: surface::Term,
+    },
     AmbiguousTerm {
         location: Location,
         term: AmbiguousTerm,
     },
+    UnsolvedMeta {
+        location: Location,
+        input_name: Option<String>,
+    },
+    HoleGoal {
+        location: Location,
+        name: String,
+        expected_type: surface::Term,
+        locals: Vec<(String, surface::Term)>,
+    },
     MismatchedTypes {
         location: Location,
         found_type: surface::Term,
@@ -554,6 +977,63 @@ impl SurfaceToCoreMessage {
                     primary(location).map(|label| label.with_message("not found in this scope")),
                 )),
 
+            SurfaceToCoreMessage::UnexpectedLocalStage {
+                location,
+                name,
+                local_stage,
+                current_stage,
+            } => Diagnostic::error()
+                .with_message(format!("cannot use `{}` at this stage", name))
+                .with_labels(option_to_vec(primary(location).map(|label| {
+                    label.with_message(format!(
+                        "bound at stage `{}`, but used at stage `{}`",
+                        local_stage, current_stage,
+                    ))
+                })))
+                .with_notes(vec![
+                    "runtime variables cannot be used at compile time".to_owned()
+                ]),
+            SurfaceToCoreMessage::UnexpectedLocalUsage {
+                location,
+                name,
+                usage,
+                found_usage,
+            } => Diagnostic::error()
+                .with_message(format!("`{}` was used an unexpected number of times", name))
+                .with_labels(option_to_vec(
+                    primary(location).map(|label| label.with_message("bound here")),
+                ))
+                .with_notes(vec![format!(
+                    "expected `{}` to be used {} at runtime, but it was used {}",
+                    name,
+                    match usage {
+                        Usage::Zero => "zero times",
+                        Usage::One | Usage::Many => "exactly once",
+                    },
+                    match found_usage {
+                        Usage::Zero => "zero times",
+                        Usage::One => "once",
+                        Usage::Many => "more than once",
+                    },
+                )]),
+
+            SurfaceToCoreMessage::ImportNotFound {
+                location,
+                path,
+                error,
+            } => Diagnostic::error()
+                .with_message(format!("could not import `{}`", path))
+                .with_labels(option_to_vec(
+                    primary(location).map(|label| label.with_message("failed to read file")),
+                ))
+                .with_notes(vec![error.clone()]),
+
+            SurfaceToCoreMessage::CyclicImport { location, path } => Diagnostic::error()
+                .with_message(format!("cyclic import of `{}`", path))
+                .with_labels(option_to_vec(primary(location).map(|label| {
+                    label.with_message("this file is already in the process of being imported")
+                }))),
+
             SurfaceToCoreMessage::InvalidRecordType { duplicate_labels } => Diagnostic::error()
                 .with_message("invalid record type")
                 .with_labels({
@@ -606,6 +1086,32 @@ impl SurfaceToCoreMessage {
                     labels
                 }),
 
+            SurfaceToCoreMessage::CyclicRecordEntryDependency {
+                label,
+                label_location,
+                dependency_label,
+                dependency_location,
+            } => Diagnostic::error()
+                .with_message(format!(
+                    "cyclic dependency between the entries `{}` and `{}`",
+                    label, dependency_label,
+                ))
+                .with_labels({
+                    let mut labels = Vec::with_capacity(2);
+
+                    labels.extend(primary(label_location).map(|label| {
+                        label.with_message(format!("this entry depends on `{}`", dependency_label))
+                    }));
+                    labels.extend(secondary(dependency_location).map(|dependency| {
+                        dependency.with_message(format!(
+                            "but this entry must come after `{}` in the record type",
+                            label,
+                        ))
+                    }));
+
+                    labels
+                }),
+
             SurfaceToCoreMessage::LabelNotFound {
                 head_location,
                 label_location,
@@ -629,6 +1135,68 @@ impl SurfaceToCoreMessage {
                         }))
                         .collect(),
                 ),
+            SurfaceToCoreMessage::UnexpectedErasedLabel {
+                label_location,
+                label,
+            } => Diagnostic::error()
+                .with_message(format!("cannot use erased entry `{}` at runtime", label))
+                .with_labels(option_to_vec(
+                    primary(label_location).map(|label| label.with_message("erased entry")),
+                ))
+                .with_notes(vec![
+                    "entries with a usage of `0` can only be used in types".to_owned(),
+                ]),
+
+            SurfaceToCoreMessage::UnexpectedRecordUpdateHead {
+                head_location,
+                head_type,
+            } => Diagnostic::error()
+                .with_message("cannot update a term that is not a record")
+                .with_labels(option_to_vec(primary(head_location).map(|label| {
+                    label.with_message(format!(
+                        // TODO: multi-line?
+                        "expected a record type, found `{}`",
+                        to_doc(&head_type).pretty(std::usize::MAX),
+                    ))
+                }))),
+
+            SurfaceToCoreMessage::InvalidRecordUpdate {
+                location,
+                duplicate_labels,
+                dependent_labels,
+            } => Diagnostic::error()
+                .with_message("invalid record update")
+                .with_labels({
+                    let mut labels = Vec::with_capacity(
+                        duplicate_labels.len() * 2
+                            + if dependent_labels.is_empty() { 0 } else { 1 },
+                    );
+
+                    for (label_name, label_location1, label_location2) in duplicate_labels {
+                        labels.extend(secondary(label_location1).map(|label| {
+                            label.with_message(format!("first use of `{}`", label_name))
+                        }));
+                        labels
+                            .extend(primary(label_location2).map(|label| {
+                                label.with_message("entry label used more than once")
+                            }));
+                    }
+
+                    if !dependent_labels.is_empty() {
+                        labels.extend(primary(location).map(|label| {
+                            label.with_message(format!(
+                                "the labels {} depend on updated entries, and must also be updated",
+                                dependent_labels
+                                    .iter()
+                                    // TODO: reduce string allocations
+                                    .map(|label| format!("`{}`", label))
+                                    .format(", "),
+                            ))
+                        }));
+                    }
+
+                    labels
+                }),
 
             SurfaceToCoreMessage::TooManyInputsInFunctionTerm { unexpected_inputs } => {
                 Diagnostic::error()
@@ -666,6 +1234,287 @@ impl SurfaceToCoreMessage {
                         }))
                         .collect(),
                 ),
+            SurfaceToCoreMessage::UnexpectedImplicitInput { input_location } => Diagnostic::error()
+                .with_message("unexpected implicit input in function term")
+                .with_labels(
+                    primary(input_location)
+                        .map(|label| label.with_message("expected an explicit input"))
+                        .into_iter()
+                        .collect(),
+                ),
+            SurfaceToCoreMessage::ImplicitInputNotFound {
+                head_location,
+                head_type,
+                input_name,
+                input_name_location,
+            } => Diagnostic::error()
+                .with_message(format!("no implicit input named `{}`", input_name))
+                .with_labels(
+                    primary(input_name_location)
+                        .map(|label| label.with_message("unexpected implicit input"))
+                        .into_iter()
+                        .chain(secondary(head_location).map(|label| {
+                            label.with_message(format!(
+                                "the function has type `{}`",
+                                to_doc(&head_type).pretty(std::usize::MAX),
+                            ))
+                        }))
+                        .collect(),
+                ),
+
+            SurfaceToCoreMessage::InvalidEnumType { duplicate_labels } => Diagnostic::error()
+                .with_message("invalid enumeration type")
+                .with_labels({
+                    let mut labels = Vec::with_capacity(duplicate_labels.len() * 2);
+
+                    for (label_name, label_location1, label_location2) in duplicate_labels {
+                        labels.extend(secondary(label_location1).map(|label| {
+                            label.with_message(format!("first use of `{}`", label_name))
+                        }));
+                        labels.extend(
+                            primary(label_location2)
+                                .map(|label| label.with_message("label used more than once")),
+                        );
+                    }
+
+                    labels
+                }),
+
+            SurfaceToCoreMessage::InvalidEnumTerm {
+                label_location,
+                label,
+                expected_type,
+            } => Diagnostic::error()
+                .with_message(format!("no enumeration label `{}`", label))
+                .with_labels(option_to_vec(primary(label_location).map(|label| {
+                    label.with_message(format!(
+                        "not found in `{}`",
+                        to_doc(&expected_type).pretty(std::usize::MAX),
+                    ))
+                }))),
+
+            SurfaceToCoreMessage::InvalidEnumElim {
+                location,
+                missing_labels,
+                unexpected_labels,
+                duplicate_labels,
+            } => {
+                Diagnostic::error()
+                    .with_message("invalid match expression")
+                    .with_labels({
+                        let mut labels = Vec::with_capacity(
+                            unexpected_labels.len()
+                                + duplicate_labels.len() * 2
+                                + if missing_labels.is_empty() { 0 } else { 1 },
+                        );
+
+                        for label_location in unexpected_labels {
+                            labels.extend(
+                                primary(label_location)
+                                    .map(|label| label.with_message("unexpected branch label")),
+                            );
+                        }
+
+                        for (label_name, label_location1, label_location2) in duplicate_labels {
+                            labels.extend(secondary(label_location1).map(|label| {
+                                label.with_message(format!("first use of `{}`", label_name))
+                            }));
+                            labels.extend(primary(label_location2).map(|label| {
+                                label.with_message("branch label used more than once")
+                            }));
+                        }
+
+                        if !missing_labels.is_empty() {
+                            labels.extend(primary(location).map(|label| {
+                                label.with_message(format!(
+                                    "missing branches for the labels {} in this match expression",
+                                    missing_labels
+                                        .iter()
+                                        // TODO: reduce string allocations
+                                        .map(|label| format!("`{}`", label))
+                                        .format(", "),
+                                ))
+                            }));
+                        }
+
+                        labels
+                    })
+            }
+
+            SurfaceToCoreMessage::UnexpectedEnumElimHead {
+                head_location,
+                head_type,
+            } => Diagnostic::error()
+                .with_message("cannot match on a term that is not an enumeration or variant")
+                .with_labels(option_to_vec(primary(head_location).map(|label| {
+                    label.with_message(format!(
+                        // TODO: multi-line?
+                        "expected an enumeration or variant type, found `{}`",
+                        to_doc(&head_type).pretty(std::usize::MAX),
+                    ))
+                }))),
+
+            SurfaceToCoreMessage::UnexpectedBranchName { name_location } => Diagnostic::error()
+                .with_message("unexpected payload name in match branch")
+                .with_labels(option_to_vec(
+                    primary(name_location).map(|label| label.with_message("unexpected name")),
+                ))
+                .with_notes(vec![
+                    "only branches on variant types can bind a payload".to_owned()
+                ]),
+
+            SurfaceToCoreMessage::InvalidVariantType { duplicate_labels } => Diagnostic::error()
+                .with_message("invalid variant type")
+                .with_labels({
+                    let mut labels = Vec::with_capacity(duplicate_labels.len() * 2);
+
+                    for (label_name, label_location1, label_location2) in duplicate_labels {
+                        labels.extend(secondary(label_location1).map(|label| {
+                            label.with_message(format!("first use of `{}`", label_name))
+                        }));
+                        labels.extend(
+                            primary(label_location2)
+                                .map(|label| label.with_message("label used more than once")),
+                        );
+                    }
+
+                    labels
+                }),
+
+            SurfaceToCoreMessage::InvalidVariantTerm {
+                label_location,
+                label,
+                expected_type,
+            } => Diagnostic::error()
+                .with_message(format!("no variant labelled `{}`", label))
+                .with_labels(option_to_vec(primary(label_location).map(|label| {
+                    label.with_message(format!(
+                        // TODO: multi-line?
+                        "not found in `{}`",
+                        to_doc(&expected_type).pretty(std::usize::MAX),
+                    ))
+                }))),
+
+            SurfaceToCoreMessage::MismatchedBranchNames {
+                label_location,
+                label,
+                expected_counts,
+                found_count,
+            } => Diagnostic::error()
+                .with_message(format!("unexpected number of names bound by `{}`", label))
+                .with_labels(option_to_vec(primary(label_location).map(|label| {
+                    label.with_message(format!(
+                        "expected {} names, found {}",
+                        expected_counts.iter().format(" or "),
+                        found_count,
+                    ))
+                }))),
+
+            SurfaceToCoreMessage::InvalidInductiveType { duplicate_labels } => {
+                Diagnostic::error()
+                    .with_message("invalid inductive type")
+                    .with_labels({
+                        let mut labels = Vec::with_capacity(duplicate_labels.len() * 2);
+
+                        for (label_name, label_location1, label_location2) in duplicate_labels {
+                            labels.extend(secondary(label_location1).map(|label| {
+                                label.with_message(format!("first use of `{}`", label_name))
+                            }));
+                            labels.extend(primary(label_location2).map(|label| {
+                                label.with_message("constructor defined more than once")
+                            }));
+                        }
+
+                        labels
+                    })
+            }
+
+            SurfaceToCoreMessage::UnsupportedRecursiveOccurrence {
+                location,
+                type_name,
+            } => Diagnostic::error()
+                .with_message(format!("unsupported occurrence of `{}`", type_name))
+                .with_labels(option_to_vec(primary(location).map(|label| {
+                    label.with_message(format!("`{}` is used within this input type", type_name))
+                })))
+                .with_notes(vec![format!(
+                    "`{}` can only be used directly as the type of a constructor input",
+                    type_name,
+                )]),
+
+            SurfaceToCoreMessage::UnexpectedConstructorOutputType {
+                location,
+                type_name,
+            } => Diagnostic::error()
+                .with_message("unexpected output type of constructor")
+                .with_labels(option_to_vec(primary(location).map(|label| {
+                    label.with_message(format!("expected `{}`", type_name))
+                })))
+                .with_notes(vec![format!(
+                    "constructors must return the type being defined, `{}`",
+                    type_name,
+                )]),
+
+            SurfaceToCoreMessage::UnexpectedInductiveElimHead {
+                head_location,
+                head_type,
+            } => Diagnostic::error()
+                .with_message("cannot eliminate a term that is not of an inductive type")
+                .with_labels(option_to_vec(primary(head_location).map(|label| {
+                    label.with_message(format!(
+                        // TODO: multi-line?
+                        "expected an inductive type, found `{}`",
+                        to_doc(&head_type).pretty(std::usize::MAX),
+                    ))
+                }))),
+
+            SurfaceToCoreMessage::InvalidEqTerm {
+                location,
+                expected_type,
+            } => Diagnostic::error()
+                .with_message("invalid reflexivity proof")
+                .with_labels(option_to_vec(primary(location).map(|label| {
+                    label.with_message(format!(
+                        // TODO: multi-line?
+                        "expected `{}`, found `refl`",
+                        to_doc(&expected_type).pretty(std::usize::MAX),
+                    ))
+                })))
+                .with_notes(vec![
+                    "`refl` can only prove equalities between computationally equal terms"
+                        .to_owned(),
+                ]),
+
+            SurfaceToCoreMessage::UnexpectedEqElimHead {
+                head_location,
+                head_type,
+            } => Diagnostic::error()
+                .with_message("cannot transport along a term that is not an equality proof")
+                .with_labels(option_to_vec(primary(head_location).map(|label| {
+                    label.with_message(format!(
+                        // TODO: multi-line?
+                        "expected an equality type, found `{}`",
+                        to_doc(&head_type).pretty(std::usize::MAX),
+                    ))
+                }))),
+
+            SurfaceToCoreMessage::UnexpectedCodeStage { location } => Diagnostic::error()
+                .with_message("code can only be used inside a splice")
+                .with_labels(option_to_vec(
+                    primary(location).map(|label| label.with_message("used at runtime")),
+                )),
+            SurfaceToCoreMessage::UnexpectedCodeElimHead {
+                head_location,
+                head_type,
+            } => Diagnostic::error()
+                .with_message("cannot splice a term that is not code")
+                .with_labels(option_to_vec(primary(head_location).map(|label| {
+                    label.with_message(format!(
+                        // TODO: multi-line?
+                        "expected a code type, found `{}`",
+                        to_doc(&head_type).pretty(std::usize::MAX),
+                    ))
+                }))),
 
             SurfaceToCoreMessage::NoLiteralConversion {
                 location,
@@ -708,12 +1557,65 @@ impl SurfaceToCoreMessage {
                     ))
                 }))),
 
+            SurfaceToCoreMessage::ListSequenceSynthesized {
+                location,
+                list_type,
+                array_type,
+            } => Diagnostic::help()
+                .with_message("sequence elaborated as a list")
+                .with_labels(option_to_vec(primary(location).map(|label| {
+                    label.with_message(format!(
+                        "found `{}`",
+                        to_doc(&list_type).pretty(std::usize::MAX),
+                    ))
+                })))
+                .with_notes(vec![format!(
+                    "add the annotation `: {}` if an array was intended",
+                    to_doc(&array_type).pretty(std::usize::MAX),
+                )]),
+
             SurfaceToCoreMessage::AmbiguousTerm { location, term } => Diagnostic::error()
                 .with_message(format!("ambiguous {}", term.description()))
                 .with_labels(option_to_vec(
                     primary(location).map(|label| label.with_message("type annotations needed")),
                 )),
 
+            SurfaceToCoreMessage::UnsolvedMeta {
+                location,
+                input_name,
+            } => Diagnostic::error()
+                .with_message(match input_name {
+                    Some(name) => format!("unable to infer the implicit input `{}`", name),
+                    None => "unable to infer an implicit input".to_owned(),
+                })
+                .with_labels(option_to_vec(
+                    primary(location).map(|label| label.with_message("type annotations needed")),
+                )),
+
+            SurfaceToCoreMessage::HoleGoal {
+                location,
+                name,
+                expected_type,
+                locals,
+            } => Diagnostic::note()
+                .with_message(format!("found the hole `?{}`", name))
+                .with_labels(option_to_vec(primary(location).map(|label| {
+                    label.with_message(format!(
+                        // TODO: multi-line?
+                        "expected `{}`",
+                        to_doc(&expected_type).pretty(std::usize::MAX),
+                    ))
+                })))
+                .with_notes(match locals.is_empty() {
+                    true => Vec::new(),
+                    false => vec![std::iter::once("locals in scope:".to_owned())
+                        .chain(locals.iter().map(|(name, r#type)| {
+                            format!("  {} : {}", name, to_doc(r#type).pretty(std::usize::MAX),)
+                        }))
+                        .collect::<Vec<_>>()
+                        .join("\n")],
+                }),
+
             SurfaceToCoreMessage::MismatchedTypes {
                 location,
                 found_type,
@@ -1,10 +1,12 @@
 //! Integration tests against the language samples directory.
 
 use codespan_reporting::files::SimpleFiles;
-use codespan_reporting::term::termcolor::{BufferedStandardStream, ColorChoice};
+use codespan_reporting::term::termcolor::{Buffer, BufferedStandardStream, ColorChoice};
 use pikelet::lang::{core, surface};
-use pikelet::pass::surface_to_core;
+use pikelet::pass::{staging, surface_to_core, surface_to_pretty};
+use std::cell::RefCell;
 use std::io::Write;
+use std::sync::Arc;
 
 fn run_test(path: &str, source: &str) -> Result<(), Box<dyn std::error::Error>> {
     let mut is_failed = false;
@@ -13,31 +15,33 @@ fn run_test(path: &str, source: &str) -> Result<(), Box<dyn std::error::Error>>
     let globals = core::Globals::default();
     let pretty_alloc = pretty::BoxAllocator;
     let config = codespan_reporting::term::Config::default();
-    let mut files = SimpleFiles::new();
+    let files = RefCell::new(SimpleFiles::new());
     let (messages_tx, messages_rx) = crossbeam_channel::unbounded();
 
-    let file_id = files.add(path, source);
-    let file = files.get(file_id).unwrap();
-    let surface_term = surface::Term::from_str(file_id, file.source(), &messages_tx);
+    let file_id = files.borrow_mut().add(path.to_owned(), source.to_owned());
+    let surface_term = surface::Term::from_str(file_id, source, &messages_tx);
     if !messages_rx.is_empty() {
         is_failed = true;
         writeln!(writer, "surface::Term::from_str messages:")?;
         for message in messages_rx.try_iter() {
             let diagnostic = message.to_diagnostic(&pretty_alloc);
-            codespan_reporting::term::emit(&mut writer, &config, &files, &diagnostic)?;
+            codespan_reporting::term::emit(&mut writer, &config, &*files.borrow(), &diagnostic)?;
             writer.flush()?;
         }
         writeln!(writer)?;
     }
 
-    let mut state = surface_to_core::State::new(&globals, messages_tx.clone());
-    let (core_term, r#type) = state.synth_type(&surface_term);
+    let mut state = surface_to_core::State::new(&globals, &files, messages_tx.clone());
+    let (core_term, r#type) = state.synth_top_level_type(&surface_term);
     if !messages_rx.is_empty() {
         is_failed = true;
-        writeln!(writer, "surface_to_core::State::synth_type messages:")?;
+        writeln!(
+            writer,
+            "surface_to_core::State::synth_top_level_type messages:"
+        )?;
         for message in messages_rx.try_iter() {
             let diagnostic = message.to_diagnostic(&pretty_alloc);
-            codespan_reporting::term::emit(&mut writer, &config, &files, &diagnostic)?;
+            codespan_reporting::term::emit(&mut writer, &config, &*files.borrow(), &diagnostic)?;
             writer.flush()?;
         }
         writeln!(writer)?;
@@ -51,7 +55,7 @@ fn run_test(path: &str, source: &str) -> Result<(), Box<dyn std::error::Error>>
         writeln!(writer, "core::typing::State::synth_term messages:")?;
         for message in messages_rx.try_iter() {
             let diagnostic = message.to_diagnostic(&pretty_alloc);
-            codespan_reporting::term::emit(&mut writer, &config, &files, &diagnostic)?;
+            codespan_reporting::term::emit(&mut writer, &config, &*files.borrow(), &diagnostic)?;
             writer.flush()?;
         }
         writeln!(writer)?;
@@ -63,7 +67,41 @@ fn run_test(path: &str, source: &str) -> Result<(), Box<dyn std::error::Error>>
         writeln!(writer, "core::typing::State::check_term messages:")?;
         for message in messages_rx.try_iter() {
             let diagnostic = message.to_diagnostic(&pretty_alloc);
-            codespan_reporting::term::emit(&mut writer, &config, &files, &diagnostic)?;
+            codespan_reporting::term::emit(&mut writer, &config, &*files.borrow(), &diagnostic)?;
+            writer.flush()?;
+        }
+        writeln!(writer)?;
+    }
+
+    // The type is read back and checked again, as it is when files are
+    // checked with `--validate-core`.
+    let core_type = state.read_back_value(&r#type);
+    state.synth_type(&core::Term::generated(core::TermData::Ann(
+        Arc::new(core_term.clone()),
+        Arc::new(core_type),
+    )));
+    if !messages_rx.is_empty() {
+        is_failed = true;
+        writeln!(
+            writer,
+            "core::typing::State::synth_term messages (read back):"
+        )?;
+        for message in messages_rx.try_iter() {
+            let diagnostic = message.to_diagnostic(&pretty_alloc);
+            codespan_reporting::term::emit(&mut writer, &config, &*files.borrow(), &diagnostic)?;
+            writer.flush()?;
+        }
+        writeln!(writer)?;
+    }
+
+    let staged_term = staging::stage_term(&globals, &core_term);
+    state.check_type(&staged_term, &r#type);
+    if !messages_rx.is_empty() {
+        is_failed = true;
+        writeln!(writer, "core::typing::State::check_term messages (staged):")?;
+        for message in messages_rx.try_iter() {
+            let diagnostic = message.to_diagnostic(&pretty_alloc);
+            codespan_reporting::term::emit(&mut writer, &config, &*files.borrow(), &diagnostic)?;
             writer.flush()?;
         }
         writeln!(writer)?;
@@ -76,25 +114,222 @@ fn run_test(path: &str, source: &str) -> Result<(), Box<dyn std::error::Error>>
     }
 }
 
+/// Elaborate a source, checking that the rendered diagnostics contain each of
+/// the expected messages, and returning the synthesized type along with the
+/// rendered diagnostics.
+fn run_diagnostics_test(
+    path: &str,
+    source: &str,
+    expected_messages: &[&str],
+) -> Result<(String, String), Box<dyn std::error::Error>> {
+    let mut writer = Buffer::no_color();
+    let globals = core::Globals::default();
+    let pretty_alloc = pretty::BoxAllocator;
+    let config = codespan_reporting::term::Config::default();
+    let files = RefCell::new(SimpleFiles::new());
+    let (messages_tx, messages_rx) = crossbeam_channel::unbounded();
+
+    let file_id = files.borrow_mut().add(path.to_owned(), source.to_owned());
+    let surface_term = surface::Term::from_str(file_id, source, &messages_tx);
+    let mut state = surface_to_core::State::new(&globals, &files, messages_tx.clone());
+    let (_, r#type) = state.synth_top_level_type(&surface_term);
+    let r#type = state.read_back_to_surface_term(&r#type);
+    let r#type = surface_to_pretty::from_term(&pretty_alloc, &r#type).1;
+    let r#type = r#type.pretty(std::usize::MAX).to_string();
+
+    for message in messages_rx.try_iter() {
+        let diagnostic = message.to_diagnostic(&pretty_alloc);
+        codespan_reporting::term::emit(&mut writer, &config, &*files.borrow(), &diagnostic)?;
+    }
+
+    let output = String::from_utf8(writer.into_inner())?;
+    let missing_messages = (expected_messages.iter())
+        .filter(|message| !output.contains(*message))
+        .collect::<Vec<_>>();
+    if !missing_messages.is_empty() {
+        return Err(format!(
+            "expected the messages {:?} in the output:\n{}",
+            missing_messages, output,
+        )
+        .into());
+    }
+
+    Ok((r#type, output))
+}
+
 macro_rules! example_test {
     ($test_name:ident, $path:literal) => {
         #[test]
         fn $test_name() -> Result<(), Box<dyn std::error::Error>> {
             run_test(
-                concat!("examples/", $path, ".pi"),
+                concat!("../examples/", $path, ".pi"),
                 include_str!(concat!("../../examples/", $path, ".pi")),
             )
         }
     };
 }
 
+example_test!(arithmetic, "arithmetic");
+example_test!(booleans, "booleans");
 example_test!(comments, "comments");
+example_test!(enums, "enums");
+example_test!(equality, "equality");
+example_test!(eta_conversion, "eta-conversion");
 example_test!(functions, "functions");
 example_test!(hello_world, "hello-world");
+example_test!(imports, "imports");
+example_test!(inductive_types, "inductive-types");
+example_test!(let_, "let");
 example_test!(literals, "literals");
+example_test!(options, "options");
 example_test!(prelude, "prelude");
+example_test!(record_defaults, "record-defaults");
 example_test!(record_mesh, "record-mesh");
+example_test!(record_subtyping, "record-subtyping");
 example_test!(record_term_deps, "record-term-deps");
+example_test!(record_term_order, "record-term-order");
+example_test!(record_term_synth, "record-term-synth");
 example_test!(record_type_deps, "record-type-deps");
+example_test!(record_update, "record-update");
+example_test!(recursion, "recursion");
+example_test!(staging, "staging");
+example_test!(strings, "strings");
 example_test!(universes, "universes");
+example_test!(usages, "usages");
+example_test!(variants, "variants");
 example_test!(window_settings, "window-settings");
+
+macro_rules! diagnostics_test {
+    ($test_name:ident, $source:literal, [$($message:literal),* $(,)?]) => {
+        #[test]
+        fn $test_name() -> Result<(), Box<dyn std::error::Error>> {
+            run_diagnostics_test(stringify!($test_name), $source, &[$($message),*])?;
+            Ok(())
+        }
+    };
+}
+
+diagnostics_test!(
+    unsolved_implicit_input,
+    "let const : Fun {A : Type} -> S32 -> S32 = fun a => a in const 1",
+    [
+        "error: unable to infer the implicit input `A`",
+        "^ type annotations needed",
+    ]
+);
+diagnostics_test!(
+    erased_local_in_implicit_input,
+    "let value-of : Fun {A : Type} {a : A} -> Eq A a a -> A = fun {A} {a} p => a in
+    fun x => value-of (refl : Eq S32 x x) : Fun (0 x : S32) -> S32",
+    [
+        "error: `x` was used an unexpected number of times",
+        "expected `x` to be used zero times at runtime, but it was used more than once",
+    ]
+);
+
+#[test]
+fn hole_goal() -> Result<(), Box<dyn std::error::Error>> {
+    let (_, output) = run_diagnostics_test(
+        "hole_goal",
+        "(fun x y => ?todo) : S32 -> String -> Array 3 S32",
+        &[
+            "note: found the hole `?todo`",
+            "^^^^^ expected `Array 3 S32`",
+            "x : S32",
+            "y : String",
+        ],
+    )?;
+    assert!(
+        !output.contains("error"),
+        "unexpected error in:\n{}",
+        output
+    );
+    Ok(())
+}
+diagnostics_test!(
+    cyclic_record_entry_dependency,
+    "record { b = 1, a = b } : Record { a : U32, b : U32 }",
+    [
+        "error: cyclic dependency between the entries `a` and `b`",
+        "this entry depends on `b`",
+        "but this entry must come after `a` in the record type",
+    ]
+);
+diagnostics_test!(
+    linear_local_used_twice,
+    "fun A a => record { fst = a, snd = a }
+        : Fun (0 A : Type) (1 a : A) -> Record { fst : A, snd : A }",
+    [
+        "error: `a` was used an unexpected number of times",
+        "expected `a` to be used exactly once at runtime, but it was used more than once",
+    ]
+);
+diagnostics_test!(
+    linear_local_unused,
+    "fun A a => record {} : Fun (0 A : Type) (1 a : A) -> Record {}",
+    [
+        "error: `a` was used an unexpected number of times",
+        "expected `a` to be used exactly once at runtime, but it was used zero times",
+    ]
+);
+diagnostics_test!(
+    erased_local_used,
+    "fun A a => A : Fun (0 A : Type) (a : A) -> Type",
+    [
+        "error: `A` was used an unexpected number of times",
+        "expected `A` to be used zero times at runtime, but it was used once",
+    ]
+);
+diagnostics_test!(
+    erased_entry_used,
+    "fun vec => vec.Elem : Fun (vec : Record { 0 Elem : Type, len : U32 }) -> Type",
+    [
+        "error: cannot use erased entry `Elem` at runtime",
+        "entries with a usage of `0` can only be used in types",
+    ]
+);
+diagnostics_test!(
+    invalid_enum_term,
+    "enum purple : Enum { red, green, blue }",
+    [
+        "error: no enumeration label `purple`",
+        "not found in `Enum { red, green, blue }`",
+    ]
+);
+
+#[test]
+fn synth_annotated_function_term() -> Result<(), Box<dyn std::error::Error>> {
+    let (r#type, output) =
+        run_diagnostics_test("synth_annotated_function_term", "fun (a : S32) => a", &[])?;
+    assert_eq!(r#type, "Fun (a : S32) -> S32");
+    assert!(output.is_empty(), "unexpected messages:\n{}", output);
+
+    let (r#type, output) = run_diagnostics_test(
+        "synth_annotated_function_term",
+        "fun (A : Type) (a : A) => a",
+        &[],
+    )?;
+    assert_eq!(r#type, "Fun (A : Type) -> Fun (a : A) -> A");
+    assert!(output.is_empty(), "unexpected messages:\n{}", output);
+    Ok(())
+}
+
+#[test]
+fn synth_sequence_term() -> Result<(), Box<dyn std::error::Error>> {
+    let (r#type, output) = run_diagnostics_test(
+        "synth_sequence_term",
+        "[1, 2, 3]",
+        &[
+            "help: sequence elaborated as a list",
+            "^^^^^^^^^ found `List S32`",
+            "add the annotation `: Array 3 S32` if an array was intended",
+        ],
+    )?;
+    assert_eq!(r#type, "List S32");
+    assert!(
+        !output.contains("error"),
+        "unexpected error in:\n{}",
+        output
+    );
+    Ok(())
+}